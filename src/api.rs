@@ -1,15 +1,25 @@
 use reqwest::Client;
 use serde_json::Value;
 use serenity::{
-    model::prelude::interaction::application_command::ApplicationCommandInteraction, prelude::*,
+    model::{
+        channel::AttachmentType,
+        prelude::interaction::{
+            application_command::ApplicationCommandInteraction, InteractionResponseType,
+        },
+    },
+    prelude::*,
 };
 use std::time::Duration;
 
-use crate::formatters::{
-    format_auction_response_with_page, format_leaderboard_response, format_stats_response,
+use crate::locale::Format;
+use crate::models::{
+    AuctionPage, Leaderboard, PlayerLookup, PlayerStats, Presence, StatsView, TransactionPage,
 };
-use crate::response_formatters::format_api_response;
+use crate::render::{leaderboard_not_found, render_or_message, stats_not_found, Page, Render};
 
+/// Fetches `path` and sends the page `render` builds from the response,
+/// titled `friendly_title` unless the page sets its own title.
+#[allow(clippy::too_many_arguments)]
 pub async fn send_api(
     cmd: &ApplicationCommandInteraction,
     ctx: &Context,
@@ -17,6 +27,8 @@ pub async fn send_api(
     donut_key: &str,
     path: &str,
     friendly_title: &str,
+    fmt: &Format,
+    render: impl FnOnce(&Value) -> Page,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Immediately ack with a deferred response (allows more than 3s processing)
     cmd.create_interaction_response(&ctx.http, |resp| resp.kind(serenity::model::prelude::interaction::InteractionResponseType::DeferredChannelMessageWithSource)).await?;
@@ -36,7 +48,7 @@ pub async fn send_api(
             let mut embed = serenity::builder::CreateEmbed::default();
             embed
                 .title(friendly_title)
-                .description("🔴 Player is offline")
+                .description(fmt.t("api.offline"))
                 .color(crate::constants::EMBED_COLOR_ACCENT);
 
             cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
@@ -45,21 +57,20 @@ pub async fn send_api(
         }
 
         let error_msg = match status.as_u16() {
-            401 => "❌ **Authentication Error**: Invalid API key".to_string(),
-            403 => "❌ **Access Forbidden**: You don't have permission to access this endpoint"
-                .to_string(),
-            404 => "❌ **Not Found**: The requested resource doesn't exist".to_string(),
-            429 => "❌ **Rate Limited**: Too many requests, please try again later".to_string(),
-            500..=599 => "❌ **Server Error**: The server encountered an error".to_string(),
-            _ => format!("❌ **Error**: API returned HTTP {}", status),
+            401 => fmt.t("api.unauthorized").to_string(),
+            403 => fmt.t("api.forbidden").to_string(),
+            404 => fmt.t("api.not_found").to_string(),
+            429 => fmt.t("api.rate_limited").to_string(),
+            500..=599 => fmt.t("api.server_error").to_string(),
+            _ => fmt.tf("api.http_error", &[&status]),
         };
 
         let mut embed = serenity::builder::CreateEmbed::default();
         embed
-            .title("DonutSMP API Error")
+            .title(fmt.t("api.error_title"))
             .description(error_msg)
             .color(crate::constants::EMBED_COLOR_ERROR)
-            .footer(|f| f.text(format!("Status: {} | Path: {}", status, path)));
+            .footer(|f| f.text(fmt.tf("api.error_footer", &[&status, &path])));
 
         cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
             .await?;
@@ -68,51 +79,122 @@ pub async fn send_api(
 
     let json: Value = res.json().await?;
 
-    let mut embed = serenity::builder::CreateEmbed::default();
-    embed
-        .title(friendly_title)
-        .color(crate::constants::EMBED_COLOR_ACCENT);
+    let mut page = render(&json);
+    if page.title.is_none() {
+        page.title = Some(friendly_title.to_string());
+    }
+    let embed = page.to_embed();
+    cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
+        .await?;
 
-    let formatted = format_api_response(&json, path, &mut embed);
+    Ok(())
+}
 
-    if formatted {
-        cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
-            .await?;
-    } else {
-        let pretty = serde_json::to_string_pretty(&json)?;
-        if pretty.len() <= 4000 {
-            embed.description(format!("```json\n{}\n```", pretty));
-            cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
-                .await?;
-        } else {
-            let filename = format!("{}.json", path.trim_start_matches("/").replace("/", "_"));
-            cmd.create_followup_message(&ctx.http, |m| {
-                m.add_file((pretty.as_bytes(), filename.as_str()))
-                    .content(format!(
-                        "{} — Response attached ({} bytes)",
-                        friendly_title,
-                        pretty.len()
-                    ))
-            })
-            .await?;
+/// Looks up whether `ign` is online and where.
+pub async fn fetch_presence(client: &Client, donut_key: &str, ign: &str) -> Presence {
+    let url = format!(
+        "https://api.donutsmp.net/v1/lookup/{}",
+        ign.replace(" ", "%20")
+    );
+    let res = client
+        .get(&url)
+        .bearer_auth(donut_key)
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await;
+    match res {
+        Ok(resp) if resp.status().is_success() => {
+            let json: Value = resp.json().await.unwrap_or_default();
+            Presence::Online {
+                location: PlayerLookup::from_json(&json, ign).and_then(|l| l.location),
+            }
         }
+        Ok(resp) if resp.status().as_u16() == 500 => Presence::Offline,
+        _ => Presence::Unknown,
     }
+}
 
-    Ok(())
+/// Fetches `ign`'s stats, or `None` when the API has none or can't be reached.
+pub async fn fetch_stats(client: &Client, donut_key: &str, ign: &str) -> Option<PlayerStats> {
+    let url = format!(
+        "https://api.donutsmp.net/v1/stats/{}",
+        ign.replace(" ", "%20")
+    );
+    let res = client
+        .get(&url)
+        .bearer_auth(donut_key)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .ok()?;
+    if !res.status().is_success() {
+        return None;
+    }
+    let json: Value = res.json().await.ok()?;
+    let stats = PlayerStats::from_json(&json, ign)?;
+    if let Err(e) = crate::players::record_stats(&stats) {
+        tracing::warn!("Failed to record stats snapshot: {:?}", e);
+    }
+    Some(stats)
 }
 
-pub async fn auction_embed(
+/// A `/v1/leaderboards/{kind}/{page}` response, or `None` when the API
+/// can't be reached or answers with an error.
+pub async fn fetch_leaderboard(
+    client: &Client,
+    donut_key: &str,
+    kind: &str,
+    page: u32,
+) -> Option<Value> {
+    let url = format!("https://api.donutsmp.net/v1/leaderboards/{}/{}", kind, page);
+    let res = client
+        .get(&url)
+        .bearer_auth(donut_key)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .ok()?;
+    if !res.status().is_success() {
+        return None;
+    }
+    res.json().await.ok()
+}
+
+/// Whether the API knows `ign`, or `None` when it can't tell (unreachable,
+/// rate limited or refusing the key).
+pub async fn player_exists(client: &Client, donut_key: &str, ign: &str) -> Option<bool> {
+    let url = format!(
+        "https://api.donutsmp.net/v1/stats/{}",
+        ign.replace(" ", "%20")
+    );
+    let res = client
+        .get(&url)
+        .bearer_auth(donut_key)
+        .timeout(Duration::from_secs(15))
+        .send()
+        .await
+        .ok()?;
+    let status = res.status();
+    if status.is_success() {
+        let json: Value = res.json().await.ok()?;
+        return Some(PlayerStats::from_json(&json, ign).is_some());
+    }
+    // The API answers unknown players with a 400, 404 or a plain 500; a
+    // rate limit or a rejected key says nothing about the player
+    match status.as_u16() {
+        400 | 404 | 500 => Some(false),
+        _ => None,
+    }
+}
+
+/// Fetches an auction list/transactions page, using POST when searching or sorting.
+pub async fn fetch_auction_json(
     client: &reqwest::Client,
     donut_key: &str,
     path: &str,
-    title: &str,
     search: Option<&str>,
     sort: Option<&str>,
-    current_page: u32,
-) -> Result<
-    (serenity::builder::CreateEmbed, Vec<serde_json::Value>),
-    Box<dyn std::error::Error + Send + Sync>,
-> {
+) -> Result<serde_json::Value, Box<dyn std::error::Error + Send + Sync>> {
     let url = format!("https://api.donutsmp.net{}", path);
 
     // Build request based on whether we have search/sort parameters
@@ -146,44 +228,60 @@ pub async fn auction_embed(
 
     let response_text = response.text().await?;
     let json: serde_json::Value = serde_json::from_str(&response_text)?;
+    if let Some(items) = json.get("result").and_then(|r| r.as_array()) {
+        if let Err(e) = crate::players::record_auction_items(items) {
+            tracing::warn!("Failed to record auction players: {:?}", e);
+        }
+    }
+    Ok(json)
+}
 
-    let mut embed = serenity::builder::CreateEmbed::default();
-    embed
-        .title(title)
-        .color(crate::constants::EMBED_COLOR_ACCENT);
+#[allow(clippy::too_many_arguments)]
+pub async fn auction_embed(
+    ctx: &Context,
+    client: &reqwest::Client,
+    donut_key: &str,
+    path: &str,
+    title: &str,
+    search: Option<&str>,
+    sort: Option<&str>,
+    current_page: u32,
+    fmt: &Format,
+) -> Result<
+    (serenity::builder::CreateEmbed, Vec<serde_json::Value>),
+    Box<dyn std::error::Error + Send + Sync>,
+> {
+    let json = fetch_auction_json(client, donut_key, path, search, sort).await?;
 
-    if let Some(result) = json.get("result") {
-        if let Some(items) = result.as_array() {
-            format_auction_response_with_page(items, &mut embed, current_page);
-        } else {
-            embed.description("❌ No items found or invalid response format");
+    // Transactions feed the local price history; listings are scored against it
+    if path.starts_with("/v1/auction/transactions/") {
+        if let Err(e) = crate::market::ingest(&crate::auction::parse_sales(&json)) {
+            tracing::warn!("Failed to store market history: {:?}", e);
         }
-    } else if let Some(status) = json.get("status") {
-        embed.description(format!(
-            "❌ API Error: {}",
-            status.as_str().unwrap_or("Unknown error")
-        ));
     } else {
-        embed.description("❌ Unexpected response format");
+        // Off the reply path, so scoring and posting alerts can't delay it
+        let ctx = ctx.clone();
+        let listings = crate::auction::parse_listings(&json);
+        tokio::spawn(async move { crate::snipe::flag_listings(&ctx, &listings).await });
     }
 
+    let mut page = if path.starts_with("/v1/auction/transactions/") {
+        render_or_message(TransactionPage::from_json(&json, current_page), &json, fmt)
+    } else {
+        render_or_message(AuctionPage::from_json(&json, current_page), &json, fmt)
+    };
+    page.title = Some(title.to_string());
+
     let mut footer_parts = Vec::new();
     if let Some(search_term) = search {
-        footer_parts.push(format!("🔍 Search: '{}'", search_term));
+        footer_parts.push(fmt.tf("auction.search", &[&search_term]));
     }
     if let Some(sort_type) = sort {
-        let sort_display = match sort_type {
-            "lowest_price" => "💰 Lowest Price",
-            "highest_price" => "💸 Highest Price",
-            "recently_listed" => "🕒 Recently Listed",
-            "last_listed" => "📅 Last Listed",
-            _ => sort_type,
-        };
-        footer_parts.push(format!("📊 Sort: {}", sort_display));
+        footer_parts.push(fmt.tf("auction.sort", &[&sort_name(sort_type, fmt)]));
     }
 
     if !footer_parts.is_empty() {
-        embed.footer(|f| f.text(footer_parts.join(" | ")));
+        page.footer = Some(footer_parts.join(" | "));
     }
 
     let items = json
@@ -191,7 +289,7 @@ pub async fn auction_embed(
         .and_then(|r| r.as_array())
         .cloned()
         .unwrap_or_default();
-    Ok((embed, items))
+    Ok((page.to_embed(), items))
 }
 
 pub async fn send_stats(
@@ -201,6 +299,7 @@ pub async fn send_stats(
     donut_key: &str,
     path: &str,
     player_name: &str,
+    fmt: &Format,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Immediately ack with a deferred response (allows more than 3s processing)
     cmd.create_interaction_response(&ctx.http, |resp| resp.kind(serenity::model::prelude::interaction::InteractionResponseType::DeferredChannelMessageWithSource)).await?;
@@ -214,10 +313,10 @@ pub async fn send_stats(
         .await?;
 
     if !res.status().is_success() {
-        let error_msg = format!("❌ API Error: {}", res.status());
+        let error_msg = fmt.tf("api.error_status", &[&res.status()]);
         cmd.edit_original_interaction_response(&ctx.http, |response| {
             response.embed(|e| {
-                e.title("Error")
+                e.title(fmt.t("common.error"))
                     .description(error_msg)
                     .color(crate::constants::EMBED_COLOR_ERROR)
             })
@@ -230,10 +329,10 @@ pub async fn send_stats(
     let json: Value = match serde_json::from_str(&response_text) {
         Ok(j) => j,
         Err(e) => {
-            let error_msg = format!("❌ Failed to parse response: {}", e);
+            let error_msg = fmt.tf("api.parse_failed", &[&e]);
             cmd.edit_original_interaction_response(&ctx.http, |response| {
                 response.embed(|e| {
-                    e.title("Error")
+                    e.title(fmt.t("common.error"))
                         .description(error_msg)
                         .color(crate::constants::EMBED_COLOR_ERROR)
                 })
@@ -243,9 +342,22 @@ pub async fn send_stats(
         }
     };
 
+    let page = match PlayerStats::from_json(&json, player_name) {
+        Some(stats) => {
+            if let Err(e) = crate::players::record_stats(&stats) {
+                tracing::warn!("Failed to record stats snapshot: {:?}", e);
+            }
+            StatsView {
+                percentiles: crate::players::percentiles(&stats),
+                stats,
+            }
+            .render(fmt)
+        }
+        None => stats_not_found(player_name, fmt),
+    };
     cmd.edit_original_interaction_response(&ctx.http, |response| {
         response.embed(|embed| {
-            format_stats_response(&json, embed, player_name);
+            page.apply(embed);
             embed
         })
     })
@@ -254,6 +366,42 @@ pub async fn send_stats(
     Ok(())
 }
 
+/// `/stats card:true`: the stats as a profile card image instead of an embed.
+pub async fn send_stats_card(
+    cmd: &ApplicationCommandInteraction,
+    ctx: &Context,
+    client: &Client,
+    donut_key: &str,
+    player_name: &str,
+    fmt: &Format,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    cmd.create_interaction_response(&ctx.http, |resp| {
+        resp.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+    })
+    .await?;
+
+    let stats = match fetch_stats(client, donut_key, player_name).await {
+        Some(stats) => stats,
+        None => {
+            let embed = stats_not_found(player_name, fmt).to_embed();
+            cmd.edit_original_interaction_response(&ctx.http, |r| r.add_embed(embed))
+                .await?;
+            return Ok(());
+        }
+    };
+    let head = crate::skins::head(client, &stats.player).await;
+    let file = AttachmentType::Bytes {
+        data: crate::card::stats_card(&stats, head.as_deref(), fmt)?.into(),
+        filename: "stats.png".to_string(),
+    };
+    // Edits can't carry files, so the card goes out as a follow-up
+    cmd.delete_original_interaction_response(&ctx.http).await?;
+    cmd.create_followup_message(&ctx.http, |m| m.add_file(file))
+        .await?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn send_leaderboard(
     cmd: &ApplicationCommandInteraction,
     ctx: &Context,
@@ -262,6 +410,7 @@ pub async fn send_leaderboard(
     path: &str,
     lb_type: &str,
     page: u32,
+    fmt: &Format,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     // Immediately ack with a deferred response (allows more than 3s processing)
     cmd.create_interaction_response(&ctx.http, |resp| resp.kind(serenity::model::prelude::interaction::InteractionResponseType::DeferredChannelMessageWithSource)).await?;
@@ -275,10 +424,10 @@ pub async fn send_leaderboard(
         .await?;
 
     if !res.status().is_success() {
-        let error_msg = format!("❌ API Error: {}", res.status());
+        let error_msg = fmt.tf("api.error_status", &[&res.status()]);
         cmd.edit_original_interaction_response(&ctx.http, |response| {
             response.embed(|e| {
-                e.title("Error")
+                e.title(fmt.t("common.error"))
                     .description(error_msg)
                     .color(crate::constants::EMBED_COLOR_ERROR)
             })
@@ -291,10 +440,10 @@ pub async fn send_leaderboard(
     let json: Value = match serde_json::from_str(&response_text) {
         Ok(j) => j,
         Err(e) => {
-            let error_msg = format!("❌ Failed to parse response: {}", e);
+            let error_msg = fmt.tf("api.parse_failed", &[&e]);
             cmd.edit_original_interaction_response(&ctx.http, |response| {
                 response.embed(|e| {
-                    e.title("Error")
+                    e.title(fmt.t("common.error"))
                         .description(error_msg)
                         .color(crate::constants::EMBED_COLOR_ERROR)
                 })
@@ -303,19 +452,40 @@ pub async fn send_leaderboard(
             return Ok(());
         }
     };
+    if let Err(e) = crate::players::record_leaderboard(&json, lb_type) {
+        tracing::warn!("Failed to record leaderboard players: {:?}", e);
+    }
 
     cmd.edit_original_interaction_response(&ctx.http, |response| {
         response
             .embed(|embed| {
-                format_leaderboard_response(&json, embed, lb_type, page);
+                render_leaderboard(&json, lb_type, page, fmt).apply(embed);
                 embed
             })
             .components(|c| {
                 use crate::components::lb_buttons;
-                lb_buttons(c, page, lb_type)
+                lb_buttons(c, page, lb_type, fmt)
             })
     })
     .await?;
 
     Ok(())
 }
+
+pub fn render_leaderboard(json: &Value, lb_type: &str, page: u32, fmt: &Format) -> Page {
+    match Leaderboard::from_json(json, lb_type, page) {
+        Some(leaderboard) => leaderboard.render(fmt),
+        None => leaderboard_not_found(lb_type, fmt),
+    }
+}
+
+/// The reader's name for an auction `sort` value.
+pub fn sort_name<'a>(sort: &'a str, fmt: &Format) -> &'a str {
+    match sort {
+        "lowest_price" => fmt.t("sort.lowest_price"),
+        "highest_price" => fmt.t("sort.highest_price"),
+        "recently_listed" => fmt.t("sort.recently_listed"),
+        "last_listed" => fmt.t("sort.last_listed"),
+        _ => sort,
+    }
+}
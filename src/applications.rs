@@ -0,0 +1,292 @@
+use chrono::{DateTime, Duration, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    model::id::{ChannelId, MessageId, UserId},
+    prelude::Context,
+};
+use std::{fs, io, path::PathBuf};
+use tracing::{error, info};
+
+use crate::api::fetch_stats;
+use crate::components::application_buttons;
+use crate::models::PlayerStats;
+use crate::render::Render;
+
+/// How often pending applications are checked for expiry.
+const EXPIRY_CHECK_SECS: u64 = 15 * 60;
+
+pub struct ApplicationConfig {
+    /// Where new applications are posted for review.
+    pub channel_id: u64,
+    /// Pending applications expire after this many days.
+    pub expiry_days: i64,
+}
+
+/// Reads the application settings; `None` when `APPLICATION_CHANNEL_ID` is
+/// unset, which closes `/apply`.
+pub fn config() -> Option<ApplicationConfig> {
+    let channel_id = std::env::var("APPLICATION_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())?;
+    let expiry_days = std::env::var("APPLICATION_EXPIRY_DAYS")
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(7);
+    Some(ApplicationConfig {
+        channel_id,
+        expiry_days,
+    })
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationState {
+    Pending,
+    Accepted,
+    Rejected,
+    Expired,
+}
+
+impl ApplicationState {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            ApplicationState::Pending => "application.state.pending",
+            ApplicationState::Accepted => "application.state.accepted",
+            ApplicationState::Rejected => "application.state.rejected",
+            ApplicationState::Expired => "application.state.expired",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            ApplicationState::Pending => "🕓",
+            ApplicationState::Accepted => "✅",
+            ApplicationState::Rejected => "❌",
+            ApplicationState::Expired => "⌛",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Application {
+    pub id: u64,
+    /// Discord user who applied.
+    pub user_id: u64,
+    pub user_tag: String,
+    pub ign: String,
+    /// ISO code, as on the roster.
+    pub country: String,
+    pub skills: Vec<String>,
+    pub about: String,
+    pub submitted: DateTime<Utc>,
+    pub state: ApplicationState,
+    /// Who accepted or rejected it.
+    #[serde(default)]
+    pub reviewed_by: Option<u64>,
+    #[serde(default)]
+    pub decided: Option<DateTime<Utc>>,
+    /// The applicant's Discord locale, for messages sent to them later.
+    #[serde(default)]
+    pub locale: String,
+    /// The review post, so it can be updated when the application expires.
+    #[serde(default)]
+    pub channel_id: Option<u64>,
+    #[serde(default)]
+    pub message_id: Option<u64>,
+}
+
+impl Application {
+    pub fn expires(&self, expiry_days: i64) -> DateTime<Utc> {
+        self.submitted + Duration::days(expiry_days)
+    }
+}
+
+/// An application as posted in the review channel, with the applicant's
+/// stats at the time.
+pub struct ApplicationView {
+    pub application: Application,
+    pub stats: Option<PlayerStats>,
+    pub expiry_days: i64,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("APPLICATION_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("applications.json")
+}
+
+fn load() -> Vec<Application> {
+    let path = store_path();
+    if !path.exists() {
+        return Vec::new();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    }
+}
+
+fn save(applications: &[Application]) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(applications).unwrap_or_else(|_| "[]".into());
+    fs::write(path, json)
+}
+
+pub fn get(id: u64) -> Option<Application> {
+    load().into_iter().find(|a| a.id == id)
+}
+
+/// The application `user_id` is still waiting on, if any.
+pub fn pending_for(user_id: u64) -> Option<Application> {
+    load()
+        .into_iter()
+        .find(|a| a.user_id == user_id && a.state == ApplicationState::Pending)
+}
+
+/// Stores a new application, giving it the next id.
+pub fn submit(mut application: Application) -> io::Result<Application> {
+    let mut applications = load();
+    application.id = applications.iter().map(|a| a.id).max().unwrap_or(0) + 1;
+    applications.push(application.clone());
+    save(&applications)?;
+    Ok(application)
+}
+
+/// Drops an application that never reached the review channel, so the
+/// applicant can try again.
+pub fn remove(id: u64) -> io::Result<()> {
+    let mut applications = load();
+    applications.retain(|a| a.id != id);
+    save(&applications)
+}
+
+/// Remembers where the application was posted for review.
+pub fn set_message(id: u64, channel_id: u64, message_id: u64) -> io::Result<()> {
+    let mut applications = load();
+    if let Some(application) = applications.iter_mut().find(|a| a.id == id) {
+        application.channel_id = Some(channel_id);
+        application.message_id = Some(message_id);
+    }
+    save(&applications)
+}
+
+/// Accepts or rejects a pending application. Returns it as it is afterwards,
+/// so a decision that came too late shows the state it already had, and
+/// whether this call was the one that decided it.
+pub fn decide(
+    id: u64,
+    state: ApplicationState,
+    by: u64,
+) -> io::Result<Option<(Application, bool)>> {
+    let mut applications = load();
+    let application = match applications.iter_mut().find(|a| a.id == id) {
+        Some(application) => application,
+        None => return Ok(None),
+    };
+    if application.state != ApplicationState::Pending {
+        return Ok(Some((application.clone(), false)));
+    }
+    application.state = state;
+    application.reviewed_by = Some(by);
+    application.decided = Some(Utc::now());
+    let application = application.clone();
+    save(&applications)?;
+    Ok(Some((application, true)))
+}
+
+/// Puts a decided application back up for review, for when acting on the
+/// decision failed.
+pub fn reopen(id: u64) -> io::Result<()> {
+    let mut applications = load();
+    if let Some(application) = applications.iter_mut().find(|a| a.id == id) {
+        application.state = ApplicationState::Pending;
+        application.reviewed_by = None;
+        application.decided = None;
+    }
+    save(&applications)
+}
+
+/// Marks pending applications older than `expiry_days` as expired and
+/// returns them.
+pub fn expire_due(now: DateTime<Utc>, expiry_days: i64) -> io::Result<Vec<Application>> {
+    let mut applications = load();
+    let mut expired = Vec::new();
+    for application in &mut applications {
+        if application.state == ApplicationState::Pending && application.expires(expiry_days) <= now
+        {
+            application.state = ApplicationState::Expired;
+            application.decided = Some(now);
+            expired.push(application.clone());
+        }
+    }
+    if !expired.is_empty() {
+        save(&applications)?;
+    }
+    Ok(expired)
+}
+
+/// Sends the applicant a direct message, logging rather than failing when
+/// they don't accept DMs.
+pub async fn notify(ctx: &Context, user_id: u64, message: String) {
+    let channel = match UserId(user_id).create_dm_channel(&ctx.http).await {
+        Ok(channel) => channel,
+        Err(e) => {
+            error!("Failed to open DM with applicant {}: {:?}", user_id, e);
+            return;
+        }
+    };
+    if let Err(e) = channel
+        .send_message(&ctx.http, |m| m.content(message))
+        .await
+    {
+        error!("Failed to notify applicant {}: {:?}", user_id, e);
+    }
+}
+
+/// Expires applications nobody reviewed in time, updating their review post
+/// and letting the applicant know.
+pub async fn run_expiry(ctx: Context, http_client: Client, donut_key: String, expiry_days: i64) {
+    loop {
+        match expire_due(Utc::now(), expiry_days) {
+            Ok(expired) => {
+                for application in expired {
+                    info!("Application {} expired", application.id);
+                    let fmt = crate::settings::guild_format();
+                    if let (Some(channel_id), Some(message_id)) =
+                        (application.channel_id, application.message_id)
+                    {
+                        let stats = fetch_stats(&http_client, &donut_key, &application.ign).await;
+                        let view = ApplicationView {
+                            application: application.clone(),
+                            stats,
+                            expiry_days,
+                        };
+                        let embed = view.render(&fmt).to_embed();
+                        let id = application.id;
+                        if let Err(e) = ChannelId(channel_id)
+                            .edit_message(&ctx.http, MessageId(message_id), |m| {
+                                m.set_embed(embed)
+                                    .components(|c| application_buttons(c, id, false, &fmt))
+                            })
+                            .await
+                        {
+                            error!("Failed to update expired application {}: {:?}", id, e);
+                        }
+                    }
+                    let fmt = crate::settings::format_for(application.user_id, &application.locale);
+                    let message = fmt.tf("application.expired_dm", &[&application.ign]);
+                    notify(&ctx, application.user_id, message).await;
+                }
+            }
+            Err(e) => error!("Failed to expire applications: {:?}", e),
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(EXPIRY_CHECK_SECS)).await;
+    }
+}
@@ -0,0 +1,168 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// A single auction house entry as returned by `/v1/auction/list/{page}`.
+#[derive(Debug, Clone)]
+pub struct Listing {
+    pub item_id: String,
+    pub display_name: String,
+    pub count: i64,
+    pub enchants: BTreeMap<String, i64>,
+    pub price: i64,
+    pub seller: String,
+}
+
+pub fn item_display_name(item: &Value) -> String {
+    item.get("display_name")
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .or_else(|| item.get("id").and_then(|v| v.as_str()))
+        .map(|name| {
+            if name.starts_with("minecraft:") {
+                name.replace("minecraft:", "").replace("_", " ")
+            } else {
+                name.to_string()
+            }
+        })
+        .unwrap_or_else(|| "Unknown Item".to_string())
+}
+
+pub fn item_enchants(item: &Value) -> BTreeMap<String, i64> {
+    item.get("enchants")
+        .and_then(|enchants| enchants.get("enchantments"))
+        .and_then(|enchantments| enchantments.get("levels"))
+        .and_then(|levels| levels.as_object())
+        .map(|levels| {
+            levels
+                .iter()
+                .map(|(name, level)| (name.clone(), level.as_i64().unwrap_or(1)))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+impl Listing {
+    pub fn from_value(auction: &Value) -> Option<Listing> {
+        let item = auction.get("item")?;
+        let item_id = item.get("id").and_then(|v| v.as_str())?.to_string();
+        Some(Listing {
+            display_name: item_display_name(item),
+            count: item.get("count").and_then(|v| v.as_i64()).unwrap_or(1).max(1),
+            enchants: item_enchants(item),
+            price: auction.get("price").and_then(|v| v.as_i64()).unwrap_or(0),
+            seller: auction
+                .get("seller")
+                .and_then(|seller| seller.get("name"))
+                .and_then(|v| v.as_str())
+                .or_else(|| auction.get("seller_name").and_then(|v| v.as_str()))
+                .unwrap_or("Unknown")
+                .to_string(),
+            item_id,
+        })
+    }
+
+    /// Identifies "the same item": item ID plus its exact enchant set.
+    pub fn item_key(&self) -> String {
+        let enchants: Vec<String> = self
+            .enchants
+            .iter()
+            .map(|(name, level)| format!("{}={}", name, level))
+            .collect();
+        format!("{}|{}", self.item_id, enchants.join(","))
+    }
+
+    pub fn unit_price(&self) -> f64 {
+        self.price as f64 / self.count as f64
+    }
+
+    /// `diamond sword (sharpness 5, unbreaking 3)`
+    pub fn name(&self) -> String {
+        let enchant_list: Vec<String> = self
+            .enchants
+            .iter()
+            .map(|(name, level)| {
+                let display_name = name.replace("minecraft:", "").replace("_", " ");
+                if *level > 1 {
+                    format!("{} {}", display_name, level)
+                } else {
+                    display_name
+                }
+            })
+            .collect();
+        if enchant_list.is_empty() {
+            self.display_name.clone()
+        } else {
+            format!("{} ({})", self.display_name, enchant_list.join(", "))
+        }
+    }
+
+    /// `16x diamond sword (sharpness 5, unbreaking 3)`
+    pub fn label(&self) -> String {
+        if self.count > 1 {
+            format!("{}x {}", self.count, self.name())
+        } else {
+            self.name()
+        }
+    }
+}
+
+pub fn parse_listings(json: &Value) -> Vec<Listing> {
+    json.get("result")
+        .and_then(|r| r.as_array())
+        .map(|items| items.iter().filter_map(Listing::from_value).collect())
+        .unwrap_or_default()
+}
+
+/// A completed sale from `/v1/auction/transactions/{page}`.
+#[derive(Debug, Clone)]
+pub struct Sale {
+    pub listing: Listing,
+    pub buyer: Option<String>,
+    /// Unix timestamp in seconds, if the API provided one.
+    pub sold_at: Option<i64>,
+}
+
+/// Anything above this is treated as milliseconds (10^11 s is the year 5138).
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Normalises a Unix timestamp that may be in seconds or milliseconds to seconds.
+pub fn normalize_unix_seconds(raw: i64) -> i64 {
+    if raw.abs() >= MILLIS_THRESHOLD {
+        raw / 1000
+    } else {
+        raw
+    }
+}
+
+/// Sale time of a transaction in Unix seconds, whichever field and unit the API used.
+pub fn transaction_timestamp(transaction: &Value) -> Option<i64> {
+    ["timestamp", "time", "unixMillisDateSold", "sold_at"]
+        .iter()
+        .find_map(|key| transaction.get(*key).and_then(|v| v.as_i64()))
+        .map(normalize_unix_seconds)
+}
+
+impl Sale {
+    pub fn from_value(transaction: &Value) -> Option<Sale> {
+        let listing = Listing::from_value(transaction)?;
+        let buyer = transaction
+            .get("buyer")
+            .and_then(|buyer| buyer.get("name"))
+            .and_then(|v| v.as_str())
+            .or_else(|| transaction.get("buyer_name").and_then(|v| v.as_str()))
+            .map(|s| s.to_string());
+        let sold_at = transaction_timestamp(transaction);
+        Some(Sale {
+            listing,
+            buyer,
+            sold_at,
+        })
+    }
+}
+
+pub fn parse_sales(json: &Value) -> Vec<Sale> {
+    json.get("result")
+        .and_then(|r| r.as_array())
+        .map(|items| items.iter().filter_map(Sale::from_value).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,288 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serenity::{model::id::ChannelId, prelude::Context};
+use std::{
+    fs, io,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+};
+use tracing::{error, info};
+
+use crate::render::Render;
+use crate::team::{CustomField, Squad, Team, TeamMember};
+
+/// How often new entries are mirrored to the moderation channel.
+const MIRROR_INTERVAL_SECS: u64 = 30;
+
+/// Older entries are dropped once there are more than this.
+const MAX_ENTRIES: usize = 1000;
+
+/// `/team-audit` without `days` looks this far back.
+pub const DEFAULT_DAYS: i64 = 30;
+
+/// What a roster change did, with the values before and after it.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Change {
+    Name {
+        before: String,
+        after: String,
+    },
+    /// `before` is `None` for added members, `after` for removed ones.
+    Member {
+        before: Option<TeamMember>,
+        after: Option<TeamMember>,
+    },
+    Field {
+        before: Option<CustomField>,
+        after: Option<CustomField>,
+    },
+    Squad {
+        before: Option<Squad>,
+        after: Option<Squad>,
+    },
+}
+
+impl Change {
+    /// The member this change is about.
+    pub fn ign(&self) -> Option<&str> {
+        match self {
+            Change::Member { before, after } => {
+                after.as_ref().or(before.as_ref()).map(|m| m.ign.as_str())
+            }
+            _ => None,
+        }
+    }
+
+    /// Changes between two versions of the team: its name, then custom
+    /// fields, then squads, then members.
+    pub fn between(before: &Team, after: &Team) -> Vec<Change> {
+        let mut changes = Vec::new();
+        if before.name != after.name {
+            changes.push(Change::Name {
+                before: before.name.clone(),
+                after: after.name.clone(),
+            });
+        }
+        for field in &after.custom_fields {
+            let previous = before.custom_fields.iter().find(|f| f.key == field.key);
+            if previous != Some(field) {
+                changes.push(Change::Field {
+                    before: previous.cloned(),
+                    after: Some(field.clone()),
+                });
+            }
+        }
+        for field in &before.custom_fields {
+            if !after.custom_fields.iter().any(|f| f.key == field.key) {
+                changes.push(Change::Field {
+                    before: Some(field.clone()),
+                    after: None,
+                });
+            }
+        }
+        for squad in &after.squads {
+            let previous = before.squads.iter().find(|s| s.name == squad.name);
+            if previous != Some(squad) {
+                changes.push(Change::Squad {
+                    before: previous.cloned(),
+                    after: Some(squad.clone()),
+                });
+            }
+        }
+        for squad in &before.squads {
+            if !after.squads.iter().any(|s| s.name == squad.name) {
+                changes.push(Change::Squad {
+                    before: Some(squad.clone()),
+                    after: None,
+                });
+            }
+        }
+        changes.extend(Change::members(&before.members, &after.members));
+        changes
+    }
+
+    /// Changes between two versions of the roster, one per added, changed or
+    /// removed member.
+    fn members(before: &[TeamMember], after: &[TeamMember]) -> Vec<Change> {
+        let find = |members: &[TeamMember], ign: &str| {
+            members
+                .iter()
+                .find(|m| m.ign.eq_ignore_ascii_case(ign))
+                .cloned()
+        };
+        let mut changes = Vec::new();
+        for member in after {
+            let previous = find(before, &member.ign);
+            if previous.as_ref() != Some(member) {
+                changes.push(Change::Member {
+                    before: previous,
+                    after: Some(member.clone()),
+                });
+            }
+        }
+        for member in before {
+            if find(after, &member.ign).is_none() {
+                changes.push(Change::Member {
+                    before: Some(member.clone()),
+                    after: None,
+                });
+            }
+        }
+        changes
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub id: u64,
+    pub at: DateTime<Utc>,
+    /// Discord user who made the change.
+    pub by: u64,
+    #[serde(flatten)]
+    pub change: Change,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AuditLog {
+    #[serde(default)]
+    entries: Vec<AuditEntry>,
+    /// Last entry posted to the moderation channel. `None` until mirroring
+    /// first runs, so turning it on doesn't repost the whole history.
+    #[serde(default)]
+    mirrored: Option<u64>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("AUDIT_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("audit_log.json")
+}
+
+/// Held for every read and write of the log, so the mirror job and roster
+/// changes can't overwrite each other's updates.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn load() -> AuditLog {
+    let _guard = lock();
+    read()
+}
+
+/// Loads, changes and saves the log in one go under the lock.
+fn update(change: impl FnOnce(&mut AuditLog)) -> io::Result<()> {
+    let _guard = lock();
+    let mut log = read();
+    change(&mut log);
+    write(&log)
+}
+
+fn read() -> AuditLog {
+    let path = store_path();
+    if !path.exists() {
+        return AuditLog::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => AuditLog::default(),
+    }
+}
+
+fn write(log: &AuditLog) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(log).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// Appends changes made by the Discord user `by`.
+pub fn record(by: u64, changes: Vec<Change>) -> io::Result<()> {
+    if changes.is_empty() {
+        return Ok(());
+    }
+    let at = Utc::now();
+    update(|log| {
+        let next = log.entries.last().map_or(0, |e| e.id) + 1;
+        for (id, change) in (next..).zip(changes) {
+            log.entries.push(AuditEntry { id, at, by, change });
+        }
+        let excess = log.entries.len().saturating_sub(MAX_ENTRIES);
+        log.entries.drain(..excess);
+    })
+}
+
+/// Entries from the last `days` days, newest first, optionally only those
+/// about one member.
+pub fn history(ign: Option<&str>, days: i64) -> Vec<AuditEntry> {
+    let since = Utc::now() - Duration::days(days);
+    load()
+        .entries
+        .into_iter()
+        .rev()
+        .take_while(|e| e.at >= since)
+        .filter(|e| {
+            ign.is_none_or(|ign| e.change.ign().is_some_and(|i| i.eq_ignore_ascii_case(ign)))
+        })
+        .collect()
+}
+
+/// Entries for `/team-audit`, with the filters that picked them.
+pub struct AuditView {
+    pub entries: Vec<AuditEntry>,
+    pub ign: Option<String>,
+    pub days: i64,
+}
+
+fn set_mirrored(id: u64) -> io::Result<()> {
+    // Reloaded so entries recorded while posting are kept
+    update(|log| log.mirrored = Some(id))
+}
+
+/// Posts new entries to `channel_id` as they're recorded.
+pub async fn run_mirror(ctx: Context, channel_id: u64) {
+    let channel = ChannelId(channel_id);
+    loop {
+        let log = load();
+        let latest = log.entries.last().map_or(0, |e| e.id);
+        let mirrored = match log.mirrored {
+            Some(mirrored) => mirrored,
+            None => {
+                if let Err(e) = set_mirrored(latest) {
+                    error!("Failed to save audit mirror state: {:?}", e);
+                }
+                latest
+            }
+        };
+        let fmt = crate::settings::guild_format();
+        let mut posted = mirrored;
+        for entry in log.entries.iter().filter(|e| e.id > mirrored) {
+            let embed = entry.render(&fmt).to_embed();
+            match channel
+                .send_message(&ctx.http, |m| m.set_embed(embed))
+                .await
+            {
+                Ok(_) => posted = entry.id,
+                Err(e) => {
+                    error!("Failed to mirror audit entry {}: {:?}", entry.id, e);
+                    break;
+                }
+            }
+        }
+        if posted > mirrored {
+            info!("Mirrored {} audit entries", posted - mirrored);
+            if let Err(e) = set_mirrored(posted) {
+                error!("Failed to save audit mirror state: {:?}", e);
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_secs(MIRROR_INTERVAL_SECS)).await;
+    }
+}
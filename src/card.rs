@@ -0,0 +1,668 @@
+use embedded_graphics::{
+    mono_font::{
+        iso_8859_1::{FONT_10X20, FONT_6X10, FONT_7X13, FONT_7X13_BOLD, FONT_9X15_BOLD},
+        MonoTextStyle,
+    },
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle, Rectangle, RoundedRectangle},
+    text::{Baseline, Text},
+};
+use std::{collections::HashMap, convert::Infallible};
+
+use crate::countries;
+use crate::locale::Format;
+use crate::models::{PlayerStats, Presence};
+use crate::status::{Group, Layout, TeamStatus};
+use crate::team::{Detail, Rank, Team, TeamMember};
+
+/// Cards are drawn at this size and blown up by `SCALE` when encoded, so the
+/// bitmap fonts stay sharp and readable on high-DPI screens.
+const SCALE: u32 = 2;
+
+const BACKGROUND: Rgb888 = Rgb888::new(0x2f, 0x31, 0x36);
+const HEADER: Rgb888 = Rgb888::new(0x20, 0x22, 0x25);
+const STRIPE: Rgb888 = Rgb888::new(0x36, 0x39, 0x3f);
+const BADGE: Rgb888 = Rgb888::new(0x40, 0x44, 0x4b);
+const TEXT: Rgb888 = Rgb888::new(0xdc, 0xdd, 0xde);
+const MUTED: Rgb888 = Rgb888::new(0x96, 0x98, 0x9d);
+const ACCENT: Rgb888 = Rgb888::new(0xf1, 0xc4, 0x0f);
+const ONLINE: Rgb888 = Rgb888::new(0x43, 0xb5, 0x81);
+const OFFLINE: Rgb888 = Rgb888::new(0xf0, 0x47, 0x47);
+const UNKNOWN: Rgb888 = Rgb888::new(0x74, 0x7f, 0x8d);
+
+const TITLE: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_10X20, TEXT);
+const LABEL: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_7X13_BOLD, MUTED);
+const NAME: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_7X13_BOLD, TEXT);
+const BODY: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_7X13, TEXT);
+const BODY_MUTED: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_7X13, MUTED);
+const SMALL: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_6X10, TEXT);
+const SMALL_MUTED: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_6X10, MUTED);
+const VALUE: MonoTextStyle<Rgb888> = MonoTextStyle::new(&FONT_9X15_BOLD, TEXT);
+
+/// A decoded RGBA image, such as a skin head.
+pub struct Image {
+    width: u32,
+    height: u32,
+    rgba: Vec<u8>,
+}
+
+impl Image {
+    /// Decodes a PNG of any colour type; `None` when it isn't one.
+    pub fn decode(png: &[u8]) -> Option<Image> {
+        let mut decoder = png::Decoder::new(png);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info().ok()?;
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).ok()?;
+        let data = &data[..info.buffer_size()];
+        let rgba = match info.color_type {
+            png::ColorType::Rgba => data.to_vec(),
+            png::ColorType::Rgb => data
+                .chunks(3)
+                .flat_map(|p| [p[0], p[1], p[2], 255])
+                .collect(),
+            png::ColorType::GrayscaleAlpha => data
+                .chunks(2)
+                .flat_map(|p| [p[0], p[0], p[0], p[1]])
+                .collect(),
+            png::ColorType::Grayscale => data.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+            png::ColorType::Indexed => return None,
+        };
+        if info.width == 0 || info.height == 0 {
+            return None;
+        }
+        Some(Image {
+            width: info.width,
+            height: info.height,
+            rgba,
+        })
+    }
+}
+
+/// An RGB image to draw cards on.
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<Rgb888>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32, background: Rgb888) -> Canvas {
+        Canvas {
+            width,
+            height,
+            pixels: vec![background; (width * height) as usize],
+        }
+    }
+
+    pub fn rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Rgb888) {
+        let _ = Rectangle::new(Point::new(x, y), Size::new(width, height))
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(self);
+    }
+
+    pub fn rounded_rect(&mut self, x: i32, y: i32, width: u32, height: u32, color: Rgb888) {
+        let rect = Rectangle::new(Point::new(x, y), Size::new(width, height));
+        let _ = RoundedRectangle::with_equal_corners(rect, Size::new(3, 3))
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(self);
+    }
+
+    pub fn dot(&mut self, x: i32, y: i32, diameter: u32, color: Rgb888) {
+        let _ = Circle::new(Point::new(x, y), diameter)
+            .into_styled(PrimitiveStyle::with_fill(color))
+            .draw(self);
+    }
+
+    /// Draws `image` scaled to `width` by `height`, keeping pixel art blocky
+    /// and blending in transparent parts.
+    pub fn image(&mut self, x: i32, y: i32, width: u32, height: u32, image: &Image) {
+        for dy in 0..height {
+            for dx in 0..width {
+                let (px, py) = (x + dx as i32, y + dy as i32);
+                if px < 0 || py < 0 || px as u32 >= self.width || py as u32 >= self.height {
+                    continue;
+                }
+                let (sx, sy) = (dx * image.width / width, dy * image.height / height);
+                let i = ((sy * image.width + sx) * 4) as usize;
+                let [r, g, b, a] = [0, 1, 2, 3].map(|c| image.rgba[i + c] as u32);
+                let pixel = &mut self.pixels[(py as u32 * self.width + px as u32) as usize];
+                let blend =
+                    |over: u32, under: u8| ((over * a + under as u32 * (255 - a)) / 255) as u8;
+                *pixel = Rgb888::new(
+                    blend(r, pixel.r()),
+                    blend(g, pixel.g()),
+                    blend(b, pixel.b()),
+                );
+            }
+        }
+    }
+
+    /// Draws `text` with its top left corner at `at`. Characters the font
+    /// doesn't have come out as `?`.
+    pub fn text(&mut self, at: Point, text: &str, style: MonoTextStyle<Rgb888>) {
+        let _ = Text::with_baseline(text, at, style, Baseline::Top).draw(self);
+    }
+
+    /// Like [`Canvas::text`], but with the top right corner at `at`.
+    pub fn text_right(&mut self, at: Point, text: &str, style: MonoTextStyle<Rgb888>) {
+        let width = text.chars().count() as i32 * style.font.character_size.width as i32;
+        self.text(at - Point::new(width, 0), text, style);
+    }
+
+    /// Encodes the canvas as a PNG, `SCALE` times its drawn size.
+    pub fn to_png(&self) -> Result<Vec<u8>, png::EncodingError> {
+        let (width, height) = (self.width * SCALE, self.height * SCALE);
+        let mut data = Vec::with_capacity((width * height * 3) as usize);
+        for row in self.pixels.chunks(self.width as usize) {
+            let mut line = Vec::with_capacity((width * 3) as usize);
+            for color in row {
+                for _ in 0..SCALE {
+                    line.extend_from_slice(&[color.r(), color.g(), color.b()]);
+                }
+            }
+            for _ in 0..SCALE {
+                data.extend_from_slice(&line);
+            }
+        }
+
+        let mut out = Vec::new();
+        let mut encoder = png::Encoder::new(&mut out, width, height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&data)?;
+        writer.finish()?;
+        Ok(out)
+    }
+}
+
+impl OriginDimensions for Canvas {
+    fn size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+impl DrawTarget for Canvas {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if point.x >= 0
+                && point.y >= 0
+                && (point.x as u32) < self.width
+                && (point.y as u32) < self.height
+            {
+                self.pixels[(point.y as u32 * self.width + point.x as u32) as usize] = color;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Cuts `text` to `max_chars`, ending in `..` when it had to.
+fn fit(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut cut: String = text.chars().take(max_chars.saturating_sub(2)).collect();
+    cut.push_str("..");
+    cut
+}
+
+fn rank_color(rank: &Rank) -> Rgb888 {
+    match rank {
+        Rank::Owner => ACCENT,
+        Rank::Admin => Rgb888::new(0xe6, 0x7e, 0x22),
+        Rank::Member => Rgb888::new(0x99, 0xaa, 0xb5),
+    }
+}
+
+const CARD_WIDTH: u32 = 480;
+const PAD: i32 = 12;
+const HEADER_HEIGHT: u32 = 40;
+const ROW_HEIGHT: u32 = 20;
+/// A line of member details under their row, in the detailed layout.
+const DETAIL_HEIGHT: u32 = 14;
+const FOOTER_HEIGHT: u32 = 22;
+
+// Column offsets in the member table
+const COL_NAME: i32 = 28;
+const COL_COUNTRY: i32 = 160;
+const COL_LOCATION: i32 = 192;
+const COL_DETAILS: i32 = 308;
+const FLAG_WIDTH: u32 = 20;
+
+/// A line of the member table.
+enum Row<'a> {
+    /// A rank or squad heading with how many members are shown under it.
+    Heading(String, Rgb888, usize),
+    /// A member with the details shown for them.
+    Member(&'a TeamMember, Vec<String>),
+    /// Shown instead of members when there are none to show.
+    Note(&'a str),
+}
+
+impl Row<'_> {
+    fn height(&self, detailed: bool) -> u32 {
+        match self {
+            Row::Member(_, details) if detailed => {
+                ROW_HEIGHT + DETAIL_HEIGHT * details.len() as u32
+            }
+            _ => ROW_HEIGHT,
+        }
+    }
+}
+
+/// The rows of the team status, grouped and filtered like the embed.
+fn rows<'a>(status: &TeamStatus, team: &'a Team, fmt: &'a Format) -> Vec<Row<'a>> {
+    // The country has its own column, and the heading already says the squad
+    let details: Vec<Detail> = status
+        .config
+        .details
+        .iter()
+        .copied()
+        .filter(|d| *d != Detail::Country)
+        .filter(|d| !(*d == Detail::Squad && status.config.group_by_squad))
+        .collect();
+    let mut rows = Vec::new();
+    for (group, members) in status.groups(team) {
+        let (heading, color) = match group {
+            Group::Rank(rank) => (fmt.t(rank.label_key()).to_string(), rank_color(&rank)),
+            Group::Squad(Some(squad)) => (squad.name.clone(), ACCENT),
+            Group::Squad(None) => (fmt.t("card.no_squad").to_string(), MUTED),
+        };
+        rows.push(Row::Heading(heading, color, members.len()));
+        for member in members {
+            let values = details
+                .iter()
+                .filter_map(|detail| {
+                    let value = detail.value(member, fmt)?.replace('\n', " ");
+                    Some(match status.config.layout {
+                        Layout::Detailed => fmt.tf(detail.line_key(), &[&value]),
+                        _ => value,
+                    })
+                })
+                .collect();
+            rows.push(Row::Member(member, values));
+        }
+    }
+    if rows.is_empty() {
+        let note = if team.members.is_empty() {
+            "card.no_members"
+        } else {
+            "team.nobody_online"
+        };
+        rows.push(Row::Note(fmt.t(note)));
+    }
+    rows
+}
+
+/// The team status as a table, laid out like the embed: members under their
+/// rank or squad, with an online dot, IGN, country flag and where they are,
+/// plus the configured details beside them or, in the detailed layout, below.
+/// `flags` holds flag PNGs by ISO code; countries without one show their code.
+pub fn team_status_card(
+    status: &TeamStatus,
+    flags: &HashMap<String, Vec<u8>>,
+    fmt: &Format,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let team = status.sorted_team();
+    let detailed = status.config.layout == Layout::Detailed;
+    let show_country = status.config.details.contains(&Detail::Country);
+    let rows = rows(status, &team, fmt);
+    let table: u32 = rows.iter().map(|row| row.height(detailed)).sum();
+    let height = HEADER_HEIGHT + ROW_HEIGHT + table + FOOTER_HEIGHT;
+    let mut canvas = Canvas::new(CARD_WIDTH, height, BACKGROUND);
+    let right = CARD_WIDTH as i32 - PAD;
+
+    canvas.rect(0, 0, CARD_WIDTH, HEADER_HEIGHT, HEADER);
+    canvas.rect(0, HEADER_HEIGHT as i32 - 2, CARD_WIDTH, 2, ACCENT);
+    canvas.text(Point::new(PAD, 9), &fit(&team.name, 30), TITLE);
+    let online = team
+        .members
+        .iter()
+        .filter(|m| matches!(status.presence_of(&m.ign), Some(Presence::Online { .. })))
+        .count();
+    let count = fmt.tf("card.online_count", &[&online, &team.members.len()]);
+    canvas.text_right(Point::new(right, 14), &count, BODY_MUTED);
+
+    let mut y = HEADER_HEIGHT as i32;
+    canvas.text(
+        Point::new(COL_NAME, y + 4),
+        &fit(fmt.t("card.player"), 18),
+        LABEL,
+    );
+    canvas.text(
+        Point::new(COL_LOCATION, y + 4),
+        &fit(fmt.t("card.location"), 16),
+        LABEL,
+    );
+    y += ROW_HEIGHT as i32;
+
+    let mut stripe = false;
+    for row in &rows {
+        let (member, details) = match row {
+            Row::Heading(heading, color, count) => {
+                let heading = format!("{} ({})", heading, fmt.number(*count as i64));
+                canvas.text(
+                    Point::new(PAD, y + 4),
+                    &fit(&heading, 40),
+                    MonoTextStyle::new(&FONT_7X13_BOLD, *color),
+                );
+                y += ROW_HEIGHT as i32;
+                stripe = false;
+                continue;
+            }
+            Row::Note(note) => {
+                canvas.text(Point::new(COL_NAME, y + 4), note, BODY_MUTED);
+                y += ROW_HEIGHT as i32;
+                continue;
+            }
+            Row::Member(member, details) => (member, details),
+        };
+        let row_height = row.height(detailed);
+        if stripe {
+            canvas.rect(0, y, CARD_WIDTH, row_height, STRIPE);
+        }
+        stripe = !stripe;
+
+        let presence = status.presence_of(&member.ign);
+        let dot = match presence {
+            Some(Presence::Online { .. }) => ONLINE,
+            Some(Presence::Offline) => OFFLINE,
+            _ => UNKNOWN,
+        };
+        canvas.dot(PAD, y + 6, 8, dot);
+
+        // Leads get a star after their name, like the ⭐ in the embeds
+        let mut name = fit(&member.ign, 18);
+        if team.is_lead(member) {
+            name = fit(&member.ign, 16);
+            let after = COL_NAME + 7 * name.chars().count() as i32 + 4;
+            canvas.text(
+                Point::new(after, y + 4),
+                "*",
+                MonoTextStyle::new(&FONT_7X13_BOLD, ACCENT),
+            );
+        }
+        canvas.text(Point::new(COL_NAME, y + 4), &name, NAME);
+
+        if show_country {
+            draw_country(
+                &mut canvas,
+                Point::new(COL_COUNTRY, y),
+                &member.country,
+                flags,
+            );
+        }
+
+        let (location, style) = match presence {
+            Some(Presence::Online {
+                location: Some(location),
+            }) => (location.as_str(), BODY),
+            Some(Presence::Online { location: None }) => (fmt.t("card.online"), BODY),
+            Some(Presence::Offline) => (fmt.t("card.offline"), BODY_MUTED),
+            Some(Presence::Unknown) => (fmt.t("card.unknown"), BODY_MUTED),
+            None => ("", BODY_MUTED),
+        };
+        let location_chars = if detailed { 38 } else { 16 };
+        canvas.text(
+            Point::new(COL_LOCATION, y + 4),
+            &fit(location, location_chars),
+            style,
+        );
+
+        if detailed {
+            for (i, line) in details.iter().enumerate() {
+                let at = Point::new(
+                    COL_NAME,
+                    y + ROW_HEIGHT as i32 + DETAIL_HEIGHT as i32 * i as i32,
+                );
+                canvas.text(at, &fit(line, 72), SMALL_MUTED);
+            }
+        } else if !details.is_empty() {
+            canvas.text(
+                Point::new(COL_DETAILS, y + 5),
+                &fit(&details.join(" · "), 26),
+                SMALL_MUTED,
+            );
+        }
+        y += row_height as i32;
+    }
+
+    if let Some(at) = status.at {
+        let updated = fmt.tf("team.last_updated", &[&fmt.datetime(at)]);
+        canvas.text_right(Point::new(right, height as i32 - 16), &updated, SMALL_MUTED);
+    }
+    canvas.to_png()
+}
+
+/// The country's flag, or its ISO code on a badge when there's no flag to draw.
+fn draw_country(canvas: &mut Canvas, at: Point, country: &str, flags: &HashMap<String, Vec<u8>>) {
+    let known = countries::exact(country);
+    let flag = known
+        .and_then(|c| flags.get(c.code))
+        .and_then(|png| Image::decode(png));
+    if let Some(flag) = flag {
+        let height = (FLAG_WIDTH * flag.height / flag.width).clamp(1, 16);
+        let top = at.y + (ROW_HEIGHT as i32 - height as i32) / 2;
+        canvas.image(at.x, top, FLAG_WIDTH, height, &flag);
+        return;
+    }
+    let code = match known {
+        Some(country) => country.code.to_string(),
+        None => country
+            .trim()
+            .chars()
+            .take(3)
+            .collect::<String>()
+            .to_uppercase(),
+    };
+    if !code.is_empty() {
+        let width = 6 * code.chars().count() as u32 + 8;
+        canvas.rounded_rect(at.x, at.y + 3, width, 14, BADGE);
+        canvas.text(Point::new(at.x + 4, at.y + 5), &code, SMALL);
+    }
+}
+
+const STATS_WIDTH: u32 = 432;
+const HEAD_SIZE: u32 = 64;
+const TILE_WIDTH: u32 = 128;
+const TILE_HEIGHT: u32 = 44;
+const GAP: u32 = 8;
+
+/// A player's profile card: their skin head, or their initial when there is
+/// no head to show, above a grid of their main stats.
+pub fn stats_card(
+    stats: &PlayerStats,
+    head: Option<&[u8]>,
+    fmt: &Format,
+) -> Result<Vec<u8>, png::EncodingError> {
+    let header = 2 * PAD as u32 + HEAD_SIZE;
+    let height = header + PAD as u32 + 2 * TILE_HEIGHT + GAP + PAD as u32;
+    let mut canvas = Canvas::new(STATS_WIDTH, height, BACKGROUND);
+
+    canvas.rect(0, 0, STATS_WIDTH, header, HEADER);
+    canvas.rect(0, header as i32 - 2, STATS_WIDTH, 2, ACCENT);
+    match head.and_then(Image::decode) {
+        Some(image) => canvas.image(PAD, PAD, HEAD_SIZE, HEAD_SIZE, &image),
+        None => {
+            canvas.rounded_rect(PAD, PAD, HEAD_SIZE, HEAD_SIZE, BADGE);
+            let initial: String = stats.player.chars().take(1).collect();
+            let at = Point::new(PAD + 27, PAD + 22);
+            canvas.text(at, &initial.to_uppercase(), TITLE);
+        }
+    }
+    let left = PAD + HEAD_SIZE as i32 + PAD;
+    canvas.text(Point::new(left, PAD + 12), &fit(&stats.player, 31), TITLE);
+    canvas.text(
+        Point::new(left, PAD + 38),
+        fmt.t("card.stats_subtitle"),
+        SMALL_MUTED,
+    );
+
+    let tiles = [
+        ("stats.money", stats.money.map(|v| fmt.money(v))),
+        ("stats.shards", stats.shards.map(|v| fmt.number(v))),
+        ("stats.kd", stats.kd_ratio().map(|kd| format!("{:.2}", kd))),
+        (
+            "stats.playtime",
+            stats.playtime_ms.map(|v| fmt.duration_ms(v)),
+        ),
+        (
+            "stats.blocks_placed",
+            stats.placed_blocks.map(|v| fmt.number(v)),
+        ),
+        (
+            "stats.blocks_broken",
+            stats.broken_blocks.map(|v| fmt.number(v)),
+        ),
+    ];
+    for (i, (label, value)) in tiles.iter().enumerate() {
+        let x = PAD + (i as u32 % 3 * (TILE_WIDTH + GAP)) as i32;
+        let y = (header + PAD as u32 + i as u32 / 3 * (TILE_HEIGHT + GAP)) as i32;
+        canvas.rounded_rect(x, y, TILE_WIDTH, TILE_HEIGHT, STRIPE);
+        canvas.text(
+            Point::new(x + 8, y + 7),
+            &fit(fmt.t(label), 19),
+            SMALL_MUTED,
+        );
+        let value = value.as_deref().unwrap_or("-");
+        canvas.text(Point::new(x + 8, y + 21), &fit(value, 12), VALUE);
+    }
+    canvas.to_png()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::status::StatusConfig;
+    use std::collections::HashMap;
+
+    fn decode(png: &[u8]) -> (u32, u32, Vec<u8>) {
+        let decoder = png::Decoder::new(png);
+        let mut reader = decoder.read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        (info.width, info.height, data)
+    }
+
+    #[test]
+    fn stats_card_draws_the_head_or_an_initial() {
+        let stats = PlayerStats {
+            player: "Steve".into(),
+            money: Some(1_234_567),
+            kills: Some(10),
+            deaths: Some(4),
+            ..Default::default()
+        };
+        let head = Canvas::new(8, 8, OFFLINE).to_png().unwrap();
+        let fmt = Format::default();
+        let pixel = |png: &[u8]| {
+            let (width, _, data) = decode(png);
+            let i = (((PAD as u32 + 32) * SCALE * width + (PAD as u32 + 4) * SCALE) * 3) as usize;
+            Rgb888::new(data[i], data[i + 1], data[i + 2])
+        };
+        assert_eq!(
+            pixel(&stats_card(&stats, Some(&head), &fmt).unwrap()),
+            OFFLINE
+        );
+        assert_eq!(pixel(&stats_card(&stats, None, &fmt).unwrap()), BADGE);
+        assert_eq!(
+            pixel(&stats_card(&stats, Some(b"not a png"), &fmt).unwrap()),
+            BADGE
+        );
+    }
+
+    #[test]
+    fn fit_cuts_long_text() {
+        assert_eq!(fit("Steve", 10), "Steve");
+        assert_eq!(fit("Notch_the_Creator", 8), "Notch_..");
+    }
+
+    fn status(layout: Layout, details: Vec<Detail>) -> TeamStatus {
+        let member = |ign: &str, rank: Rank| TeamMember {
+            ign: ign.into(),
+            rank,
+            country: "NL".into(),
+            ..Default::default()
+        };
+        let team = Team {
+            name: "Donut Crew".into(),
+            members: vec![member("Steve", Rank::Member), member("Alex", Rank::Owner)],
+            ..Default::default()
+        };
+        let mut presence = HashMap::new();
+        presence.insert(
+            "alex".to_string(),
+            Presence::Online {
+                location: Some("spawn".into()),
+            },
+        );
+        presence.insert("steve".to_string(), Presence::Offline);
+        TeamStatus {
+            team,
+            presence,
+            config: StatusConfig {
+                layout,
+                details,
+                group_by_squad: false,
+                image: true,
+            },
+            at: None,
+        }
+    }
+
+    /// The colour at `(x, y)` in drawn (unscaled) pixels.
+    fn pixel_at(png: &[u8], x: u32, y: u32) -> Rgb888 {
+        let (width, _, data) = decode(png);
+        let i = ((y * SCALE * width + x * SCALE) * 3) as usize;
+        Rgb888::new(data[i], data[i + 1], data[i + 2])
+    }
+
+    #[test]
+    fn team_card_groups_members_by_rank() {
+        let status = status(Layout::Compact, Vec::new());
+        let png = team_status_card(&status, &HashMap::new(), &Format::default()).unwrap();
+        let (width, height, _) = decode(&png);
+        assert_eq!(width, CARD_WIDTH * SCALE);
+        // Column labels, then the Owner and Member headings each with one member
+        assert_eq!(
+            height,
+            (HEADER_HEIGHT + ROW_HEIGHT * 5 + FOOTER_HEIGHT) * SCALE
+        );
+
+        // Alex sorts first as the owner and is online, Steve is offline
+        let dot = |row: u32| pixel_at(&png, PAD as u32 + 4, HEADER_HEIGHT + ROW_HEIGHT * row + 10);
+        assert_eq!(dot(2), ONLINE);
+        assert_eq!(dot(4), OFFLINE);
+    }
+
+    #[test]
+    fn online_only_card_skips_offline_members_and_draws_flags() {
+        let status = status(Layout::OnlineOnly, vec![Detail::Country]);
+        let mut flags = HashMap::new();
+        flags.insert(
+            "NL".to_string(),
+            Canvas::new(4, 3, OFFLINE).to_png().unwrap(),
+        );
+        let png = team_status_card(&status, &flags, &Format::default()).unwrap();
+        let (_, height, _) = decode(&png);
+        assert_eq!(
+            height,
+            (HEADER_HEIGHT + ROW_HEIGHT * 3 + FOOTER_HEIGHT) * SCALE
+        );
+        let flag = pixel_at(
+            &png,
+            COL_COUNTRY as u32 + 5,
+            HEADER_HEIGHT + ROW_HEIGHT * 2 + 10,
+        );
+        assert_eq!(flag, OFFLINE);
+    }
+}
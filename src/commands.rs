@@ -1,20 +1,52 @@
+use chrono::Utc;
 use reqwest::Client;
 use serenity::{
-    builder::CreateApplicationCommands,
+    builder::{CreateApplicationCommandOption, CreateApplicationCommands, CreateEmbed},
     model::{
         application::command::CommandOptionType,
         application::interaction::InteractionResponseType,
+        channel::AttachmentType,
+        guild::Member,
+        id::ChannelId,
         prelude::{
-            interaction::application_command::ApplicationCommandInteraction,
+            component::ActionRowComponent,
+            interaction::application_command::{
+                ApplicationCommandInteraction, CommandDataOptionValue,
+            },
+            interaction::autocomplete::AutocompleteInteraction,
             interaction::message_component::MessageComponentInteraction,
+            interaction::modal::ModalSubmitInteraction,
         },
     },
     prelude::*,
 };
 
-use crate::api::{auction_embed, send_api, send_leaderboard, send_stats};
-use crate::components::{auction_buttons, lb_buttons, txn_buttons};
-use crate::team::{self, country_flag, Rank, TeamMember};
+use crate::api::{
+    auction_embed, fetch_presence, fetch_stats, player_exists, send_api, send_leaderboard,
+    send_stats, send_stats_card, sort_name,
+};
+use crate::applications::{self, Application, ApplicationState, ApplicationView};
+use crate::audit::{self, AuditView, Change};
+use crate::components::{
+    add_modal, application_buttons, apply_modal, auction_buttons, edit_modal, import_buttons,
+    lb_buttons, profile_buttons, rank_menu, restore_buttons, txn_buttons,
+};
+use crate::countries::{self, country_flag};
+use crate::goals::{self, Goal, GoalProgress, GoalStat};
+use crate::history::{self, RestorePreview, Undone};
+use crate::i18n::Describe;
+use crate::locale::{Format, Language, NumberStyle};
+use crate::models::{Comparison, PlayerLookup};
+use crate::players;
+use crate::render::{render_or_message, Render};
+use crate::roster::{self, PendingImport, RosterDiff, RosterFormat};
+use crate::settings;
+use crate::status::{self, Layout, TeamStatus};
+use crate::team::{self, CustomField, FieldKind, MemberUpdate, Rank, Team, TeamMember};
+use crate::trader::send_trader;
+
+/// Players `/compare` takes at most; a wider table wraps on phones.
+const MAX_COMPARED: usize = 4;
 
 pub fn register_all_commands(
     commands: &mut CreateApplicationCommands,
@@ -22,30 +54,51 @@ pub fn register_all_commands(
     commands
         .create_application_command(|c| {
             c.name("lookup")
-                .description("Get player info from DonutSMP")
+                .describe("cmd.lookup")
                 .create_option(|o| {
                     o.name("user")
-                        .description("Username or UUID")
+                        .describe("opt.user")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
                 })
         })
         .create_application_command(|c| {
             c.name("stats")
-                .description("Get detailed stats/profile from DonutSMP")
+                .describe("cmd.stats")
                 .create_option(|o| {
                     o.name("user")
-                        .description("Username or UUID")
+                        .describe("opt.user")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_option(|o| {
+                    o.name("card")
+                        .describe("opt.stats_card")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
                 })
         })
+        .create_application_command(|c| {
+            c.name("compare").describe("cmd.compare");
+            for i in 1..=MAX_COMPARED {
+                c.create_option(|o| {
+                    o.name(format!("ign{}", i))
+                        .describe("opt.compare_player")
+                        .kind(CommandOptionType::String)
+                        .required(i <= 2)
+                        .set_autocomplete(true)
+                });
+            }
+            c
+        })
         .create_application_command(|c| {
             c.name("leaderboard")
-                .description("Show DonutSMP leaderboards")
+                .describe("cmd.leaderboard")
                 .create_option(|o| {
                     o.name("type")
-                        .description("Leaderboard type")
+                        .describe("opt.leaderboard_type")
                         .kind(CommandOptionType::String)
                         .required(true)
                         .add_string_choice("💰 Money", "money")
@@ -61,29 +114,29 @@ pub fn register_all_commands(
                 })
                 .create_option(|o| {
                     o.name("page")
-                        .description("Page number (default 1)")
+                        .describe("opt.page")
                         .kind(CommandOptionType::Integer)
                         .required(false)
                 })
         })
         .create_application_command(|c| {
             c.name("auction")
-                .description("Show auction house entries")
+                .describe("cmd.auction")
                 .create_option(|o| {
                     o.name("page")
-                        .description("Page number (default 1)")
+                        .describe("opt.page")
                         .kind(CommandOptionType::Integer)
                         .required(false)
                 })
                 .create_option(|o| {
                     o.name("search")
-                        .description("Search for specific items (e.g. diamond, sword)")
+                        .describe("opt.search_examples")
                         .kind(CommandOptionType::String)
                         .required(false)
                 })
                 .create_option(|o| {
                     o.name("sort")
-                        .description("Sort order")
+                        .describe("opt.sort")
                         .kind(CommandOptionType::String)
                         .required(false)
                         .add_string_choice("💰 Lowest Price", "lowest_price")
@@ -94,22 +147,22 @@ pub fn register_all_commands(
         })
         .create_application_command(|c| {
             c.name("auction-transactions")
-                .description("Show auction house transaction history")
+                .describe("cmd.auction_transactions")
                 .create_option(|o| {
                     o.name("page")
-                        .description("Page number (default 1)")
+                        .describe("opt.page")
                         .kind(CommandOptionType::Integer)
                         .required(false)
                 })
                 .create_option(|o| {
                     o.name("search")
-                        .description("Search for specific items")
+                        .describe("opt.search")
                         .kind(CommandOptionType::String)
                         .required(false)
                 })
                 .create_option(|o| {
                     o.name("sort")
-                        .description("Sort order")
+                        .describe("opt.sort")
                         .kind(CommandOptionType::String)
                         .required(false)
                         .add_string_choice("💰 Lowest Price", "lowest_price")
@@ -118,44 +171,191 @@ pub fn register_all_commands(
                         .add_string_choice("📅 Last Listed", "last_listed")
                 })
         })
+        .create_application_command(|c| {
+            c.name("trader")
+                .describe("cmd.trader")
+                .create_option(|o| {
+                    o.name("ign")
+                        .describe("opt.ign")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("link")
+                .describe("cmd.link")
+                .create_option(|o| {
+                    o.name("ign")
+                        .describe("opt.link_ign")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("goal")
+                .describe("cmd.goal")
+                .create_option(|o| {
+                    o.name("set")
+                        .describe("cmd.goal.set")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(goal_stat_option)
+                        .create_sub_option(|o| {
+                            o.name("target")
+                                .describe("opt.goal_target")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("remove")
+                        .describe("cmd.goal.remove")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(goal_stat_option)
+                })
+                .create_option(|o| {
+                    o.name("list")
+                        .describe("cmd.goal.list")
+                        .kind(CommandOptionType::SubCommand)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("settings")
+                .describe("cmd.settings")
+                .create_option(|o| {
+                    o.name("numbers")
+                        .describe("opt.numbers")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .add_string_choice("1.234.567", "dot")
+                        .add_string_choice("1,234,567", "comma")
+                        .add_string_choice("1 234 567", "space")
+                        .add_string_choice("1.2M (compact)", "compact")
+                })
+                .create_option(|o| {
+                    o.name("language")
+                        .describe("opt.language")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .add_string_choice("English (1d 2h 5m)", "en")
+                        .add_string_choice("Nederlands (1d 2u 5m)", "nl")
+                })
+                .create_option(|o| {
+                    o.name("server")
+                        .describe("opt.server")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("reset")
+                        .describe("opt.reset")
+                        .kind(CommandOptionType::Boolean)
+                        .required(false)
+                })
+        })
         .create_application_command(|c| {
             c.name("help")
-                .description("Show all available commands with descriptions")
+                .describe("cmd.help")
         })
+        .create_application_command(|c| c.name("apply").describe("cmd.apply"))
         .create_application_command(|c| {
             c.name("team-name")
-                .description("Set or view the team name")
+                .describe("cmd.team_name")
                 .create_option(|o| {
                     o.name("name")
-                        .description("New team name (omit to view current)")
+                        .describe("opt.team_name")
                         .kind(CommandOptionType::String)
                         .required(false)
                 })
         })
         .create_application_command(|c| {
             c.name("team-add")
-                .description("Add or update a team member")
+                .describe("cmd.team_add")
                 .create_option(|o| {
                     o.name("ign")
-                        .description("In-game name")
+                        .describe("opt.ign")
                         .kind(CommandOptionType::String)
-                        .required(true)
+                        .required(false)
                 })
                 .create_option(|o| {
                     o.name("country")
-                        .description("Country")
+                        .describe("opt.country")
                         .kind(CommandOptionType::String)
-                        .required(true)
+                        .required(false)
+                        .set_autocomplete(true)
                 })
                 .create_option(|o| {
                     o.name("skill")
-                        .description("Skill")
+                        .describe("opt.skill")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("rank")
+                        .describe("opt.rank")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .add_string_choice("Owner", "owner")
+                        .add_string_choice("Admin", "admin")
+                        .add_string_choice("Member", "member")
+                })
+                .create_option(|o| {
+                    o.name("about")
+                        .describe("opt.about")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("discord")
+                        .describe("opt.discord")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("timezone")
+                        .describe("opt.timezone")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("joined")
+                        .describe("opt.joined")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("base")
+                        .describe("opt.base")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-edit")
+                .describe("cmd.team_edit")
+                .create_option(|o| {
+                    o.name("ign")
+                        .describe("opt.ign")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
+                })
+                .create_option(|o| {
+                    o.name("country")
+                        .describe("opt.country")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .set_autocomplete(true)
+                })
+                .create_option(|o| {
+                    o.name("skill")
+                        .describe("opt.skill")
+                        .kind(CommandOptionType::String)
+                        .required(false)
                 })
                 .create_option(|o| {
                     o.name("rank")
-                        .description("Rank")
+                        .describe("opt.rank")
                         .kind(CommandOptionType::String)
                         .required(false)
                         .add_string_choice("Owner", "owner")
@@ -164,40 +364,472 @@ pub fn register_all_commands(
                 })
                 .create_option(|o| {
                     o.name("about")
-                        .description("About")
+                        .describe("opt.about")
                         .kind(CommandOptionType::String)
                         .required(false)
                 })
                 .create_option(|o| {
                     o.name("discord")
-                        .description("Discord tag (e.g. Name#1234)")
+                        .describe("opt.discord")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("timezone")
+                        .describe("opt.timezone")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("joined")
+                        .describe("opt.joined")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                })
+                .create_option(|o| {
+                    o.name("base")
+                        .describe("opt.base")
                         .kind(CommandOptionType::String)
                         .required(false)
                 })
         })
         .create_application_command(|c| {
             c.name("team-remove")
-                .description("Remove a team member by IGN")
+                .describe("cmd.team_remove")
                 .create_option(|o| {
                     o.name("ign")
-                        .description("In-game name")
+                        .describe("opt.ign")
                         .kind(CommandOptionType::String)
                         .required(true)
+                        .set_autocomplete(true)
                 })
         })
         .create_application_command(|c| {
-            c.name("team-list").description("List the team and members")
+            c.name("team-list")
+                .describe("cmd.team_list")
+                .create_option(|o| {
+                    o.name("squad")
+                        .describe("opt.squad_filter")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .set_autocomplete(true)
+                })
+                .create_option(|o| {
+                    o.name("layout")
+                        .describe("opt.layout")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .add_string_choice("Compact", "compact")
+                        .add_string_choice("Detailed", "detailed")
+                        .add_string_choice("Online only", "online")
+                })
         })
         .create_application_command(|c| {
             c.name("online")
-                .description("Check which team members are online")
+                .describe("cmd.online")
+                .create_option(|o| {
+                    o.name("squad")
+                        .describe("opt.squad_filter")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .set_autocomplete(true)
+                })
         })
         .create_application_command(|c| {
             c.name("team-help")
-                .description("Show team commands and usage")
+                .describe("cmd.team_help")
+        })
+        .create_application_command(|c| {
+            c.name("team-countries")
+                .describe("cmd.team_countries")
+        })
+        .create_application_command(|c| {
+            c.name("team-export")
+                .describe("cmd.team_export")
+                .create_option(|o| {
+                    o.name("format")
+                        .describe("opt.format")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .add_string_choice("JSON", "json")
+                        .add_string_choice("CSV", "csv")
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-audit")
+                .describe("cmd.team_audit")
+                .create_option(|o| {
+                    o.name("ign")
+                        .describe("opt.audit_ign")
+                        .kind(CommandOptionType::String)
+                        .required(false)
+                        .set_autocomplete(true)
+                })
+                .create_option(|o| {
+                    o.name("days")
+                        .describe("opt.days")
+                        .kind(CommandOptionType::Integer)
+                        .required(false)
+                        .min_int_value(1)
+                        .max_int_value(365)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-undo")
+                .describe("cmd.team_undo")
+        })
+        .create_application_command(|c| {
+            c.name("team-restore")
+                .describe("cmd.team_restore")
+                .create_option(|o| {
+                    o.name("version")
+                        .describe("opt.version")
+                        .kind(CommandOptionType::Integer)
+                        .required(true)
+                        .min_int_value(1)
+                        .set_autocomplete(true)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-import")
+                .describe("cmd.team_import")
+                .create_option(|o| {
+                    o.name("file")
+                        .describe("opt.import_file")
+                        .kind(CommandOptionType::Attachment)
+                        .required(true)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-profile")
+                .describe("cmd.team_profile")
+                .create_option(|o| {
+                    o.name("ign")
+                        .describe("opt.ign")
+                        .kind(CommandOptionType::String)
+                        .required(true)
+                        .set_autocomplete(true)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-field")
+                .describe("cmd.team_field")
+                .create_option(|o| {
+                    o.name("define")
+                        .describe("cmd.team_field.define")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("name")
+                                .describe("opt.field_key")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("kind")
+                                .describe("opt.field_kind")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .add_string_choice("Text", "text")
+                                .add_string_choice("Number", "number")
+                                .add_string_choice("Date", "date")
+                                .add_string_choice("Link", "link")
+                                .add_string_choice("Yes/No", "toggle")
+                        })
+                        .create_sub_option(|o| {
+                            o.name("label")
+                                .describe("opt.field_label")
+                                .kind(CommandOptionType::String)
+                                .required(false)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("remove")
+                        .describe("cmd.team_field.remove")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("name")
+                                .describe("opt.field_key")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("set")
+                        .describe("cmd.team_field.set")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("ign")
+                                .describe("opt.ign")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("name")
+                                .describe("opt.field_key")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("value")
+                                .describe("opt.field_value")
+                                .kind(CommandOptionType::String)
+                                .required(false)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("list")
+                        .describe("cmd.team_field.list")
+                        .kind(CommandOptionType::SubCommand)
+                })
+        })
+        .create_application_command(|c| {
+            c.name("team-squad")
+                .describe("cmd.team_squad")
+                .create_option(|o| {
+                    o.name("create")
+                        .describe("cmd.team_squad.create")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("name")
+                                .describe("opt.squad_name")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("remove")
+                        .describe("cmd.team_squad.remove")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("squad")
+                                .describe("opt.squad")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .set_autocomplete(true)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("assign")
+                        .describe("cmd.team_squad.assign")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("ign")
+                                .describe("opt.ign")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .set_autocomplete(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("squad")
+                                .describe("opt.squad_assign")
+                                .kind(CommandOptionType::String)
+                                .required(false)
+                                .set_autocomplete(true)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("lead")
+                        .describe("cmd.team_squad.lead")
+                        .kind(CommandOptionType::SubCommand)
+                        .create_sub_option(|o| {
+                            o.name("squad")
+                                .describe("opt.squad")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .set_autocomplete(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("ign")
+                                .describe("opt.ign")
+                                .kind(CommandOptionType::String)
+                                .required(true)
+                                .set_autocomplete(true)
+                        })
+                        .create_sub_option(|o| {
+                            o.name("remove")
+                                .describe("opt.lead_remove")
+                                .kind(CommandOptionType::Boolean)
+                                .required(false)
+                        })
+                })
+                .create_option(|o| {
+                    o.name("list")
+                        .describe("cmd.team_squad.list")
+                        .kind(CommandOptionType::SubCommand)
+                })
         })
 }
 
+/// The required `stat` option of `/goal set` and `/goal remove`.
+fn goal_stat_option(o: &mut CreateApplicationCommandOption) -> &mut CreateApplicationCommandOption {
+    o.name("stat")
+        .describe("opt.goal_stat")
+        .kind(CommandOptionType::String)
+        .required(true);
+    for stat in GoalStat::ALL {
+        let label = crate::i18n::text(Language::En, stat.label_key());
+        o.add_string_choice(format!("{} {}", stat.emoji(), label), stat.as_str());
+    }
+    o
+}
+
+/// `🏪 Auction House (Page 2) | 🔍 'diamond' | 💰 Lowest Price`
+fn auction_title(
+    heading_key: &str,
+    page: u32,
+    search: Option<&str>,
+    sort: Option<&str>,
+    fmt: &Format,
+) -> String {
+    let mut title_parts = vec![fmt.tf(heading_key, &[&page])];
+    if let Some(search_term) = search {
+        title_parts.push(format!("🔍 '{}'", search_term));
+    }
+    if let Some(sort_type) = sort {
+        title_parts.push(sort_name(sort_type, fmt).to_string());
+    }
+    title_parts.join(" | ")
+}
+
+/// The reply to a saved `/team-add`.
+fn saved_embed(member: &TeamMember, team_name: &str, updated: bool, fmt: &Format) -> CreateEmbed {
+    let action = if updated {
+        "team.member_updated"
+    } else {
+        "team.member_added"
+    };
+    let flag = country_flag(&member.country);
+    let flag_space = if flag.is_empty() {
+        "".to_string()
+    } else {
+        format!("{} ", flag)
+    };
+    let mut embed = CreateEmbed::default();
+    embed
+        .title(fmt.t("team.member_saved_title"))
+        .description(fmt.tf(
+            action,
+            &[
+                &flag_space,
+                &member.ign,
+                &team_name,
+                &fmt.t(member.rank.label_key()),
+            ],
+        ))
+        .color(crate::constants::EMBED_COLOR_ACCENT);
+    embed
+}
+
+/// Saves a member from `/team-add` once the API knows their IGN and their
+/// country is recognised. `Err` holds the message only the adder should see.
+async fn add_member(
+    client: &Client,
+    donut_key: &str,
+    mut member: TeamMember,
+    by: u64,
+    fmt: &Format,
+) -> Result<(CreateEmbed, TeamMember), String> {
+    member.country = countries::resolve(&member.country, fmt)?;
+    match player_exists(client, donut_key, &member.ign).await {
+        Some(true) => {}
+        Some(false) => return Err(fmt.tf("team.unknown_player", &[&member.ign])),
+        None => return Err(fmt.tf("team.player_check_failed", &[&member.ign])),
+    }
+    let (team, updated) = team::upsert_member(member.clone(), by)
+        .map_err(|e| fmt.tf("team.member_save_failed", &[&e]))?;
+    Ok((saved_embed(&member, &team.name, updated, fmt), member))
+}
+
+/// Narrows `team` to the squad picked in the command's `squad` option, if
+/// any, adding the squad to the team name shown in titles. `Err` holds the
+/// message for a squad that doesn't exist.
+fn squad_filter(
+    cmd: &ApplicationCommandInteraction,
+    team: Team,
+    fmt: &Format,
+) -> Result<Team, String> {
+    let name = match cmd
+        .data
+        .options
+        .iter()
+        .find(|o| o.name == "squad")
+        .and_then(|o| o.value.as_ref())
+        .and_then(|v| v.as_str())
+    {
+        Some(name) => name,
+        None => return Ok(team),
+    };
+    let squad = match team.squad(name) {
+        Some(squad) => squad.name.clone(),
+        None => return Err(fmt.tf("squad.unknown", &[&name.trim()])),
+    };
+    let mut team = team.only_squad(&squad);
+    team.name = format!("{} · {}", team.name, squad);
+    Ok(team)
+}
+
+/// Whether the member behind an interaction has the Manage Server permission.
+fn can_manage(member: Option<&Member>) -> bool {
+    member
+        .and_then(|m| m.permissions)
+        .is_some_and(|p| p.manage_guild())
+}
+
+/// Fetches an uploaded file as text.
+async fn download(
+    client: &Client,
+    url: &str,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let res = client
+        .get(url)
+        .timeout(std::time::Duration::from_secs(15))
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(res.text().await?)
+}
+
+/// Lists what's wrong with an import file, keeping the message within
+/// Discord's 2000 character limit.
+fn import_errors(filename: &str, errors: &[String], fmt: &Format) -> String {
+    let mut message = fmt.tf("import.invalid", &[&filename]);
+    for (i, error) in errors.iter().enumerate() {
+        let line = format!("\n• {}", error);
+        if message.chars().count() + line.chars().count() > 1900 {
+            message.push('\n');
+            message.push_str(&fmt.tf("import.more_errors", &[&(errors.len() - i)]));
+            break;
+        }
+        message.push_str(&line);
+    }
+    message
+}
+
+/// The reply to a `/team-edit`: the member's new details, or an error only
+/// the editor should see.
+fn edit_outcome(
+    result: std::io::Result<(team::Team, Option<TeamMember>)>,
+    ign: &str,
+    fmt: &Format,
+) -> Result<CreateEmbed, String> {
+    match result {
+        Ok((_, Some(member))) => {
+            let mut embed = CreateEmbed::default();
+            embed
+                .title(fmt.t("team.member_saved_title"))
+                .description(format!(
+                    "{}\n\n{}",
+                    fmt.tf("team.member_edited", &[&member.ign]),
+                    member.details(fmt)
+                ))
+                .color(crate::constants::EMBED_COLOR_ACCENT);
+            Ok(embed)
+        }
+        Ok((_, None)) => Err(fmt.tf("team.not_a_member", &[&ign.trim()])),
+        Err(e) => Err(fmt.tf("team.member_save_failed", &[&e])),
+    }
+}
+
 pub async fn handle_command(
     client: &Client,
     donut_key: &str,
@@ -205,6 +837,7 @@ pub async fn handle_command(
     cmd: &ApplicationCommandInteraction,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let name = cmd.data.name.as_str();
+    let fmt = settings::format_for(cmd.user.id.0, &cmd.locale);
     match name {
         "lookup" => {
             let user = cmd
@@ -215,13 +848,16 @@ pub async fn handle_command(
                 .and_then(|v| v.as_str())
                 .unwrap();
             let path = format!("/v1/lookup/{}", user.replace(" ", "%20"));
+            let title = fmt.tf("lookup.title", &[&user]);
             send_api(
                 cmd,
                 ctx,
                 client,
                 donut_key,
                 &path,
-                &format!("🔍 Player Lookup: {}", user),
+                &title,
+                &fmt,
+                |json| render_or_message(PlayerLookup::from_json(json, user), json, &fmt),
             )
             .await?;
         }
@@ -233,8 +869,49 @@ pub async fn handle_command(
                 .and_then(|v| v.value.as_ref())
                 .and_then(|v| v.as_str())
                 .unwrap();
-            let path = format!("/v1/stats/{}", user.replace(" ", "%20"));
-            send_stats(cmd, ctx, client, donut_key, &path, user).await?;
+            let card = cmd
+                .data
+                .options
+                .iter()
+                .find(|o| o.name == "card")
+                .and_then(|o| o.value.as_ref())
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            if card {
+                send_stats_card(cmd, ctx, client, donut_key, user, &fmt).await?;
+            } else {
+                let path = format!("/v1/stats/{}", user.replace(" ", "%20"));
+                send_stats(cmd, ctx, client, donut_key, &path, user, &fmt).await?;
+            }
+        }
+        "compare" => {
+            // In option order, each player once
+            let mut igns: Vec<String> = Vec::new();
+            for ign in cmd
+                .data
+                .options
+                .iter()
+                .filter_map(|o| o.value.as_ref().and_then(|v| v.as_str()))
+                .map(str::trim)
+            {
+                if !ign.is_empty() && !igns.iter().any(|i| i.eq_ignore_ascii_case(ign)) {
+                    igns.push(ign.to_string());
+                }
+            }
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await?;
+            let mut comparison = Comparison::default();
+            for ign in igns {
+                match fetch_stats(client, donut_key, &ign).await {
+                    Some(stats) => comparison.players.push(stats),
+                    None => comparison.missing.push(ign),
+                }
+            }
+            let embed = comparison.render(&fmt).to_embed();
+            cmd.edit_original_interaction_response(&ctx.http, |r| r.add_embed(embed))
+                .await?;
         }
         "leaderboard" => {
             let lb_type = cmd
@@ -252,7 +929,7 @@ pub async fn handle_command(
                 .and_then(|v| v.as_i64())
                 .unwrap_or(1) as u32;
             let path = format!("/v1/leaderboards/{}/{}", lb_type, page);
-            send_leaderboard(cmd, ctx, client, donut_key, &path, lb_type, page).await?;
+            send_leaderboard(cmd, ctx, client, donut_key, &path, lb_type, page, &fmt).await?;
         }
         "auction" => {
             let page = cmd
@@ -278,32 +955,19 @@ pub async fn handle_command(
                 .and_then(|v| v.as_str());
 
             let path = format!("/v1/auction/list/{}", page);
+            let title = auction_title("auction.title", page, search, sort, &fmt);
 
-            let mut title_parts = vec![format!("🏪 Auction House (Page {})", page)];
-            if let Some(search_term) = search {
-                title_parts.push(format!("🔍 '{}'", search_term));
-            }
-            if let Some(sort_type) = sort {
-                let sort_emoji = match sort_type {
-                    "lowest_price" => "💰",
-                    "highest_price" => "💸",
-                    "recently_listed" => "🕒",
-                    "last_listed" => "📅",
-                    _ => "📊",
-                };
-                title_parts.push(format!("{} {}", sort_emoji, sort_type.replace("_", " ")));
-            }
-            let title = title_parts.join(" | ");
-
-            let (embed, _) =
-                auction_embed(client, donut_key, &path, &title, search, sort, page).await?;
+            let (embed, _) = auction_embed(
+                ctx, client, donut_key, &path, &title, search, sort, page, &fmt,
+            )
+            .await?;
 
             cmd.create_interaction_response(&ctx.http, |response| {
                 response
                     .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
                     .interaction_response_data(|data| {
                         data.add_embed(embed)
-                            .components(|c| auction_buttons(c, page, search, sort))
+                            .components(|c| auction_buttons(c, page, search, sort, &fmt))
                     })
             }).await?;
         }
@@ -311,32 +975,39 @@ pub async fn handle_command(
             let page = cmd
                 .data
                 .options
-                .get(0)
+                .iter()
+                .find(|opt| opt.name == "page")
                 .and_then(|v| v.value.as_ref())
                 .and_then(|v| v.as_i64())
                 .unwrap_or(1) as u32;
             let search = cmd
                 .data
                 .options
-                .get(1)
+                .iter()
+                .find(|opt| opt.name == "search")
                 .and_then(|v| v.value.as_ref())
                 .and_then(|v| v.as_str());
             let sort = cmd
                 .data
                 .options
-                .get(2)
+                .iter()
+                .find(|opt| opt.name == "sort")
                 .and_then(|v| v.value.as_ref())
                 .and_then(|v| v.as_str());
 
+            let title = auction_title("auction.transactions_title", page, search, sort, &fmt);
+
             let path = format!("/v1/auction/transactions/{}", page);
             let (embed, _items) = auction_embed(
+                ctx,
                 client,
                 donut_key,
                 &path,
-                "📜 Auction Transactions",
+                &title,
                 search,
                 sort,
                 page,
+                &fmt,
             )
             .await?;
 
@@ -345,120 +1016,284 @@ pub async fn handle_command(
                     .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
                     .interaction_response_data(|data| {
                         data.add_embed(embed)
-                            .components(|c| txn_buttons(c, page, search, sort))
+                            .components(|c| txn_buttons(c, page, search, sort, &fmt))
                     })
             }).await?;
         }
-        "help" => {
-            cmd.create_interaction_response(&ctx.http, |response| {
-                response
-                    .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
-                    .interaction_response_data(|data| {
-            data.embed(|embed| {
-                            embed
-                                .title("🤖 DonutSMP Bot Commands")
-                                .description("Here are all available commands:")
-                .color(crate::constants::EMBED_COLOR_ACCENT)
-                                .field("**👤 Player Commands**", 
-                                    "`/lookup <user>` - Get player info\n\
-                                     `/stats <user>` - Show player statistics", 
-                                    false)
-                                .field("**🏆 Leaderboard Commands**", 
-                                    "`/leaderboard <type> [page]` - Show various leaderboards", 
-                                    false)
-                                .field("**🏪 Auction Commands**", 
-                                    "`/auction [page] [search] [sort]` - Browse auction house\n\
-                                     `/auction-transactions [page] [search] [sort]` - View transaction history", 
-                                    false)
-                                .field("**👥 Team Commands**", 
-                                    "`/team-help` - Show team commands and usage", 
-                                    false)
-                                .field("**ℹ️ Other Commands**", 
-                                    "`/help` - Show this help message", 
-                                    false)
-                                .footer(|f| f.text("💡 [brackets] for optional parameters, <brackets> for required parameters"))
-                        })
-                    })
-            }).await?;
+        "trader" => {
+            let ign = cmd
+                .data
+                .options
+                .first()
+                .and_then(|v| v.value.as_ref())
+                .and_then(|v| v.as_str())
+                .unwrap();
+            send_trader(cmd, ctx, client, donut_key, ign.trim(), &fmt).await?;
         }
-        "team-help" => {
-            cmd.create_interaction_response(&ctx.http, |response| {
-                response
-                    .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
-                    .interaction_response_data(|data| {
-            data.embed(|embed| {
-                            embed
-                                .title("👥 Team Commands")
-                                .description("Manage your team stored by the bot:")
-                .color(crate::constants::EMBED_COLOR_ACCENT)
-                                .field(
-                                    "Commands",
-                                    "`/team-name [name]` - View or set the team name\n\
-                                     `/team-add <ign> <country> <skill> [rank] [about] [discord]` - Add or update a member\n\
-                                     `/team-remove <ign>` - Remove a member by IGN\n\
-                                     `/team-list` - Show members grouped by rank\n\
-                                     `/online` - Check who is online in your team\n\
-                                     `/team-help` - Show this team help",
-                                    false,
-                                )
-                                 .footer(|f| f.text("💡 [brackets] for optional parameters, <brackets> for required parameters"))
+        "link" => {
+            let maybe_ign = cmd
+                .data
+                .options
+                .first()
+                .and_then(|v| v.value.as_ref())
+                .and_then(|v| v.as_str());
+            let description = match maybe_ign {
+                Some(ign) => match players::link(cmd.user.id.0, ign) {
+                    Ok(()) => fmt.tf("link.linked", &[&ign.trim()]),
+                    Err(e) => fmt.tf("link.failed", &[&e]),
+                },
+                None => match players::linked_ign(cmd.user.id.0) {
+                    Some(ign) => fmt.tf("link.current", &[&ign]),
+                    None => fmt.t("link.none").to_string(),
+                },
+            };
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.embed(|e| {
+                            e.title(fmt.t("link.title"))
+                                .description(description)
+                                .color(crate::constants::EMBED_COLOR_ACCENT)
                         })
+                        .ephemeral(true)
                     })
-            }).await?;
+            })
+            .await?;
         }
-        "online" => {
-            let team = team::load();
-            if team.members.is_empty() {
+        "goal" => {
+            let sub = match cmd.data.options.first() {
+                Some(sub) => sub,
+                None => return Err("Missing subcommand".into()),
+            };
+            let text = |name: &str| {
+                sub.options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.value.as_ref())
+                    .and_then(|v| v.as_str())
+                    .map(str::trim)
+            };
+            let user_id = cmd.user.id.0;
+            let stat = text("stat").and_then(GoalStat::parse);
+            let target = stat.zip(text("target")).map(|(s, t)| (s, s.parse_target(t)));
+            let ign = players::linked_ign(user_id);
+
+            let notice = match (sub.name.as_str(), &ign, stat, target) {
+                (_, None, _, _) => Some(fmt.t("goal.not_linked").to_string()),
+                ("set" | "remove", _, None, _) => Some(fmt.t("goal.unknown_stat").to_string()),
+                ("set", _, _, Some((stat, None))) => {
+                    let example = if stat == GoalStat::Playtime {
+                        "500h"
+                    } else {
+                        "10M"
+                    };
+                    Some(fmt.tf("goal.invalid_target", &[&example]))
+                }
+                ("remove", _, Some(stat), _) => Some(match goals::remove(user_id, stat) {
+                    Ok(true) => fmt.tf("goal.removed", &[&fmt.t(stat.label_key())]),
+                    Ok(false) => fmt.tf("goal.not_set", &[&fmt.t(stat.label_key())]),
+                    Err(e) => fmt.tf("goal.save_failed", &[&e]),
+                }),
+                _ => None,
+            };
+            if let Some(notice) = notice {
                 cmd.create_interaction_response(&ctx.http, |r| {
                     r.kind(InteractionResponseType::ChannelMessageWithSource)
-                        .interaction_response_data(|d| {
-                            d.embed(|e| {
-                                e.title("👥 Team Online Status")
-                                    .description("No members yet. Use /team-add to add someone.")
-                                    .color(crate::constants::EMBED_COLOR_ACCENT)
-                            })
-                        })
+                        .interaction_response_data(|d| d.content(notice).ephemeral(true))
                 })
                 .await?;
                 return Ok(());
             }
+            // Unlinked users got a notice above
+            let ign = ign.unwrap_or_default();
 
-            let mut lines: Vec<String> = Vec::new();
-            for m in &team.members {
-                let path = format!("/v1/lookup/{}", m.ign.replace(" ", "%20"));
-                let url = format!("https://api.donutsmp.net{}", path);
-                let res = client
-                    .get(&url)
-                    .bearer_auth(donut_key)
-                    .timeout(std::time::Duration::from_secs(10))
-                    .send()
-                    .await;
-
-                let prefix = match res {
-                    Ok(resp) if resp.status().is_success() => "🟢",
-                    _ => "🔴",
+            // Setting a goal is private; the list can be shown off
+            let setting = sub.name == "set";
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                    .interaction_response_data(|d| d.ephemeral(setting))
+            })
+            .await?;
+            let stats = fetch_stats(client, donut_key, &ign).await;
+
+            let mut intro = None;
+            if let Some((stat, Some(target))) = target.filter(|_| setting) {
+                let current = stats.as_ref().and_then(|s| stat.value(s));
+                if current.is_some_and(|v| v >= target) {
+                    let message = fmt.tf(
+                        "goal.already_reached",
+                        &[&fmt.t(stat.label_key()), &stat.show(target, &fmt)],
+                    );
+                    cmd.edit_original_interaction_response(&ctx.http, |r| r.content(message))
+                        .await?;
+                    return Ok(());
+                }
+                let goal = Goal {
+                    user_id,
+                    ign: ign.clone(),
+                    stat,
+                    target,
+                    set_at: Utc::now(),
+                    reached: None,
+                    locale: cmd.locale.clone(),
                 };
-                lines.push(format!("{} {}", prefix, m.ign));
+                intro = Some(match goals::set(goal) {
+                    Ok(()) => fmt.tf(
+                        "goal.saved",
+                        &[&fmt.t(stat.label_key()), &stat.show(target, &fmt)],
+                    ),
+                    Err(e) => fmt.tf("goal.save_failed", &[&e]),
+                });
+            }
+
+            let progress = GoalProgress {
+                goals: goals::active_for(user_id),
+                ign,
+                stats,
+            };
+            let mut page = progress.render(&fmt);
+            if let Some(intro) = intro {
+                page = page.description(intro);
             }
+            let embed = page.to_embed();
+            cmd.edit_original_interaction_response(&ctx.http, |r| r.add_embed(embed))
+                .await?;
+        }
+        "settings" => {
+            let option = |key: &str| {
+                cmd.data
+                    .options
+                    .iter()
+                    .find(|o| o.name == key)
+                    .and_then(|o| o.value.as_ref())
+            };
+            let numbers = option("numbers")
+                .and_then(|v| v.as_str())
+                .and_then(NumberStyle::parse);
+            let language = option("language")
+                .and_then(|v| v.as_str())
+                .and_then(Language::parse);
+            let server = option("server").and_then(|v| v.as_bool()).unwrap_or(false);
+            let reset = option("reset").and_then(|v| v.as_bool()).unwrap_or(false);
 
-            let description = if lines.is_empty() {
-                "None".to_string()
+            let outcome = if server && !can_manage(cmd.member.as_ref()) {
+                Ok("settings.needs_manage")
+            } else if reset && !server {
+                settings::reset_user(cmd.user.id.0).map(|()| "settings.reset")
+            } else if numbers.is_some() || language.is_some() {
+                let target = if server { None } else { Some(cmd.user.id.0) };
+                settings::update(target, numbers, language).map(|_| {
+                    if server {
+                        "settings.server_updated"
+                    } else {
+                        "settings.updated"
+                    }
+                })
             } else {
-                lines.join("\n")
+                Ok("settings.hint")
             };
 
+            // Answer in the format that now applies, so a language change shows at once
+            let fmt = if server {
+                settings::guild_format()
+            } else {
+                settings::format_for(cmd.user.id.0, &cmd.locale)
+            };
+            let description = match outcome {
+                Ok(key) => fmt.t(key).to_string(),
+                Err(e) => fmt.tf("settings.failed", &[&e]),
+            };
+            let heading = if server {
+                fmt.t("settings.server_heading")
+            } else {
+                fmt.t("settings.user_heading")
+            };
             cmd.create_interaction_response(&ctx.http, |r| {
                 r.kind(InteractionResponseType::ChannelMessageWithSource)
                     .interaction_response_data(|d| {
                         d.embed(|e| {
-                            e.title(format!("👥 {} — Online Status", team.name))
+                            e.title(fmt.t("settings.title"))
                                 .description(description)
+                                .field(
+                                    heading,
+                                    fmt.tf(
+                                        "settings.summary",
+                                        &[
+                                            &fmt.numbers.as_str(),
+                                            &fmt.numbers.example(),
+                                            &fmt.language.name(),
+                                            &fmt.money(1_234_567),
+                                            &fmt.duration_ms(93_900_000),
+                                        ],
+                                    ),
+                                    false,
+                                )
                                 .color(crate::constants::EMBED_COLOR_ACCENT)
                         })
+                        .ephemeral(true)
                     })
             })
             .await?;
         }
+        "help" => {
+            cmd.create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|data| {
+            data.embed(|embed| {
+                            embed
+                                .title(fmt.t("help.title"))
+                                .description(fmt.t("help.description"))
+                .color(crate::constants::EMBED_COLOR_ACCENT)
+                                .field(fmt.t("help.player"), fmt.t("help.player_body"), false)
+                                .field(fmt.t("help.leaderboard"), fmt.t("help.leaderboard_body"), false)
+                                .field(fmt.t("help.auction"), fmt.t("help.auction_body"), false)
+                                .field(fmt.t("help.team"), fmt.t("help.team_body"), false)
+                                .field(fmt.t("help.other"), fmt.t("help.other_body"), false)
+                                .footer(|f| f.text(fmt.t("common.help_footer")))
+                        })
+                    })
+            }).await?;
+        }
+        "team-help" => {
+            cmd.create_interaction_response(&ctx.http, |response| {
+                response
+                    .kind(serenity::model::prelude::interaction::InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|data| {
+            data.embed(|embed| {
+                            embed
+                                .title(fmt.t("team_help.title"))
+                                .description(fmt.t("team_help.description"))
+                .color(crate::constants::EMBED_COLOR_ACCENT)
+                                .field(fmt.t("team_help.commands"), fmt.t("team_help.body"), false)
+                                .footer(|f| f.text(fmt.t("common.help_footer")))
+                        })
+                    })
+            }).await?;
+        }
+        "apply" => {
+            let message = if applications::config().is_none() {
+                Some(fmt.t("application.closed").to_string())
+            } else {
+                applications::pending_for(cmd.user.id.0)
+                    .map(|a| fmt.tf("application.already_pending", &[&a.id]))
+            };
+            match message {
+                Some(message) => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+                }
+                None => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::Modal)
+                            .interaction_response_data(|d| apply_modal(d, &fmt))
+                    })
+                    .await?;
+                }
+            }
+        }
         "team-name" => {
             let maybe_name = cmd
                 .data
@@ -467,17 +1302,16 @@ pub async fn handle_command(
                 .and_then(|v| v.value.as_ref())
                 .and_then(|v| v.as_str());
             if let Some(new_name) = maybe_name {
-                match team::set_name(new_name) {
+                match team::set_name(new_name, cmd.user.id.0) {
                     Ok(updated) => {
                         cmd.create_interaction_response(&ctx.http, |r| {
                             r.kind(InteractionResponseType::ChannelMessageWithSource)
                                 .interaction_response_data(|d| {
                                     d.embed(|e| {
-                                        e.title("👥 Team Name Updated")
-                                            .description(format!(
-                                                "Team name set to: **{}**",
-                                                updated.name
-                                            ))
+                                        e.title(fmt.t("team.name_updated_title"))
+                                            .description(
+                                                fmt.tf("team.name_set", &[&updated.name]),
+                                            )
                                             .color(crate::constants::EMBED_COLOR_ACCENT)
                                     })
                                 })
@@ -487,7 +1321,7 @@ pub async fn handle_command(
                     Err(e) => {
                         cmd.create_interaction_response(&ctx.http, |r| {
                             r.interaction_response_data(|d| {
-                                d.content(format!("❌ Failed to save: {}", e))
+                                d.content(fmt.tf("team.save_failed", &[&e]))
                                     .ephemeral(true)
                             })
                         })
@@ -500,226 +1334,825 @@ pub async fn handle_command(
                     r.kind(InteractionResponseType::ChannelMessageWithSource)
                         .interaction_response_data(|d| {
                             d.embed(|e| {
-                                e.title("👥 Team Name")
-                                    .description(format!("Current team name: **{}**", current.name))
+                                e.title(fmt.t("team.name_title"))
+                                    .description(fmt.tf("team.name_current", &[&current.name]))
                                     .color(crate::constants::EMBED_COLOR_ACCENT)
                             })
                         })
-                })
-                .await?;
-            }
+                })
+                .await?;
+            }
+        }
+        "team-add" => {
+            let get = |name: &str| {
+                cmd.data
+                    .options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.value.as_ref())
+                    .and_then(|v| v.as_str())
+            };
+            let joined = match get("joined") {
+                Some(raw) => match team::parse_date(raw) {
+                    Some(date) => Some(date),
+                    None => {
+                        cmd.create_interaction_response(&ctx.http, |r| {
+                            r.interaction_response_data(|d| {
+                                d.content(fmt.tf("team.invalid_joined", &[&raw]))
+                                    .ephemeral(true)
+                            })
+                        })
+                        .await?;
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+
+            let member = TeamMember {
+                ign: get("ign").unwrap_or("").trim().into(),
+                country: get("country").unwrap_or("").into(),
+                skills: team::parse_skills(get("skill").unwrap_or("")),
+                about: get("about").unwrap_or("").into(),
+                discord_tag: get("discord").unwrap_or("").into(),
+                rank: get("rank").map(Rank::from_str).unwrap_or_default(),
+                timezone: get("timezone").unwrap_or("").trim().into(),
+                joined,
+                base: get("base").unwrap_or("").into(),
+                ..TeamMember::default()
+            };
+
+            // Missing details are filled in on a form instead
+            if member.ign.is_empty() || member.country.trim().is_empty() || member.skills.is_empty()
+            {
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::Modal)
+                        .interaction_response_data(|d| add_modal(d, &member, &fmt))
+                })
+                .await?;
+                return Ok(());
+            }
+
+            // Checking the IGN can take longer than Discord's 3 second window
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await?;
+            match add_member(client, donut_key, member, cmd.user.id.0, &fmt).await {
+                Ok((embed, member)) => {
+                    cmd.edit_original_interaction_response(&ctx.http, |r| {
+                        r.add_embed(embed)
+                            .components(|c| rank_menu(c, &member.ign, &member.rank, &fmt))
+                    })
+                    .await?;
+                }
+                Err(message) => {
+                    cmd.delete_original_interaction_response(&ctx.http).await?;
+                    cmd.create_followup_message(&ctx.http, |m| m.content(message).ephemeral(true))
+                        .await?;
+                }
+            }
+        }
+        "team-edit" => {
+            let get = |name: &str| {
+                cmd.data
+                    .options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.value.as_ref())
+                    .and_then(|v| v.as_str())
+            };
+            let ign = get("ign").unwrap_or_default();
+            let member = match team::load().member(ign) {
+                Some(member) => member.clone(),
+                None => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| {
+                            d.content(fmt.tf("team.not_a_member", &[&ign.trim()]))
+                                .ephemeral(true)
+                        })
+                    })
+                    .await?;
+                    return Ok(());
+                }
+            };
+            // `-` clears a detail
+            let text = |name: &str| {
+                get(name).map(|v| match v.trim() {
+                    "-" => String::new(),
+                    v => v.to_string(),
+                })
+            };
+            let joined = match get("joined") {
+                Some(raw) => match team::parse_date(raw) {
+                    Some(date) => Some(date),
+                    None => {
+                        cmd.create_interaction_response(&ctx.http, |r| {
+                            r.interaction_response_data(|d| {
+                                d.content(fmt.tf("team.invalid_joined", &[&raw]))
+                                    .ephemeral(true)
+                            })
+                        })
+                        .await?;
+                        return Ok(());
+                    }
+                },
+                None => None,
+            };
+            let country = match text("country") {
+                Some(country) if !country.is_empty() => match countries::resolve(&country, &fmt) {
+                    Ok(code) => Some(code),
+                    Err(message) => {
+                        cmd.create_interaction_response(&ctx.http, |r| {
+                            r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                        })
+                        .await?;
+                        return Ok(());
+                    }
+                },
+                other => other,
+            };
+            let update = MemberUpdate {
+                country,
+                skills: text("skill").map(|s| team::parse_skills(&s)),
+                rank: get("rank").map(Rank::from_str),
+                about: text("about"),
+                discord_tag: text("discord"),
+                timezone: text("timezone"),
+                joined,
+                base: text("base"),
+            };
+
+            // Nothing to change given: edit everything in a form instead
+            if update.is_empty() {
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::Modal)
+                        .interaction_response_data(|d| edit_modal(d, &member, &fmt))
+                })
+                .await?;
+                return Ok(());
+            }
+
+            match edit_outcome(
+                team::update_member(&member.ign, update, cmd.user.id.0),
+                ign,
+                &fmt,
+            ) {
+                Ok(embed) => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| d.add_embed(embed))
+                    })
+                    .await?;
+                }
+                Err(message) => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+                }
+            }
+        }
+        "team-remove" => {
+            let ign = cmd
+                .data
+                .options
+                .get(0)
+                .and_then(|o| o.value.as_ref())
+                .and_then(|v| v.as_str())
+                .unwrap();
+            match team::remove_member(ign, cmd.user.id.0) {
+                Ok((t, removed)) => {
+                    let msg = if removed {
+                        fmt.tf("team.removed", &[&ign, &t.name])
+                    } else {
+                        fmt.tf("team.not_found", &[&ign, &t.name])
+                    };
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| {
+                                d.embed(|e| {
+                                    e.title(fmt.t("team.removal_title"))
+                                        .description(msg)
+                                        .color(crate::constants::EMBED_COLOR_ACCENT)
+                                })
+                            })
+                    })
+                    .await?;
+                }
+                Err(e) => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| {
+                            d.content(fmt.tf("team.remove_failed", &[&e]))
+                                .ephemeral(true)
+                        })
+                    })
+                    .await?;
+                }
+            }
+        }
+        // `/online` is `/team-list` with the online layout
+        "team-list" | "online" => {
+            let team = match squad_filter(cmd, team::load(), &fmt) {
+                Ok(team) => team,
+                Err(message) => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+                    return Ok(());
+                }
+            };
+            let mut config = status::config();
+            let layout = if name == "online" {
+                Some(Layout::OnlineOnly)
+            } else {
+                cmd.data
+                    .options
+                    .iter()
+                    .find(|o| o.name == "layout")
+                    .and_then(|o| o.value.as_ref())
+                    .and_then(|v| v.as_str())
+                    .and_then(Layout::parse)
+            };
+            if let Some(layout) = layout {
+                config.layout = layout;
+            }
+
+            // Only the online layout needs lookups, which can take longer than
+            // Discord's 3 second window
+            let presence = if config.layout == Layout::OnlineOnly {
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                })
+                .await?;
+                Some(status::fetch_presences(client, donut_key, &team).await)
+            } else {
+                None
+            };
+            let deferred = presence.is_some();
+            let page = TeamStatus {
+                team,
+                presence: presence.unwrap_or_default(),
+                config,
+                at: None,
+            }
+            .render(&fmt);
+
+            // Large teams don't fit one embed; the rest follow as extra messages
+            let mut embeds = page.to_embeds().into_iter();
+            let first = embeds.next().unwrap_or_default();
+            if deferred {
+                cmd.edit_original_interaction_response(&ctx.http, |r| r.add_embed(first))
+                    .await?;
+            } else {
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| d.add_embed(first))
+                })
+                .await?;
+            }
+            for embed in embeds {
+                cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
+                    .await?;
+            }
+        }
+        "team-countries" => {
+            let page = team::CountryBreakdown::of(&team::load()).render(&fmt);
+            let mut embeds = page.to_embeds().into_iter();
+            let first = embeds.next().unwrap_or_default();
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| d.add_embed(first))
+            })
+            .await?;
+            for embed in embeds {
+                cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
+                    .await?;
+            }
+        }
+        "team-export" => {
+            let format = cmd
+                .data
+                .options
+                .first()
+                .and_then(|o| o.value.as_ref())
+                .and_then(|v| v.as_str())
+                .and_then(RosterFormat::parse)
+                .unwrap_or(RosterFormat::Json);
+            let current = team::load();
+            let file = AttachmentType::Bytes {
+                data: roster::export(&current, format).into_bytes().into(),
+                filename: format!("team.{}", format.extension()),
+            };
+            let content = fmt.tf(
+                "export.done",
+                &[
+                    &current.name,
+                    &fmt.number(current.members.len() as i64),
+                    &format.extension().to_uppercase(),
+                ],
+            );
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| d.content(content).add_file(file))
+            })
+            .await?;
+        }
+        "team-audit" => {
+            let option = |name: &str| {
+                cmd.data
+                    .options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.value.as_ref())
+            };
+            let ign = option("ign")
+                .and_then(|v| v.as_str())
+                .map(str::trim)
+                .filter(|ign| !ign.is_empty())
+                .map(str::to_string);
+            let days = option("days")
+                .and_then(|v| v.as_i64())
+                .unwrap_or(audit::DEFAULT_DAYS);
+            let view = AuditView {
+                entries: audit::history(ign.as_deref(), days),
+                ign,
+                days,
+            };
+            let mut embeds = view.render(&fmt).to_embeds().into_iter();
+            let first = embeds.next().unwrap_or_default();
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| d.add_embed(first))
+            })
+            .await?;
+            for embed in embeds {
+                cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
+                    .await?;
+            }
+        }
+        "team-undo" | "team-restore" if !can_manage(cmd.member.as_ref()) => {
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.interaction_response_data(|d| {
+                    d.content(fmt.t("history.needs_manage")).ephemeral(true)
+                })
+            })
+            .await?;
+        }
+        "team-undo" => match team::undo(cmd.user.id.0)? {
+            Some((snapshot, changes)) => {
+                let mut embeds = Undone { snapshot, changes }
+                    .render(&fmt)
+                    .to_embeds()
+                    .into_iter();
+                let first = embeds.next().unwrap_or_default();
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.kind(InteractionResponseType::ChannelMessageWithSource)
+                        .interaction_response_data(|d| d.add_embed(first))
+                })
+                .await?;
+                for embed in embeds {
+                    cmd.create_followup_message(&ctx.http, |m| m.add_embed(embed))
+                        .await?;
+                }
+            }
+            None => {
+                cmd.create_interaction_response(&ctx.http, |r| {
+                    r.interaction_response_data(|d| {
+                        d.content(fmt.t("undo.nothing")).ephemeral(true)
+                    })
+                })
+                .await?;
+            }
+        },
+        "team-restore" => {
+            let version = cmd
+                .data
+                .options
+                .first()
+                .and_then(|o| o.value.as_ref())
+                .and_then(|v| v.as_u64())
+                .unwrap_or_default();
+            let preview = history::get(version).map(|snapshot| RestorePreview {
+                changes: Change::between(&team::load(), &snapshot.team),
+                snapshot,
+            });
+            match preview {
+                Some(preview) if !preview.changes.is_empty() => {
+                    // The whole diff is shown, with the buttons under its last part
+                    let embeds = preview.render(&fmt).to_embeds();
+                    let current = history::latest_version();
+                    let last = embeds.len() - 1;
+                    for (i, embed) in embeds.into_iter().enumerate() {
+                        let buttons = i == last;
+                        if i == 0 {
+                            cmd.create_interaction_response(&ctx.http, |r| {
+                                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                                    .interaction_response_data(|d| {
+                                        d.add_embed(embed);
+                                        if buttons {
+                                            d.components(|c| {
+                                                restore_buttons(
+                                                    c,
+                                                    version,
+                                                    current,
+                                                    cmd.user.id.0,
+                                                    &fmt,
+                                                )
+                                            });
+                                        }
+                                        d
+                                    })
+                            })
+                            .await?;
+                        } else {
+                            cmd.create_followup_message(&ctx.http, |m| {
+                                m.add_embed(embed);
+                                if buttons {
+                                    m.components(|c| {
+                                        restore_buttons(c, version, current, cmd.user.id.0, &fmt)
+                                    });
+                                }
+                                m
+                            })
+                            .await?;
+                        }
+                    }
+                }
+                preview => {
+                    let message = match preview {
+                        Some(_) => fmt.tf("restore.no_changes", &[&version]),
+                        None => fmt.tf("restore.unknown_version", &[&version]),
+                    };
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+                }
+            }
+        }
+        "team-import" => {
+            let attachment = cmd.data.options.first().and_then(|o| match &o.resolved {
+                Some(CommandDataOptionValue::Attachment(a)) => Some(a.clone()),
+                _ => None,
+            });
+            let format = attachment
+                .as_ref()
+                .and_then(|a| RosterFormat::from_filename(&a.filename));
+            let refusal = match (&attachment, format) {
+                _ if !can_manage(cmd.member.as_ref()) => {
+                    Some(fmt.t("import.needs_manage").to_string())
+                }
+                (None, _) => Some(fmt.tf("import.unknown_format", &[&"?"])),
+                (Some(a), None) => Some(fmt.tf("import.unknown_format", &[&a.filename])),
+                (Some(a), _) if a.size > roster::MAX_IMPORT_BYTES => {
+                    Some(fmt.tf("import.too_large", &[&a.filename]))
+                }
+                _ => None,
+            };
+            let (attachment, format) = match (refusal, attachment, format) {
+                (None, Some(attachment), Some(format)) => (attachment, format),
+                (refusal, _, _) => {
+                    let message = refusal.unwrap_or_default();
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await?;
+            let text = match download(client, &attachment.url).await {
+                Ok(text) => text,
+                Err(e) => {
+                    let message = fmt.tf("import.download_failed", &[&e]);
+                    cmd.delete_original_interaction_response(&ctx.http).await?;
+                    cmd.create_followup_message(&ctx.http, |m| m.content(message).ephemeral(true))
+                        .await?;
+                    return Ok(());
+                }
+            };
+
+            let current = team::load();
+            let staged = roster::read(&text, format, &current, &fmt).and_then(|members| {
+                let diff = RosterDiff::between(&current.members, &members);
+                if diff.is_empty() {
+                    return Ok((diff, None));
+                }
+                let id = cmd.id.0.to_string();
+                let pending = PendingImport {
+                    user_id: cmd.user.id.0,
+                    created: Utc::now(),
+                    base: current.members.clone(),
+                    members,
+                };
+                roster::stage(&id, pending)
+                    .map(|_| (diff, Some(id)))
+                    .map_err(|e| vec![fmt.tf("import.save_failed", &[&e])])
+            });
+            match staged {
+                Ok((diff, id)) => {
+                    // The whole diff is shown, with the buttons under its last part
+                    let embeds = diff.render(&fmt).to_embeds();
+                    let last = embeds.len() - 1;
+                    for (i, embed) in embeds.into_iter().enumerate() {
+                        let buttons = id.as_ref().filter(|_| i == last);
+                        if i == 0 {
+                            cmd.edit_original_interaction_response(&ctx.http, |r| {
+                                r.add_embed(embed);
+                                if let Some(id) = buttons {
+                                    r.components(|c| import_buttons(c, id, &fmt));
+                                }
+                                r
+                            })
+                            .await?;
+                        } else {
+                            cmd.create_followup_message(&ctx.http, |m| {
+                                m.add_embed(embed);
+                                if let Some(id) = buttons {
+                                    m.components(|c| import_buttons(c, id, &fmt));
+                                }
+                                m
+                            })
+                            .await?;
+                        }
+                    }
+                }
+                Err(errors) => {
+                    let message = import_errors(&attachment.filename, &errors, &fmt);
+                    cmd.delete_original_interaction_response(&ctx.http).await?;
+                    cmd.create_followup_message(&ctx.http, |m| m.content(message).ephemeral(true))
+                        .await?;
+                }
+            }
+        }
+        "team-profile" => {
+            let ign = cmd
+                .data
+                .options
+                .first()
+                .and_then(|o| o.value.as_ref())
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            let current = team::load();
+            let member = match current.member(ign) {
+                Some(member) => member.clone(),
+                None => {
+                    cmd.create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| {
+                            d.content(fmt.tf("team.not_a_member", &[&ign.trim()]))
+                                .ephemeral(true)
+                        })
+                    })
+                    .await?;
+                    return Ok(());
+                }
+            };
+
+            // Two API calls can take longer than Discord's 3 second window
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await?;
+            let (presence, stats) = tokio::join!(
+                fetch_presence(client, donut_key, &member.ign),
+                fetch_stats(client, donut_key, &member.ign)
+            );
+            let profile = team::Profile {
+                lead: current.is_lead(&member),
+                discord_id: players::discord_id_for(&member.ign),
+                member,
+                fields: current.custom_fields,
+                presence,
+                stats,
+                at: Utc::now(),
+            };
+            let page = profile.render(&fmt);
+            cmd.edit_original_interaction_response(&ctx.http, |r| {
+                r.embed(|e| {
+                    page.apply(e);
+                    e
+                })
+                .components(|c| profile_buttons(c, &profile.member.ign, &fmt))
+            })
+            .await?;
+        }
+        "team-field" => {
+            let sub = match cmd.data.options.first() {
+                Some(sub) => sub,
+                None => return Err("Missing subcommand".into()),
+            };
+            let get = |name: &str| {
+                sub.options
+                    .iter()
+                    .find(|o| o.name == name)
+                    .and_then(|o| o.value.as_ref())
+                    .and_then(|v| v.as_str())
+            };
+            let name = get("name").unwrap_or_default();
+
+            let description = match sub.name.as_str() {
+                "define" | "remove" if !can_manage(cmd.member.as_ref()) => {
+                    fmt.t("field.needs_manage").to_string()
+                }
+                "define" => {
+                    let kind = get("kind")
+                        .and_then(FieldKind::parse)
+                        .unwrap_or(FieldKind::Text);
+                    let label = get("label")
+                        .map(str::trim)
+                        .filter(|l| !l.is_empty())
+                        .unwrap_or(name.trim())
+                        .to_string();
+                    let field = CustomField {
+                        key: team::field_key(name),
+                        label: label.clone(),
+                        kind,
+                    };
+                    match team::define_field(field, cmd.user.id.0) {
+                        Ok((_, existed)) => fmt.tf(
+                            if existed {
+                                "field.redefined"
+                            } else {
+                                "field.defined"
+                            },
+                            &[&label, &fmt.t(kind.label_key())],
+                        ),
+                        Err(e) => fmt.tf("field.save_failed", &[&e]),
+                    }
+                }
+                "remove" => match team::remove_field(name, cmd.user.id.0) {
+                    Ok((_, true)) => fmt.tf("field.removed", &[&name.trim()]),
+                    Ok((_, false)) => fmt.tf("field.unknown", &[&name.trim()]),
+                    Err(e) => fmt.tf("field.save_failed", &[&e]),
+                },
+                "set" => {
+                    let ign = get("ign").unwrap_or_default().trim();
+                    let value = get("value").map(str::trim).filter(|v| !v.is_empty());
+                    match team::load().field(name) {
+                        None => fmt.tf("field.unknown", &[&name.trim()]),
+                        Some(field) => match value.map(|v| field.kind.normalize(v)).transpose() {
+                            Err(problem) => fmt.tf(problem, &[&value.unwrap_or_default()]),
+                            Ok(normalized) => {
+                                let shown = normalized.clone();
+                                match team::set_custom_value(
+                                    ign,
+                                    &field.key,
+                                    normalized,
+                                    cmd.user.id.0,
+                                ) {
+                                    Ok((_, false)) => fmt.tf("team.not_a_member", &[&ign]),
+                                    Ok((_, true)) => match shown {
+                                        Some(value) => {
+                                            fmt.tf("field.set", &[&field.label, &ign, &value])
+                                        }
+                                        None => fmt.tf("field.cleared", &[&field.label, &ign]),
+                                    },
+                                    Err(e) => fmt.tf("field.save_failed", &[&e]),
+                                }
+                            }
+                        },
+                    }
+                }
+                _ => {
+                    let fields = team::load().custom_fields;
+                    if fields.is_empty() {
+                        fmt.t("field.none").to_string()
+                    } else {
+                        fields
+                            .iter()
+                            .map(|f| {
+                                fmt.tf(
+                                    "field.list_entry",
+                                    &[&f.key, &f.label, &fmt.t(f.kind.label_key())],
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("\n")
+                    }
+                }
+            };
+
+            cmd.create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::ChannelMessageWithSource)
+                    .interaction_response_data(|d| {
+                        d.embed(|e| {
+                            e.title(fmt.t("field.title"))
+                                .description(description)
+                                .color(crate::constants::EMBED_COLOR_ACCENT)
+                        })
+                        .ephemeral(true)
+                    })
+            })
+            .await?;
         }
-        "team-add" => {
+        "team-squad" => {
+            let sub = match cmd.data.options.first() {
+                Some(sub) => sub,
+                None => return Err("Missing subcommand".into()),
+            };
             let get = |name: &str| {
-                cmd.data
-                    .options
+                sub.options
                     .iter()
                     .find(|o| o.name == name)
                     .and_then(|o| o.value.as_ref())
-                    .and_then(|v| v.as_str())
             };
-            let ign = get("ign").unwrap();
-            let country = get("country").unwrap();
-            let skill = get("skill").unwrap();
-            let about = get("about").unwrap_or("");
-            let rank = get("rank").map(Rank::from_str).unwrap_or_default();
-            let discord_tag = get("discord").unwrap_or("");
+            let text = |name: &str| get(name).and_then(|v| v.as_str()).map(str::trim);
+            let squad = text("squad").filter(|s| !s.is_empty());
+            let ign = text("ign").unwrap_or_default();
+            let by = cmd.user.id.0;
+            let team = team::load();
+            let known = squad.map(|name| team.squad(name).map(|s| s.name.clone()));
 
-            let member = TeamMember {
-                ign: ign.into(),
-                country: country.into(),
-                skill: skill.into(),
-                about: about.into(),
-                discord_tag: discord_tag.into(),
-                rank,
-            };
-            match team::upsert_member(member) {
-                Ok((t, updated)) => {
-                    let action = if updated { "updated" } else { "added" };
-                    cmd.create_interaction_response(&ctx.http, |r| {
-                        r.kind(InteractionResponseType::ChannelMessageWithSource)
-                            .interaction_response_data(|d| {
-                                d.embed(|e| {
-                                    e.title("✅ Team Member Saved")
-                                        .description({
-                                            let flag = country_flag(country);
-                                            let flag_space = if flag.is_empty() {
-                                                "".to_string()
-                                            } else {
-                                                format!("{} ", flag)
-                                            };
-                                            let rank_text =
-                                                Rank::from_str(get("rank").unwrap_or("member"))
-                                                    .as_str();
-                                            format!(
-                                                "{}{} has been {} to **{}** as {}.",
-                                                flag_space, ign, action, t.name, rank_text
-                                            )
-                                        })
-                                        .color(crate::constants::EMBED_COLOR_ACCENT)
-                                })
-                            })
-                    })
-                    .await?;
-                }
-                Err(e) => {
-                    cmd.create_interaction_response(&ctx.http, |r| {
-                        r.interaction_response_data(|d| {
-                            d.content(format!("❌ Failed to save member: {}", e))
-                                .ephemeral(true)
-                        })
-                    })
-                    .await?;
+            let description = match (sub.name.as_str(), known) {
+                ("create" | "remove" | "assign" | "lead", _)
+                    if !can_manage(cmd.member.as_ref()) =>
+                {
+                    fmt.t("squad.needs_manage").to_string()
                 }
-            }
-        }
-        "team-remove" => {
-            let ign = cmd
-                .data
-                .options
-                .get(0)
-                .and_then(|o| o.value.as_ref())
-                .and_then(|v| v.as_str())
-                .unwrap();
-            match team::remove_member(ign) {
-                Ok((t, removed)) => {
-                    let msg = if removed {
-                        format!("Removed {} from {}", ign, t.name)
+                (_, Some(None)) => fmt.tf("squad.unknown", &[&squad.unwrap_or_default()]),
+                ("create", _) => {
+                    let name = text("name").unwrap_or_default();
+                    if name.is_empty() || name.chars().count() > team::MAX_SQUAD_NAME {
+                        fmt.tf("squad.invalid_name", &[&team::MAX_SQUAD_NAME])
                     } else {
-                        format!("{} not found in {}", ign, t.name)
-                    };
-                    cmd.create_interaction_response(&ctx.http, |r| {
-                        r.kind(InteractionResponseType::ChannelMessageWithSource)
-                            .interaction_response_data(|d| {
-                                d.embed(|e| {
-                                    e.title("🗑️ Team Member Removal")
-                                        .description(msg)
-                                        .color(crate::constants::EMBED_COLOR_ACCENT)
-                                })
-                            })
-                    })
-                    .await?;
+                        match team::create_squad(name, by) {
+                            Ok((_, true)) => fmt.tf("squad.exists", &[&name]),
+                            Ok((_, false)) => fmt.tf("squad.created", &[&name]),
+                            Err(e) => fmt.tf("team.save_failed", &[&e]),
+                        }
+                    }
                 }
-                Err(e) => {
-                    cmd.create_interaction_response(&ctx.http, |r| {
-                        r.interaction_response_data(|d| {
-                            d.content(format!("❌ Failed to remove member: {}", e))
-                                .ephemeral(true)
-                        })
-                    })
-                    .await?;
+                ("remove", Some(Some(squad))) => match team::remove_squad(&squad, by) {
+                    Ok(_) => fmt.tf("squad.removed", &[&squad]),
+                    Err(e) => fmt.tf("team.save_failed", &[&e]),
+                },
+                ("assign", squad) => {
+                    let squad = squad.flatten();
+                    match team::assign_squad(ign, squad.as_deref(), by) {
+                        Ok((_, false)) => fmt.tf("team.not_a_member", &[&ign]),
+                        Ok((_, true)) => match squad {
+                            Some(squad) => fmt.tf("squad.assigned", &[&ign, &squad]),
+                            None => fmt.tf("squad.unassigned", &[&ign]),
+                        },
+                        Err(e) => fmt.tf("team.save_failed", &[&e]),
+                    }
                 }
-            }
-        }
-        "team-list" => {
-            let mut team = team::load();
-            // Sort by rank (Owner > Admin > Member), then by IGN alphabetically
-            team.members
-                .sort_by(|a, b| match a.rank.sort_key().cmp(&b.rank.sort_key()) {
-                    std::cmp::Ordering::Equal => {
-                        a.ign.to_ascii_lowercase().cmp(&b.ign.to_ascii_lowercase())
+                ("lead", Some(Some(squad))) => {
+                    let remove = get("remove").and_then(|v| v.as_bool()).unwrap_or(false);
+                    match team::set_squad_lead(&squad, ign, !remove, by) {
+                        Ok((_, false)) => fmt.tf("team.not_a_member", &[&ign]),
+                        Ok((_, true)) if remove => fmt.tf("squad.lead_removed", &[&ign, &squad]),
+                        Ok((_, true)) => fmt.tf("squad.lead_added", &[&ign, &squad]),
+                        Err(e) => fmt.tf("team.save_failed", &[&e]),
                     }
-                    other => other,
-                });
+                }
+                _ if team.squads.is_empty() => fmt.t("squad.none").to_string(),
+                _ => team
+                    .squads
+                    .iter()
+                    .map(|squad| {
+                        let members = team.squad_members(&squad.name);
+                        let leads: Vec<&str> = members
+                            .iter()
+                            .filter(|m| squad.is_lead(&m.ign))
+                            .map(|m| m.ign.as_str())
+                            .collect();
+                        let leads = if leads.is_empty() {
+                            "—".to_string()
+                        } else {
+                            leads.join(", ")
+                        };
+                        fmt.tf(
+                            "squad.list_entry",
+                            &[&squad.name, &fmt.number(members.len() as i64), &leads],
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            };
+
             cmd.create_interaction_response(&ctx.http, |r| {
                 r.kind(InteractionResponseType::ChannelMessageWithSource)
                     .interaction_response_data(|d| {
                         d.embed(|e| {
-                            let mut e = e
-                                .title(format!("👥 {}", team.name))
-                                .color(crate::constants::EMBED_COLOR_ACCENT);
-                            if team.members.is_empty() {
-                                e = e.description("No members yet. Use /team-add to add someone.");
-                            } else {
-                                let mut owners: Vec<(String, String)> = Vec::new();
-                                let mut admins: Vec<(String, String)> = Vec::new();
-                                let mut members: Vec<(String, String)> = Vec::new();
-
-                                for m in &team.members {
-                                    let flag = country_flag(&m.country);
-                                    let country_display = if flag.is_empty() {
-                                        m.country.clone()
-                                    } else {
-                                        format!("{} ({})", m.country, flag)
-                                    };
-                                    let discord = if m.discord_tag.is_empty() {
-                                        "-".to_string()
-                                    } else {
-                                        m.discord_tag.clone()
-                                    };
-                                    let mut value = format!(
-                                        "Country: {}\nSkill: {}\nDiscord: {}",
-                                        country_display, m.skill, discord
-                                    );
-                                    if !m.about.is_empty() {
-                                        value.push_str(&format!("\nAbout: {}", m.about));
-                                    }
-                                    let entry = (m.ign.clone(), value);
-                                    match m.rank {
-                                        Rank::Owner => owners.push(entry),
-                                        Rank::Admin => admins.push(entry),
-                                        Rank::Member => members.push(entry),
-                                    }
-                                }
-
-                                if !owners.is_empty() {
-                                    let count = owners.len();
-                                    e = e.field(
-                                        format!("👑 Owner ({})", count),
-                                        crate::constants::ZWSP,
-                                        false,
-                                    );
-                                    for (name, val) in owners.iter() {
-                                        e = e.field(name.clone(), val.clone(), false);
-                                    }
-                                }
-                                if !owners.is_empty() && (!admins.is_empty() || !members.is_empty())
-                                {
-                                    e = e.field(
-                                        crate::constants::ZWSP,
-                                        crate::constants::ZWSP,
-                                        false,
-                                    );
-                                }
-                                if !admins.is_empty() {
-                                    let count = admins.len();
-                                    e = e.field(
-                                        format!("🛡️ Admin ({})", count),
-                                        crate::constants::ZWSP,
-                                        false,
-                                    );
-                                    for (name, val) in admins.iter() {
-                                        e = e.field(name.clone(), val.clone(), false);
-                                    }
-                                }
-                                if !admins.is_empty() && !members.is_empty() {
-                                    e = e.field(
-                                        crate::constants::ZWSP,
-                                        crate::constants::ZWSP,
-                                        false,
-                                    );
-                                }
-                                if !members.is_empty() {
-                                    let count = members.len();
-                                    e = e.field(
-                                        format!("👤 Member ({})", count),
-                                        crate::constants::ZWSP,
-                                        false,
-                                    );
-                                    for (name, val) in members.iter() {
-                                        e = e.field(name.clone(), val.clone(), false);
-                                    }
-                                }
-                            }
-                            e
+                            e.title(fmt.t("squad.title"))
+                                .description(description)
+                                .color(crate::constants::EMBED_COLOR_ACCENT)
                         })
+                        .ephemeral(true)
                     })
             })
             .await?;
         }
         _ => {
             cmd.create_interaction_response(&ctx.http, |r| {
-                r.interaction_response_data(|d| d.content("❌ Unknown command").ephemeral(true))
+                r.interaction_response_data(|d| d.content(fmt.t("common.unknown_command")).ephemeral(true))
             })
             .await?;
         }
@@ -734,6 +2167,7 @@ pub async fn handle_component(
     component: &MessageComponentInteraction,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let custom_id = &component.data.custom_id;
+    let fmt = settings::format_for(component.user.id.0, &component.locale);
 
     if custom_id.starts_with("auction_") {
         let parts: Vec<&str> = custom_id.split('_').collect();
@@ -765,24 +2199,17 @@ pub async fn handle_component(
             None
         };
 
-        let mut title_parts = vec![format!("🏪 Auction House (Page {})", new_page)];
-        if let Some(search_term) = &search_param {
-            title_parts.push(format!("🔍 '{}'", search_term));
-        }
-        if let Some(sort_type) = &sort_param {
-            let sort_emoji = match sort_type.as_str() {
-                "lowest_price" => "💰",
-                "highest_price" => "💸",
-                "recently_listed" => "🕒",
-                "last_listed" => "📅",
-                _ => "📊",
-            };
-            title_parts.push(format!("{} {}", sort_emoji, sort_type.replace("_", " ")));
-        }
-        let title = title_parts.join(" | ");
+        let title = auction_title(
+            "auction.title",
+            new_page,
+            search_param.as_deref(),
+            sort_param.as_deref(),
+            &fmt,
+        );
 
         let path = format!("/v1/auction/list/{}", new_page);
         let (embed, _) = auction_embed(
+            ctx,
             http_client,
             donut_api_key,
             &path,
@@ -790,6 +2217,7 @@ pub async fn handle_component(
             search_param.as_deref(),
             sort_param.as_deref(),
             new_page,
+            &fmt,
         )
         .await?;
 
@@ -803,6 +2231,7 @@ pub async fn handle_component(
                                 new_page,
                                 search_param.as_deref(),
                                 sort_param.as_deref(),
+                                &fmt,
                             )
                         })
                     })
@@ -839,24 +2268,17 @@ pub async fn handle_component(
             None
         };
 
-        let mut title_parts = vec![format!("📜 Auction Transactions (Page {})", new_page)];
-        if let Some(search_term) = &search_param {
-            title_parts.push(format!("🔍 '{}'", search_term));
-        }
-        if let Some(sort_type) = &sort_param {
-            let sort_emoji = match sort_type.as_str() {
-                "lowest_price" => "💰",
-                "highest_price" => "💸",
-                "recently_listed" => "🕒",
-                "last_listed" => "📅",
-                _ => "📊",
-            };
-            title_parts.push(format!("{} {}", sort_emoji, sort_type.replace("_", " ")));
-        }
-        let title = title_parts.join(" | ");
+        let title = auction_title(
+            "auction.transactions_title",
+            new_page,
+            search_param.as_deref(),
+            sort_param.as_deref(),
+            &fmt,
+        );
 
         let path = format!("/v1/auction/transactions/{}", new_page);
         let (embed, _) = auction_embed(
+            ctx,
             http_client,
             donut_api_key,
             &path,
@@ -864,6 +2286,7 @@ pub async fn handle_component(
             search_param.as_deref(),
             sort_param.as_deref(),
             new_page,
+            &fmt,
         )
         .await?;
 
@@ -872,7 +2295,13 @@ pub async fn handle_component(
                 r.kind(InteractionResponseType::UpdateMessage)
                     .interaction_response_data(|d| {
                         d.add_embed(embed).components(|c| {
-                            txn_buttons(c, new_page, search_param.as_deref(), sort_param.as_deref())
+                            txn_buttons(
+                                c,
+                                new_page,
+                                search_param.as_deref(),
+                                sort_param.as_deref(),
+                                &fmt,
+                            )
                         })
                     })
             })
@@ -911,21 +2340,562 @@ pub async fn handle_component(
 
         let response_text = res.text().await?;
         let json: serde_json::Value = serde_json::from_str(&response_text)?;
+        if let Err(e) = players::record_leaderboard(&json, lb_type) {
+            tracing::warn!("Failed to record leaderboard players: {:?}", e);
+        }
 
         component
             .create_interaction_response(&ctx.http, |r| {
                 r.kind(InteractionResponseType::UpdateMessage)
                     .interaction_response_data(|d| {
                         d.embed(|embed| {
-                            use crate::formatters::format_leaderboard_response;
-                            format_leaderboard_response(&json, embed, lb_type, new_page);
+                            crate::api::render_leaderboard(&json, lb_type, new_page, &fmt)
+                                .apply(embed);
                             embed
                         })
-                        .components(|c| lb_buttons(c, new_page, lb_type))
+                        .components(|c| lb_buttons(c, new_page, lb_type, &fmt))
+                    })
+            })
+            .await?;
+    } else if let Some(ign) = custom_id.strip_prefix("team_rank_") {
+        let update = MemberUpdate {
+            rank: component.data.values.first().map(|v| Rank::from_str(v)),
+            ..MemberUpdate::default()
+        };
+        match team::update_member(ign, update, component.user.id.0) {
+            Ok((t, Some(member))) => {
+                let embed = saved_embed(&member, &t.name, true, &fmt);
+                component
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::UpdateMessage)
+                            .interaction_response_data(|d| {
+                                d.add_embed(embed).components(|c| {
+                                    rank_menu(c, &member.ign, &member.rank, &fmt)
+                                })
+                            })
+                    })
+                    .await?;
+            }
+            result => {
+                let message = match result {
+                    Err(e) => fmt.tf("team.member_save_failed", &[&e]),
+                    _ => fmt.tf("team.not_a_member", &[&ign]),
+                };
+                component
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                    })
+                    .await?;
+            }
+        }
+    } else if let Some(rest) = custom_id.strip_prefix("team_restore_") {
+        let parts: Vec<&str> = rest.split('_').collect();
+        if parts.len() < 4 {
+            return Err("Invalid component ID".into());
+        }
+        let (action, version, current, user) = (
+            parts[0],
+            parts[1].parse::<u64>()?,
+            parts[2].parse::<u64>()?,
+            parts[3].parse::<u64>()?,
+        );
+        if user != component.user.id.0 {
+            component
+                .create_interaction_response(&ctx.http, |r| {
+                    r.interaction_response_data(|d| {
+                        d.content(fmt.t("restore.not_yours")).ephemeral(true)
+                    })
+                })
+                .await?;
+            return Ok(());
+        }
+        let content = if action == "cancel" {
+            fmt.t("restore.cancelled").to_string()
+        } else if history::latest_version() != current {
+            fmt.t("restore.stale").to_string()
+        } else {
+            match team::restore(version, user)? {
+                Some(_) => fmt.tf("restore.done", &[&version]),
+                None => fmt.tf("restore.unknown_version", &[&version]),
+            }
+        };
+        component
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| d.content(content).components(|c| c))
+            })
+            .await?;
+    } else if let Some(rest) = custom_id.strip_prefix("team_import_") {
+        let (action, id) = rest.split_once('_').ok_or("Invalid component ID")?;
+        // Peek first so someone else pressing a button doesn't use up the import
+        let owner = roster::owner(id);
+        if owner.is_some_and(|user| user != component.user.id.0) {
+            component
+                .create_interaction_response(&ctx.http, |r| {
+                    r.interaction_response_data(|d| {
+                        d.content(fmt.t("import.not_yours")).ephemeral(true)
+                    })
+                })
+                .await?;
+            return Ok(());
+        }
+        let content = match (action, roster::take(id)?) {
+            (_, None) => fmt.t("import.expired").to_string(),
+            ("cancel", Some(_)) => fmt.t("import.cancelled").to_string(),
+            (_, Some(pending)) if team::load().members != pending.base => {
+                fmt.t("import.stale").to_string()
+            }
+            (_, Some(pending)) => {
+                let diff = RosterDiff::between(&pending.base, &pending.members);
+                match team::replace_members(pending.members, component.user.id.0) {
+                    Ok(_) => fmt.tf(
+                        "import.applied",
+                        &[
+                            &fmt.number(diff.added.len() as i64),
+                            &fmt.number(diff.updated.len() as i64),
+                            &fmt.number(diff.removed.len() as i64),
+                        ],
+                    ),
+                    Err(e) => fmt.tf("team.save_failed", &[&e]),
+                }
+            }
+        };
+        component
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::UpdateMessage)
+                    .interaction_response_data(|d| d.content(content).components(|c| c))
+            })
+            .await?;
+    } else if let Some(rest) = custom_id.strip_prefix("application_") {
+        let (action, id) = rest.split_once('_').ok_or("Invalid component ID")?;
+        let id = id.parse::<u64>()?;
+        let application = match applications::get(id) {
+            Some(application) => application,
+            None => return Err("Unknown application".into()),
+        };
+        let rejection = match action {
+            "refresh" => None,
+            _ if !can_manage(component.member.as_ref()) => {
+                Some(fmt.t("application.needs_manage").to_string())
+            }
+            _ if application.state != ApplicationState::Pending => Some(fmt.tf(
+                "application.already_decided",
+                &[&fmt.t(application.state.label_key())],
+            )),
+            _ => None,
+        };
+        if let Some(message) = rejection {
+            component
+                .create_interaction_response(&ctx.http, |r| {
+                    r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                })
+                .await?;
+            return Ok(());
+        }
+
+        // Fetching fresh stats can take longer than Discord's 3 second window
+        component
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredUpdateMessage)
+            })
+            .await?;
+        let reviewer = component.user.id.0;
+        let state = match action {
+            "accept" => Some(ApplicationState::Accepted),
+            "reject" => Some(ApplicationState::Rejected),
+            _ => None,
+        };
+        // Deciding first means only one click, reviewer or expiry wins
+        let (application, decided) = match state {
+            Some(state) => applications::decide(id, state, reviewer)?,
+            None => Some((application, false)),
+        }
+        .ok_or("Unknown application")?;
+        if state.is_some() && !decided {
+            let message = fmt.tf(
+                "application.already_decided",
+                &[&fmt.t(application.state.label_key())],
+            );
+            component
+                .create_followup_message(&ctx.http, |m| m.content(message).ephemeral(true))
+                .await?;
+            return Ok(());
+        }
+        if action == "accept" {
+            // Re-adding someone keeps the rank and details the form doesn't ask for
+            let mut member = team::load()
+                .member(&application.ign)
+                .cloned()
+                .unwrap_or_default();
+            member.ign = application.ign.clone();
+            member.country = application.country.clone();
+            member.skills = application.skills.clone();
+            member.about = application.about.clone();
+            member.discord_tag = application.user_tag.clone();
+            member.joined = member.joined.or(Some(Utc::now().date_naive()));
+            if let Err(e) = team::upsert_member(member, reviewer) {
+                applications::reopen(id)?;
+                component
+                    .create_followup_message(&ctx.http, |m| {
+                        m.content(fmt.tf("team.member_save_failed", &[&e]))
+                            .ephemeral(true)
+                    })
+                    .await?;
+                return Ok(());
+            }
+        }
+
+        let expiry_days = applications::config().map_or(7, |c| c.expiry_days);
+        let stats = fetch_stats(http_client, donut_api_key, &application.ign).await;
+        let pending = application.state == ApplicationState::Pending;
+        let view = ApplicationView {
+            application: application.clone(),
+            stats,
+            expiry_days,
+        };
+        let embed = view.render(&fmt).to_embed();
+        component
+            .edit_original_interaction_response(&ctx.http, |r| {
+                r.set_embed(embed)
+                    .components(|c| application_buttons(c, id, pending, &fmt))
+            })
+            .await?;
+
+        let key = match action {
+            "accept" => "application.accepted_dm",
+            "reject" => "application.rejected_dm",
+            _ => return Ok(()),
+        };
+        let applicant = settings::format_for(application.user_id, &application.locale);
+        let message = applicant.tf(key, &[&team::load().name, &application.ign]);
+        applications::notify(ctx, application.user_id, message).await;
+    } else if let Some(ign) = custom_id.strip_prefix("team_edit_") {
+        match team::load().member(ign) {
+            Some(member) => {
+                component
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::Modal)
+                            .interaction_response_data(|d| edit_modal(d, member, &fmt))
+                    })
+                    .await?;
+            }
+            None => {
+                component
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| {
+                            d.content(fmt.tf("team.not_a_member", &[&ign]))
+                                .ephemeral(true)
+                        })
+                    })
+                    .await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles the `/team-add`, `/team-edit` and `/apply` forms.
+pub async fn handle_modal(
+    http_client: &reqwest::Client,
+    donut_api_key: &str,
+    ctx: &Context,
+    modal: &ModalSubmitInteraction,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let fmt = settings::format_for(modal.user.id.0, &modal.locale);
+    let input = |id: &str| {
+        modal
+            .data
+            .components
+            .iter()
+            .flat_map(|row| row.components.iter())
+            .find_map(|component| match component {
+                ActionRowComponent::InputText(input) if input.custom_id == id => {
+                    Some(input.value.trim().to_string())
+                }
+                _ => None,
+            })
+    };
+
+    if modal.data.custom_id == "team_add" {
+        let ign = input("ign").unwrap_or_default();
+        // Re-adding someone keeps the rank and details the form doesn't ask for
+        let mut member = team::load().member(&ign).cloned().unwrap_or_default();
+        member.ign = ign;
+        member.country = input("country").unwrap_or_default();
+        member.skills = team::parse_skills(&input("skills").unwrap_or_default());
+        member.discord_tag = input("discord").unwrap_or_default();
+        member.about = input("about").unwrap_or_default();
+
+        modal
+            .create_interaction_response(&ctx.http, |r| {
+                r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+            })
+            .await?;
+        match add_member(http_client, donut_api_key, member, modal.user.id.0, &fmt).await {
+            Ok((embed, member)) => {
+                modal
+                    .edit_original_interaction_response(&ctx.http, |r| {
+                        r.add_embed(embed)
+                            .components(|c| rank_menu(c, &member.ign, &member.rank, &fmt))
+                    })
+                    .await?;
+            }
+            Err(message) => {
+                modal.delete_original_interaction_response(&ctx.http).await?;
+                modal
+                    .create_followup_message(&ctx.http, |m| m.content(message).ephemeral(true))
+                    .await?;
+            }
+        }
+    } else if let Some(ign) = modal.data.custom_id.strip_prefix("team_edit_") {
+        // Every input is sent back, so an emptied one clears that detail
+        let country = match input("country") {
+            Some(country) if !country.is_empty() => match countries::resolve(&country, &fmt) {
+                Ok(code) => Some(code),
+                Err(message) => {
+                    modal
+                        .create_interaction_response(&ctx.http, |r| {
+                            r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                        })
+                        .await?;
+                    return Ok(());
+                }
+            },
+            other => other,
+        };
+        let update = MemberUpdate {
+            country,
+            skills: input("skills").map(|s| team::parse_skills(&s)),
+            timezone: input("timezone"),
+            base: input("base"),
+            about: input("about"),
+            ..MemberUpdate::default()
+        };
+
+        match edit_outcome(team::update_member(ign, update, modal.user.id.0), ign, &fmt) {
+            Ok(embed) => {
+                modal
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.kind(InteractionResponseType::ChannelMessageWithSource)
+                            .interaction_response_data(|d| d.add_embed(embed))
+                    })
+                    .await?;
+            }
+            Err(message) => {
+                modal
+                    .create_interaction_response(&ctx.http, |r| {
+                        r.interaction_response_data(|d| d.content(message).ephemeral(true))
                     })
+                    .await?;
+            }
+        }
+    } else if modal.data.custom_id == "apply" {
+        submit_application(http_client, donut_api_key, ctx, modal, &input, &fmt).await?;
+    }
+    Ok(())
+}
+
+/// Checks an `/apply` form and posts it to the review channel.
+async fn submit_application(
+    client: &Client,
+    donut_key: &str,
+    ctx: &Context,
+    modal: &ModalSubmitInteraction,
+    input: &(dyn Fn(&str) -> Option<String> + Sync),
+    fmt: &Format,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let ign = input("ign").unwrap_or_default();
+    // Checked again here since the form may have been open for a while
+    let checked = match (
+        applications::config(),
+        applications::pending_for(modal.user.id.0),
+    ) {
+        (None, _) => Err(fmt.t("application.closed").to_string()),
+        (_, Some(pending)) => Err(fmt.tf("application.already_pending", &[&pending.id])),
+        (Some(_), None) if team::load().member(&ign).is_some() => {
+            Err(fmt.tf("application.already_member", &[&ign]))
+        }
+        (Some(config), None) => countries::resolve(&input("country").unwrap_or_default(), fmt)
+            .map(|country| (config, country)),
+    };
+    let (config, country) = match checked {
+        Ok(checked) => checked,
+        Err(message) => {
+            modal
+                .create_interaction_response(&ctx.http, |r| {
+                    r.interaction_response_data(|d| d.content(message).ephemeral(true))
+                })
+                .await?;
+            return Ok(());
+        }
+    };
+
+    // Checking the IGN can take longer than Discord's 3 second window
+    modal
+        .create_interaction_response(&ctx.http, |r| {
+            r.kind(InteractionResponseType::DeferredChannelMessageWithSource)
+                .interaction_response_data(|d| d.ephemeral(true))
+        })
+        .await?;
+    let reply = |content: String| {
+        modal.edit_original_interaction_response(&ctx.http, move |r| r.content(content))
+    };
+    let unknown = match player_exists(client, donut_key, &ign).await {
+        Some(true) => None,
+        Some(false) => Some(fmt.tf("team.unknown_player", &[&ign])),
+        None => Some(fmt.tf("team.player_check_failed", &[&ign])),
+    };
+    if let Some(message) = unknown {
+        reply(message).await?;
+        return Ok(());
+    }
+
+    let application = applications::submit(Application {
+        id: 0,
+        user_id: modal.user.id.0,
+        user_tag: modal.user.tag(),
+        ign,
+        country,
+        skills: team::parse_skills(&input("skills").unwrap_or_default()),
+        about: input("about").unwrap_or_default(),
+        submitted: Utc::now(),
+        state: ApplicationState::Pending,
+        reviewed_by: None,
+        decided: None,
+        locale: modal.locale.clone(),
+        channel_id: None,
+        message_id: None,
+    })?;
+    let id = application.id;
+    let review = settings::guild_format();
+    let view = ApplicationView {
+        stats: fetch_stats(client, donut_key, &application.ign).await,
+        application,
+        expiry_days: config.expiry_days,
+    };
+    let embed = view.render(&review).to_embed();
+    let posted = ChannelId(config.channel_id)
+        .send_message(&ctx.http, |m| {
+            m.set_embed(embed)
+                .components(|c| application_buttons(c, id, true, &review))
+        })
+        .await;
+    match posted {
+        Ok(message) => {
+            applications::set_message(id, message.channel_id.0, message.id.0)?;
+            let message = fmt.tf("application.submitted", &[&id, &config.expiry_days]);
+            reply(message).await?;
+        }
+        Err(e) => {
+            applications::remove(id)?;
+            let message = fmt.tf("application.post_failed", &[&e]);
+            reply(message).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn handle_autocomplete(
+    ctx: &Context,
+    autocomplete: &AutocompleteInteraction,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Options of a subcommand are nested one level down
+    let focused = match autocomplete
+        .data
+        .options
+        .iter()
+        .flat_map(|o| std::iter::once(o).chain(o.options.iter()))
+        .find(|o| o.focused)
+    {
+        Some(o) => o,
+        None => return Ok(()),
+    };
+    let partial = focused
+        .value
+        .as_ref()
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    if (autocomplete.data.name.as_str(), focused.name.as_str()) == ("team-restore", "version") {
+        let fmt = settings::format_for(autocomplete.user.id.0, &autocomplete.locale);
+        let typed = focused
+            .value
+            .as_ref()
+            .map(|v| v.to_string().trim_matches('"').to_string())
+            .unwrap_or_default();
+        let versions: Vec<history::Snapshot> = history::list()
+            .into_iter()
+            .filter(|s| s.version.to_string().starts_with(&typed))
+            // Discord shows at most 25 choices
+            .take(25)
+            .collect();
+        autocomplete
+            .create_autocomplete_response(&ctx.http, |r| {
+                for snapshot in &versions {
+                    let name = fmt.tf(
+                        "history.version_choice",
+                        &[
+                            &snapshot.version,
+                            &fmt.datetime(snapshot.at),
+                            &fmt.number(snapshot.team.members.len() as i64),
+                        ],
+                    );
+                    r.add_int_choice(name, snapshot.version as i64);
+                }
+                r
             })
             .await?;
+        return Ok(());
     }
 
+    // (shown name, value sent back)
+    let suggestions: Vec<(String, String)> = match (
+        autocomplete.data.name.as_str(),
+        focused.name.as_str(),
+    ) {
+        ("lookup", "user")
+        | ("stats", "user")
+        | ("team-remove", "ign")
+        | ("team-edit", "ign")
+        | ("team-audit", "ign")
+        | ("team-squad", "ign")
+        | ("team-profile", "ign")
+        | ("trader", "ign")
+        | ("compare", _) => players::suggest(partial, autocomplete.user.id.0)
+            .into_iter()
+            .map(|ign| (ign.clone(), ign))
+            .collect(),
+        ("team-add", "country") | ("team-edit", "country") => {
+            let fmt = settings::format_for(autocomplete.user.id.0, &autocomplete.locale);
+            countries::suggest(partial, fmt.language)
+                .into_iter()
+                // Discord shows at most 25 choices
+                .take(25)
+                .map(|c| (format!("{} {}", c.flag(), c.name(fmt.language)), c.code.to_string()))
+                .collect()
+        }
+        ("team-list", "squad") | ("online", "squad") | ("team-squad", "squad") => {
+            let partial = partial.to_lowercase();
+            team::load()
+                .squads
+                .into_iter()
+                .filter(|s| s.name.to_lowercase().contains(&partial))
+                .take(25)
+                .map(|s| (s.name.clone(), s.name))
+                .collect()
+        }
+        _ => Vec::new(),
+    };
+
+    autocomplete
+        .create_autocomplete_response(&ctx.http, |r| {
+            for (name, value) in &suggestions {
+                r.add_string_choice(name, value);
+            }
+            r
+        })
+        .await?;
+
     Ok(())
 }
@@ -1,10 +1,18 @@
-use serenity::{builder::CreateComponents, model::prelude::component::ButtonStyle};
+use serenity::{
+    builder::{CreateComponents, CreateInteractionResponseData},
+    model::prelude::component::{ButtonStyle, InputTextStyle},
+};
+
+use crate::countries;
+use crate::locale::Format;
+use crate::team::{Rank, TeamMember};
 
 pub fn auction_buttons<'a>(
     components: &'a mut CreateComponents,
     current_page: u32,
     search: Option<&str>,
     sort: Option<&str>,
+    fmt: &Format,
 ) -> &'a mut CreateComponents {
     let search_encoded = search
         .map(|s| s.replace(" ", "%20"))
@@ -25,7 +33,7 @@ pub fn auction_buttons<'a>(
         .create_button(|button| {
             button
                 .style(ButtonStyle::Primary)
-                .label(format!("Page {}", current_page))
+                .label(fmt.tf("common.page", &[&current_page]))
                 .custom_id("auction_current")
                 .disabled(true)
         })
@@ -46,6 +54,7 @@ pub fn txn_buttons<'a>(
     current_page: u32,
     search: Option<&str>,
     sort: Option<&str>,
+    fmt: &Format,
 ) -> &'a mut CreateComponents {
     let search_encoded = search
         .map(|s| s.replace(" ", "%20"))
@@ -66,7 +75,7 @@ pub fn txn_buttons<'a>(
         .create_button(|button| {
             button
                 .style(ButtonStyle::Primary)
-                .label(format!("Page {}", current_page))
+                .label(fmt.tf("common.page", &[&current_page]))
                 .custom_id("transaction_current")
                 .disabled(true)
         })
@@ -86,6 +95,7 @@ pub fn lb_buttons<'a>(
     components: &'a mut CreateComponents,
     current_page: u32,
     lb_type: &str,
+    fmt: &Format,
 ) -> &'a mut CreateComponents {
     components.create_action_row(|row| {
         row.create_button(|button| {
@@ -98,7 +108,7 @@ pub fn lb_buttons<'a>(
         .create_button(|button| {
             button
                 .style(ButtonStyle::Primary)
-                .label(format!("Page {}", current_page))
+                .label(fmt.tf("common.page", &[&current_page]))
                 .custom_id("leaderboard_current")
                 .disabled(true)
         })
@@ -110,3 +120,242 @@ pub fn lb_buttons<'a>(
         })
     })
 }
+
+/// Edit button under a `/team-profile` embed.
+pub fn profile_buttons<'a>(
+    components: &'a mut CreateComponents,
+    ign: &str,
+    fmt: &Format,
+) -> &'a mut CreateComponents {
+    components.create_action_row(|row| {
+        row.create_button(|button| {
+            button
+                .style(ButtonStyle::Secondary)
+                .label(fmt.t("team.edit_button"))
+                .custom_id(format!("team_edit_{}", ign))
+        })
+    })
+}
+
+/// Apply and Cancel under a `/team-import` preview.
+pub fn import_buttons<'a>(
+    components: &'a mut CreateComponents,
+    id: &str,
+    fmt: &Format,
+) -> &'a mut CreateComponents {
+    components.create_action_row(|row| {
+        row.create_button(|button| {
+            button
+                .style(ButtonStyle::Success)
+                .label(fmt.t("import.confirm"))
+                .custom_id(format!("team_import_confirm_{}", id))
+        })
+        .create_button(|button| {
+            button
+                .style(ButtonStyle::Secondary)
+                .label(fmt.t("import.cancel"))
+                .custom_id(format!("team_import_cancel_{}", id))
+        })
+    })
+}
+
+/// Restore and Cancel under a `/team-restore` preview. The IDs carry the
+/// version, the roster version it was previewed against and who asked.
+pub fn restore_buttons<'a>(
+    components: &'a mut CreateComponents,
+    version: u64,
+    current: u64,
+    user: u64,
+    fmt: &Format,
+) -> &'a mut CreateComponents {
+    let id = format!("{}_{}_{}", version, current, user);
+    components.create_action_row(|row| {
+        row.create_button(|button| {
+            button
+                .style(ButtonStyle::Danger)
+                .label(fmt.t("restore.confirm"))
+                .custom_id(format!("team_restore_confirm_{}", id))
+        })
+        .create_button(|button| {
+            button
+                .style(ButtonStyle::Secondary)
+                .label(fmt.t("import.cancel"))
+                .custom_id(format!("team_restore_cancel_{}", id))
+        })
+    })
+}
+
+/// One text input per row, in order. The `about` input gets a paragraph box.
+fn text_inputs<'a>(
+    components: &'a mut CreateComponents,
+    inputs: &[(&str, &str, &str, u64, bool)],
+) -> &'a mut CreateComponents {
+    for &(id, label, value, max_length, required) in inputs {
+        components.create_action_row(|row| {
+            row.create_input_text(|input| {
+                let style = if id == "about" {
+                    InputTextStyle::Paragraph
+                } else {
+                    InputTextStyle::Short
+                };
+                input
+                    .custom_id(id)
+                    .label(label)
+                    .style(style)
+                    .max_length(max_length)
+                    .required(required);
+                // Discord rejects an empty pre-filled value
+                if !value.is_empty() {
+                    input.value(value);
+                }
+                input
+            })
+        });
+    }
+    components
+}
+
+/// Modal for `/team-edit`, pre-filled with the member's current details.
+/// Clearing an input clears that detail.
+pub fn edit_modal<'a, 'b>(
+    data: &'a mut CreateInteractionResponseData<'b>,
+    member: &TeamMember,
+    fmt: &Format,
+) -> &'a mut CreateInteractionResponseData<'b> {
+    let country = countries::display_name(&member.country, fmt.language);
+    let skills = member.skills.join(", ");
+    data.custom_id(format!("team_edit_{}", member.ign))
+        .title(fmt.tf("team.edit_title", &[&member.ign]))
+        .components(|c| {
+            text_inputs(
+                c,
+                &[
+                    ("country", fmt.t("profile.country"), &country, 100, false),
+                    ("skills", fmt.t("opt.skill"), &skills, 200, false),
+                    (
+                        "timezone",
+                        fmt.t("profile.timezone"),
+                        &member.timezone,
+                        100,
+                        false,
+                    ),
+                    ("base", fmt.t("profile.base"), &member.base, 200, false),
+                    ("about", fmt.t("profile.about"), &member.about, 1000, false),
+                ],
+            )
+        })
+}
+
+/// Modal for `/team-add`, pre-filled with whatever options were given.
+/// The rank is picked afterwards with [`rank_menu`].
+pub fn add_modal<'a, 'b>(
+    data: &'a mut CreateInteractionResponseData<'b>,
+    draft: &TeamMember,
+    fmt: &Format,
+) -> &'a mut CreateInteractionResponseData<'b> {
+    let skills = draft.skills.join(", ");
+    data.custom_id("team_add")
+        .title(fmt.t("team.add_title"))
+        .components(|c| {
+            text_inputs(
+                c,
+                &[
+                    ("ign", fmt.t("opt.ign"), &draft.ign, 16, true),
+                    (
+                        "country",
+                        fmt.t("profile.country"),
+                        &draft.country,
+                        100,
+                        true,
+                    ),
+                    ("skills", fmt.t("opt.skill"), &skills, 200, true),
+                    (
+                        "discord",
+                        fmt.t("profile.discord"),
+                        &draft.discord_tag,
+                        100,
+                        false,
+                    ),
+                    ("about", fmt.t("profile.about"), &draft.about, 1000, false),
+                ],
+            )
+        })
+}
+
+/// Modal for `/apply`.
+pub fn apply_modal<'a, 'b>(
+    data: &'a mut CreateInteractionResponseData<'b>,
+    fmt: &Format,
+) -> &'a mut CreateInteractionResponseData<'b> {
+    data.custom_id("apply")
+        .title(fmt.t("application.modal_title"))
+        .components(|c| {
+            text_inputs(
+                c,
+                &[
+                    ("ign", fmt.t("opt.ign"), "", 16, true),
+                    ("country", fmt.t("profile.country"), "", 100, true),
+                    ("skills", fmt.t("opt.skill"), "", 200, false),
+                    ("about", fmt.t("application.about_label"), "", 1000, true),
+                ],
+            )
+        })
+}
+
+/// Buttons under an application in the review channel. Decided applications
+/// only keep the stats refresh.
+pub fn application_buttons<'a>(
+    components: &'a mut CreateComponents,
+    id: u64,
+    pending: bool,
+    fmt: &Format,
+) -> &'a mut CreateComponents {
+    components.create_action_row(|row| {
+        if pending {
+            row.create_button(|button| {
+                button
+                    .style(ButtonStyle::Success)
+                    .label(fmt.t("application.accept"))
+                    .custom_id(format!("application_accept_{}", id))
+            })
+            .create_button(|button| {
+                button
+                    .style(ButtonStyle::Danger)
+                    .label(fmt.t("application.reject"))
+                    .custom_id(format!("application_reject_{}", id))
+            });
+        }
+        row.create_button(|button| {
+            button
+                .style(ButtonStyle::Secondary)
+                .label(fmt.t("application.refresh"))
+                .custom_id(format!("application_refresh_{}", id))
+        })
+    })
+}
+
+/// Rank picker under a saved team member.
+pub fn rank_menu<'a>(
+    components: &'a mut CreateComponents,
+    ign: &str,
+    current: &Rank,
+    fmt: &Format,
+) -> &'a mut CreateComponents {
+    components.create_action_row(|row| {
+        row.create_select_menu(|menu| {
+            menu.custom_id(format!("team_rank_{}", ign))
+                .placeholder(fmt.t("team.rank_placeholder"))
+                .options(|options| {
+                    for rank in Rank::ALL {
+                        options.create_option(|option| {
+                            option
+                                .label(format!("{} {}", rank.emoji(), fmt.t(rank.label_key())))
+                                .value(rank.as_str())
+                                .default_selection(rank == *current)
+                        });
+                    }
+                    options
+                })
+        })
+    })
+}
@@ -0,0 +1,526 @@
+use crate::locale::{Format, Language};
+
+/// An ISO 3166-1 country. Team members store the `code`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Country {
+    pub code: &'static str,
+    pub en: &'static str,
+    pub nl: &'static str,
+    /// Native names and other names people type.
+    pub aliases: &'static [&'static str],
+}
+
+impl Country {
+    pub fn name(&self, language: Language) -> &'static str {
+        match language {
+            Language::En => self.en,
+            Language::Nl => self.nl,
+        }
+    }
+
+    pub fn flag(&self) -> String {
+        flag_from_code(self.code).unwrap_or_default()
+    }
+}
+
+const fn country(
+    code: &'static str,
+    en: &'static str,
+    nl: &'static str,
+    aliases: &'static [&'static str],
+) -> Country {
+    Country {
+        code,
+        en,
+        nl,
+        aliases,
+    }
+}
+
+#[rustfmt::skip]
+pub const COUNTRIES: &[Country] = &[
+    country("AD", "Andorra", "Andorra", &[]),
+    country("AE", "United Arab Emirates", "Verenigde Arabische Emiraten", &["UAE", "Emirates", "الإمارات"]),
+    country("AF", "Afghanistan", "Afghanistan", &["افغانستان"]),
+    country("AG", "Antigua and Barbuda", "Antigua en Barbuda", &["Antigua"]),
+    country("AI", "Anguilla", "Anguilla", &[]),
+    country("AL", "Albania", "Albanië", &["Shqipëria"]),
+    country("AM", "Armenia", "Armenië", &["Hayastan", "Հայաստան"]),
+    country("AO", "Angola", "Angola", &[]),
+    country("AQ", "Antarctica", "Antarctica", &[]),
+    country("AR", "Argentina", "Argentinië", &[]),
+    country("AS", "American Samoa", "Amerikaans-Samoa", &[]),
+    country("AT", "Austria", "Oostenrijk", &["Österreich"]),
+    country("AU", "Australia", "Australië", &[]),
+    country("AW", "Aruba", "Aruba", &[]),
+    country("AX", "Åland Islands", "Åland", &["Ahvenanmaa"]),
+    country("AZ", "Azerbaijan", "Azerbeidzjan", &["Azərbaycan"]),
+    country("BA", "Bosnia and Herzegovina", "Bosnië en Herzegovina", &["Bosnia", "Bosna i Hercegovina", "BiH"]),
+    country("BB", "Barbados", "Barbados", &[]),
+    country("BD", "Bangladesh", "Bangladesh", &["বাংলাদেশ"]),
+    country("BE", "Belgium", "België", &["Belgique", "Belgien"]),
+    country("BF", "Burkina Faso", "Burkina Faso", &[]),
+    country("BG", "Bulgaria", "Bulgarije", &["България", "Balgariya"]),
+    country("BH", "Bahrain", "Bahrein", &["البحرين"]),
+    country("BI", "Burundi", "Burundi", &[]),
+    country("BJ", "Benin", "Benin", &["Bénin"]),
+    country("BL", "Saint Barthélemy", "Saint-Barthélemy", &["St Barts", "Saint Barts"]),
+    country("BM", "Bermuda", "Bermuda", &[]),
+    country("BN", "Brunei", "Brunei", &["Brunei Darussalam"]),
+    country("BO", "Bolivia", "Bolivia", &[]),
+    country("BQ", "Caribbean Netherlands", "Caribisch Nederland", &["Bonaire", "Sint Eustatius", "Saba", "BES Islands"]),
+    country("BR", "Brazil", "Brazilië", &["Brasil"]),
+    country("BS", "Bahamas", "Bahama's", &["The Bahamas"]),
+    country("BT", "Bhutan", "Bhutan", &["འབྲུག"]),
+    country("BV", "Bouvet Island", "Bouveteiland", &[]),
+    country("BW", "Botswana", "Botswana", &[]),
+    country("BY", "Belarus", "Belarus", &["Беларусь", "Wit-Rusland", "Byelorussia"]),
+    country("BZ", "Belize", "Belize", &[]),
+    country("CA", "Canada", "Canada", &[]),
+    country("CC", "Cocos (Keeling) Islands", "Cocoseilanden", &["Cocos Islands", "Keeling Islands"]),
+    country("CD", "DR Congo", "Congo-Kinshasa", &["Democratic Republic of the Congo", "DRC", "Zaire"]),
+    country("CF", "Central African Republic", "Centraal-Afrikaanse Republiek", &["Centrafrique"]),
+    country("CG", "Congo", "Congo-Brazzaville", &["Republic of the Congo"]),
+    country("CH", "Switzerland", "Zwitserland", &["Schweiz", "Suisse", "Svizzera"]),
+    country("CI", "Côte d'Ivoire", "Ivoorkust", &["Ivory Coast"]),
+    country("CK", "Cook Islands", "Cookeilanden", &[]),
+    country("CL", "Chile", "Chili", &[]),
+    country("CM", "Cameroon", "Kameroen", &["Cameroun"]),
+    country("CN", "China", "China", &["中国", "Zhongguo", "PRC"]),
+    country("CO", "Colombia", "Colombia", &[]),
+    country("CR", "Costa Rica", "Costa Rica", &[]),
+    country("CU", "Cuba", "Cuba", &[]),
+    country("CV", "Cape Verde", "Kaapverdië", &["Cabo Verde"]),
+    country("CW", "Curaçao", "Curaçao", &["Kòrsou"]),
+    country("CX", "Christmas Island", "Christmaseiland", &[]),
+    country("CY", "Cyprus", "Cyprus", &["Κύπρος", "Kıbrıs"]),
+    country("CZ", "Czechia", "Tsjechië", &["Czech Republic", "Česko", "Česká republika"]),
+    country("DE", "Germany", "Duitsland", &["Deutschland"]),
+    country("DJ", "Djibouti", "Djibouti", &[]),
+    country("DK", "Denmark", "Denemarken", &["Danmark"]),
+    country("DM", "Dominica", "Dominica", &[]),
+    country("DO", "Dominican Republic", "Dominicaanse Republiek", &["República Dominicana"]),
+    country("DZ", "Algeria", "Algerije", &["Algérie", "الجزائر"]),
+    country("EC", "Ecuador", "Ecuador", &[]),
+    country("EE", "Estonia", "Estland", &["Eesti"]),
+    country("EG", "Egypt", "Egypte", &["Misr", "مصر"]),
+    country("EH", "Western Sahara", "Westelijke Sahara", &[]),
+    country("ER", "Eritrea", "Eritrea", &[]),
+    country("ES", "Spain", "Spanje", &["España"]),
+    country("ET", "Ethiopia", "Ethiopië", &["ኢትዮጵያ"]),
+    country("FI", "Finland", "Finland", &["Suomi"]),
+    country("FJ", "Fiji", "Fiji", &[]),
+    country("FK", "Falkland Islands", "Falklandeilanden", &["Falklands", "Malvinas"]),
+    country("FM", "Micronesia", "Micronesia", &["Federated States of Micronesia"]),
+    country("FO", "Faroe Islands", "Faeröer", &["Faroes", "Føroyar"]),
+    country("FR", "France", "Frankrijk", &[]),
+    country("GA", "Gabon", "Gabon", &[]),
+    country("GB", "United Kingdom", "Verenigd Koninkrijk", &["UK", "Great Britain", "Britain", "England", "Scotland", "Wales", "Northern Ireland", "Engeland", "Schotland", "Groot-Brittannië"]),
+    country("GD", "Grenada", "Grenada", &[]),
+    country("GE", "Georgia", "Georgië", &["Sakartvelo", "საქართველო"]),
+    country("GF", "French Guiana", "Frans-Guyana", &["Guyane"]),
+    country("GG", "Guernsey", "Guernsey", &[]),
+    country("GH", "Ghana", "Ghana", &[]),
+    country("GI", "Gibraltar", "Gibraltar", &[]),
+    country("GL", "Greenland", "Groenland", &["Kalaallit Nunaat", "Grønland"]),
+    country("GM", "Gambia", "Gambia", &["The Gambia"]),
+    country("GN", "Guinea", "Guinee", &["Guinée"]),
+    country("GP", "Guadeloupe", "Guadeloupe", &[]),
+    country("GQ", "Equatorial Guinea", "Equatoriaal-Guinea", &["Guinea Ecuatorial"]),
+    country("GR", "Greece", "Griekenland", &["Ελλάδα", "Hellas", "Ellada"]),
+    country("GS", "South Georgia and the South Sandwich Islands", "Zuid-Georgia en de Zuidelijke Sandwicheilanden", &["South Georgia"]),
+    country("GT", "Guatemala", "Guatemala", &[]),
+    country("GU", "Guam", "Guam", &[]),
+    country("GW", "Guinea-Bissau", "Guinee-Bissau", &["Guiné-Bissau"]),
+    country("GY", "Guyana", "Guyana", &[]),
+    country("HK", "Hong Kong", "Hongkong", &["香港"]),
+    country("HM", "Heard Island and McDonald Islands", "Heard en McDonaldeilanden", &[]),
+    country("HN", "Honduras", "Honduras", &[]),
+    country("HR", "Croatia", "Kroatië", &["Hrvatska"]),
+    country("HT", "Haiti", "Haïti", &["Ayiti"]),
+    country("HU", "Hungary", "Hongarije", &["Magyarország"]),
+    country("ID", "Indonesia", "Indonesië", &[]),
+    country("IE", "Ireland", "Ierland", &["Éire"]),
+    country("IL", "Israel", "Israël", &["ישראל"]),
+    country("IM", "Isle of Man", "Man", &["Mannin"]),
+    country("IN", "India", "India", &["Bharat", "भारत"]),
+    country("IO", "British Indian Ocean Territory", "Brits Indische Oceaanterritorium", &["Chagos Islands"]),
+    country("IQ", "Iraq", "Irak", &["العراق"]),
+    country("IR", "Iran", "Iran", &["ایران"]),
+    country("IS", "Iceland", "IJsland", &["Ísland"]),
+    country("IT", "Italy", "Italië", &["Italia"]),
+    country("JE", "Jersey", "Jersey", &[]),
+    country("JM", "Jamaica", "Jamaica", &[]),
+    country("JO", "Jordan", "Jordanië", &["الأردن"]),
+    country("JP", "Japan", "Japan", &["日本", "Nippon", "Nihon"]),
+    country("KE", "Kenya", "Kenia", &[]),
+    country("KG", "Kyrgyzstan", "Kirgizië", &["Kirghizia", "Кыргызстан"]),
+    country("KH", "Cambodia", "Cambodja", &["Kampuchea"]),
+    country("KI", "Kiribati", "Kiribati", &[]),
+    country("KM", "Comoros", "Comoren", &["Comores"]),
+    country("KN", "Saint Kitts and Nevis", "Saint Kitts en Nevis", &["St Kitts"]),
+    country("KP", "North Korea", "Noord-Korea", &["DPRK", "조선"]),
+    country("KR", "South Korea", "Zuid-Korea", &["Korea", "Hanguk", "한국"]),
+    country("KW", "Kuwait", "Koeweit", &["الكويت"]),
+    country("KY", "Cayman Islands", "Kaaimaneilanden", &["Caymans"]),
+    country("KZ", "Kazakhstan", "Kazachstan", &["Қазақстан", "Казахстан"]),
+    country("LA", "Laos", "Laos", &["ລາວ"]),
+    country("LB", "Lebanon", "Libanon", &["Liban", "لبنان"]),
+    country("LC", "Saint Lucia", "Saint Lucia", &["St Lucia"]),
+    country("LI", "Liechtenstein", "Liechtenstein", &[]),
+    country("LK", "Sri Lanka", "Sri Lanka", &["Ceylon"]),
+    country("LR", "Liberia", "Liberia", &[]),
+    country("LS", "Lesotho", "Lesotho", &[]),
+    country("LT", "Lithuania", "Litouwen", &["Lietuva"]),
+    country("LU", "Luxembourg", "Luxemburg", &["Lëtzebuerg"]),
+    country("LV", "Latvia", "Letland", &["Latvija"]),
+    country("LY", "Libya", "Libië", &["ليبيا"]),
+    country("MA", "Morocco", "Marokko", &["Maroc", "المغرب"]),
+    country("MC", "Monaco", "Monaco", &[]),
+    country("MD", "Moldova", "Moldavië", &["Moldavia"]),
+    country("ME", "Montenegro", "Montenegro", &["Crna Gora"]),
+    country("MF", "Saint Martin", "Sint-Maarten (Frans deel)", &["Saint-Martin"]),
+    country("MG", "Madagascar", "Madagaskar", &[]),
+    country("MH", "Marshall Islands", "Marshalleilanden", &[]),
+    country("MK", "North Macedonia", "Noord-Macedonië", &["Macedonia", "Северна Македонија"]),
+    country("ML", "Mali", "Mali", &[]),
+    country("MM", "Myanmar", "Myanmar", &["Burma", "Birma"]),
+    country("MN", "Mongolia", "Mongolië", &["Монгол Улс"]),
+    country("MO", "Macao", "Macau", &["澳門"]),
+    country("MP", "Northern Mariana Islands", "Noordelijke Marianen", &[]),
+    country("MQ", "Martinique", "Martinique", &[]),
+    country("MR", "Mauritania", "Mauritanië", &["Mauritanie"]),
+    country("MS", "Montserrat", "Montserrat", &[]),
+    country("MT", "Malta", "Malta", &[]),
+    country("MU", "Mauritius", "Mauritius", &["Maurice"]),
+    country("MV", "Maldives", "Maldiven", &[]),
+    country("MW", "Malawi", "Malawi", &[]),
+    country("MX", "Mexico", "Mexico", &["México"]),
+    country("MY", "Malaysia", "Maleisië", &[]),
+    country("MZ", "Mozambique", "Mozambique", &["Moçambique"]),
+    country("NA", "Namibia", "Namibië", &[]),
+    country("NC", "New Caledonia", "Nieuw-Caledonië", &["Nouvelle-Calédonie"]),
+    country("NE", "Niger", "Niger", &[]),
+    country("NF", "Norfolk Island", "Norfolk", &[]),
+    country("NG", "Nigeria", "Nigeria", &[]),
+    country("NI", "Nicaragua", "Nicaragua", &[]),
+    country("NL", "Netherlands", "Nederland", &["The Netherlands", "Holland"]),
+    country("NO", "Norway", "Noorwegen", &["Norge", "Noreg"]),
+    country("NP", "Nepal", "Nepal", &["नेपाल"]),
+    country("NR", "Nauru", "Nauru", &[]),
+    country("NU", "Niue", "Niue", &[]),
+    country("NZ", "New Zealand", "Nieuw-Zeeland", &["Aotearoa"]),
+    country("OM", "Oman", "Oman", &["عمان"]),
+    country("PA", "Panama", "Panama", &["Panamá"]),
+    country("PE", "Peru", "Peru", &["Perú"]),
+    country("PF", "French Polynesia", "Frans-Polynesië", &["Polynésie française", "Tahiti"]),
+    country("PG", "Papua New Guinea", "Papoea-Nieuw-Guinea", &["PNG"]),
+    country("PH", "Philippines", "Filipijnen", &["Pilipinas"]),
+    country("PK", "Pakistan", "Pakistan", &["پاکستان"]),
+    country("PL", "Poland", "Polen", &["Polska"]),
+    country("PM", "Saint Pierre and Miquelon", "Saint-Pierre en Miquelon", &[]),
+    country("PN", "Pitcairn Islands", "Pitcairneilanden", &["Pitcairn"]),
+    country("PR", "Puerto Rico", "Puerto Rico", &[]),
+    country("PS", "Palestine", "Palestina", &["فلسطين"]),
+    country("PT", "Portugal", "Portugal", &[]),
+    country("PW", "Palau", "Palau", &[]),
+    country("PY", "Paraguay", "Paraguay", &[]),
+    country("QA", "Qatar", "Qatar", &["قطر"]),
+    country("RE", "Réunion", "Réunion", &["La Réunion"]),
+    country("RO", "Romania", "Roemenië", &["România"]),
+    country("RS", "Serbia", "Servië", &["Srbija", "Србија"]),
+    country("RU", "Russia", "Rusland", &["Russian Federation", "Rossiya", "Россия"]),
+    country("RW", "Rwanda", "Rwanda", &[]),
+    country("SA", "Saudi Arabia", "Saoedi-Arabië", &["KSA", "السعودية"]),
+    country("SB", "Solomon Islands", "Salomonseilanden", &[]),
+    country("SC", "Seychelles", "Seychellen", &[]),
+    country("SD", "Sudan", "Soedan", &["السودان"]),
+    country("SE", "Sweden", "Zweden", &["Sverige"]),
+    country("SG", "Singapore", "Singapore", &["Singapura", "新加坡"]),
+    country("SH", "Saint Helena", "Sint-Helena", &["St Helena"]),
+    country("SI", "Slovenia", "Slovenië", &["Slovenija"]),
+    country("SJ", "Svalbard and Jan Mayen", "Spitsbergen en Jan Mayen", &["Svalbard"]),
+    country("SK", "Slovakia", "Slowakije", &["Slovensko"]),
+    country("SL", "Sierra Leone", "Sierra Leone", &[]),
+    country("SM", "San Marino", "San Marino", &[]),
+    country("SN", "Senegal", "Senegal", &["Sénégal"]),
+    country("SO", "Somalia", "Somalië", &["Soomaaliya"]),
+    country("SR", "Suriname", "Suriname", &["Surinam"]),
+    country("SS", "South Sudan", "Zuid-Soedan", &[]),
+    country("ST", "São Tomé and Príncipe", "Sao Tomé en Principe", &[]),
+    country("SV", "El Salvador", "El Salvador", &[]),
+    country("SX", "Sint Maarten", "Sint Maarten", &[]),
+    country("SY", "Syria", "Syrië", &["سوريا"]),
+    country("SZ", "Eswatini", "Eswatini", &["Swaziland"]),
+    country("TC", "Turks and Caicos Islands", "Turks- en Caicoseilanden", &[]),
+    country("TD", "Chad", "Tsjaad", &["Tchad"]),
+    country("TF", "French Southern Territories", "Franse Zuidelijke Gebieden", &[]),
+    country("TG", "Togo", "Togo", &[]),
+    country("TH", "Thailand", "Thailand", &["Siam", "ประเทศไทย"]),
+    country("TJ", "Tajikistan", "Tadzjikistan", &["Тоҷикистон"]),
+    country("TK", "Tokelau", "Tokelau", &[]),
+    country("TL", "Timor-Leste", "Oost-Timor", &["East Timor"]),
+    country("TM", "Turkmenistan", "Turkmenistan", &["Türkmenistan"]),
+    country("TN", "Tunisia", "Tunesië", &["Tunisie", "تونس"]),
+    country("TO", "Tonga", "Tonga", &[]),
+    country("TR", "Turkey", "Turkije", &["Türkiye"]),
+    country("TT", "Trinidad and Tobago", "Trinidad en Tobago", &["Trinidad"]),
+    country("TV", "Tuvalu", "Tuvalu", &[]),
+    country("TW", "Taiwan", "Taiwan", &["臺灣", "台湾"]),
+    country("TZ", "Tanzania", "Tanzania", &[]),
+    country("UA", "Ukraine", "Oekraïne", &["Ukraina", "Україна"]),
+    country("UG", "Uganda", "Oeganda", &[]),
+    country("UM", "United States Minor Outlying Islands", "Kleine afgelegen eilanden van de Verenigde Staten", &[]),
+    country("US", "United States", "Verenigde Staten", &["USA", "America", "United States of America", "Amerika"]),
+    country("UY", "Uruguay", "Uruguay", &[]),
+    country("UZ", "Uzbekistan", "Oezbekistan", &["Oʻzbekiston"]),
+    country("VA", "Vatican City", "Vaticaanstad", &["Vatican", "Holy See"]),
+    country("VC", "Saint Vincent and the Grenadines", "Saint Vincent en de Grenadines", &["St Vincent"]),
+    country("VE", "Venezuela", "Venezuela", &[]),
+    country("VG", "British Virgin Islands", "Britse Maagdeneilanden", &[]),
+    country("VI", "U.S. Virgin Islands", "Amerikaanse Maagdeneilanden", &["US Virgin Islands"]),
+    country("VN", "Vietnam", "Vietnam", &["Việt Nam"]),
+    country("VU", "Vanuatu", "Vanuatu", &[]),
+    country("WF", "Wallis and Futuna", "Wallis en Futuna", &[]),
+    country("WS", "Samoa", "Samoa", &[]),
+    // Not assigned by ISO, but what everyone uses for Kosovo
+    country("XK", "Kosovo", "Kosovo", &["Kosova"]),
+    country("YE", "Yemen", "Jemen", &["اليمن"]),
+    country("YT", "Mayotte", "Mayotte", &[]),
+    country("ZA", "South Africa", "Zuid-Afrika", &["Suid-Afrika", "RSA"]),
+    country("ZM", "Zambia", "Zambia", &[]),
+    country("ZW", "Zimbabwe", "Zimbabwe", &[]),
+];
+
+fn flag_from_code(code: &str) -> Option<String> {
+    if code.len() != 2 {
+        return None;
+    }
+    let mut res = String::new();
+    for ch in code.chars() {
+        let u = ch.to_ascii_uppercase();
+        if !u.is_ascii_alphabetic() {
+            return None;
+        }
+        let base: u32 = 'A' as u32;
+        let ri_offset: u32 = 0x1F1E6;
+        let cp = ri_offset + (u as u32 - base);
+        if let Some(c) = char::from_u32(cp) {
+            res.push(c);
+        }
+    }
+    Some(res)
+}
+
+/// Lowercase without accents or punctuation, so `Côte d'Ivoire` and
+/// `cote divoire` compare equal.
+fn fold(text: &str) -> String {
+    let mut folded = String::new();
+    for c in text.trim().to_lowercase().chars() {
+        match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => folded.push('a'),
+            'ç' | 'č' => folded.push('c'),
+            'è' | 'é' | 'ê' | 'ë' | 'ě' | 'ə' => folded.push('e'),
+            'ì' | 'í' | 'î' | 'ï' | 'ı' => folded.push('i'),
+            'ñ' => folded.push('n'),
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => folded.push('o'),
+            'ù' | 'ú' | 'û' | 'ü' => folded.push('u'),
+            'ý' | 'ÿ' => folded.push('y'),
+            'š' | 'ş' | 'ș' => folded.push('s'),
+            'ž' => folded.push('z'),
+            'ß' => folded.push_str("ss"),
+            'æ' => folded.push_str("ae"),
+            '-' | '_' | '/' => folded.push(' '),
+            '&' => folded.push_str(" and "),
+            '.' | ',' | '\'' | 'ʻ' | '’' | '(' | ')' => {}
+            c => folded.push(c),
+        }
+    }
+    folded.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+impl Country {
+    /// Every name the country goes by, folded.
+    fn folded_names(&self) -> impl Iterator<Item = String> + '_ {
+        [self.en, self.nl]
+            .into_iter()
+            .chain(self.aliases.iter().copied())
+            .map(fold)
+    }
+}
+
+pub fn by_code(code: &str) -> Option<&'static Country> {
+    COUNTRIES
+        .iter()
+        .find(|c| c.code.eq_ignore_ascii_case(code.trim()))
+}
+
+/// A country by its code or any of its names, spelled exactly.
+pub fn exact(input: &str) -> Option<&'static Country> {
+    if let Some(country) = by_code(input) {
+        return Some(country);
+    }
+    let folded = fold(input);
+    COUNTRIES
+        .iter()
+        .find(|c| c.folded_names().any(|name| name == folded))
+}
+
+/// Levenshtein distance, counted in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// Finds the country someone meant. A small typo is corrected when only one
+/// country is that close; otherwise `Err` holds up to three to suggest.
+pub fn find(input: &str) -> Result<&'static Country, Vec<&'static Country>> {
+    if let Some(country) = exact(input) {
+        return Ok(country);
+    }
+    let folded = fold(input);
+    let length = folded.chars().count();
+    if length < 3 {
+        return Err(Vec::new());
+    }
+    let mut close: Vec<(usize, bool, &'static Country)> = COUNTRIES
+        .iter()
+        .filter_map(|country| {
+            let (distance, prefix) = country
+                .folded_names()
+                .map(|name| (edit_distance(&folded, &name), name.starts_with(&folded)))
+                .min_by_key(|&(distance, prefix)| if prefix { 0 } else { distance })?;
+            (prefix || distance <= (length / 3).max(1)).then_some((distance, prefix, country))
+        })
+        .collect();
+    close.sort_by_key(|&(distance, prefix, country)| {
+        (if prefix { 0 } else { distance }, country.code)
+    });
+
+    let typo_limit = if length >= 8 { 2 } else { 1 };
+    if let [(best, false, country), rest @ ..] = close.as_slice() {
+        let unique = rest.first().is_none_or(|(next, _, _)| next > best);
+        if *best <= typo_limit && unique {
+            return Ok(country);
+        }
+    }
+    Err(close.into_iter().take(3).map(|(_, _, c)| c).collect())
+}
+
+/// The ISO code for a typed country, or the message explaining why it isn't
+/// one.
+pub fn resolve(input: &str, fmt: &Format) -> Result<String, String> {
+    match find(input) {
+        Ok(country) => Ok(country.code.to_string()),
+        Err(suggestions) if suggestions.is_empty() => {
+            Err(fmt.tf("team.unknown_country", &[&input.trim()]))
+        }
+        Err(suggestions) => {
+            let names: Vec<String> = suggestions
+                .iter()
+                .map(|c| format!("{} **{}**", c.flag(), c.name(fmt.language)))
+                .collect();
+            Err(fmt.tf(
+                "team.country_suggestions",
+                &[&input.trim(), &names.join(", ")],
+            ))
+        }
+    }
+}
+
+/// Countries whose name in `language`, or any other name, contains what was
+/// typed so far, for autocomplete. Names starting with it come first.
+pub fn suggest(partial: &str, language: Language) -> Vec<&'static Country> {
+    let folded = fold(partial);
+    let mut matches: Vec<(bool, &'static Country)> = COUNTRIES
+        .iter()
+        .filter_map(|country| {
+            let mut names = country.folded_names();
+            if folded.is_empty() || country.code.eq_ignore_ascii_case(&folded) {
+                return Some((true, country));
+            }
+            let local = fold(country.name(language));
+            if local.starts_with(&folded) {
+                return Some((true, country));
+            }
+            names
+                .any(|name| name.contains(&folded))
+                .then_some((false, country))
+        })
+        .collect();
+    matches.sort_by_key(|&(starts, country)| (!starts, fold(country.name(language))));
+    matches.into_iter().map(|(_, c)| c).collect()
+}
+
+/// The flag for a country code or name, or nothing when it isn't known.
+pub fn country_flag(input: &str) -> String {
+    exact(input).map(Country::flag).unwrap_or_default()
+}
+
+/// A stored country in the reader's language. Anything that isn't a known
+/// country is shown as stored.
+pub fn display_name(stored: &str, language: Language) -> String {
+    match exact(stored) {
+        Some(country) => country.name(language).to_string(),
+        None => stored.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_is_complete() {
+        assert_eq!(COUNTRIES.len(), 250);
+        for pair in COUNTRIES.windows(2) {
+            assert!(pair[0].code < pair[1].code, "{} is out of order", pair[1].code);
+        }
+    }
+
+    #[test]
+    fn names_aliases_and_codes() {
+        let code = |input: &str| find(input).map(|c| c.code).ok();
+        assert_eq!(code(" nederland "), Some("NL"));
+        assert_eq!(code("be"), Some("BE"));
+        assert_eq!(code("cote divoire"), Some("CI"));
+        assert_eq!(code("日本"), Some("JP"));
+        assert_eq!(code("England"), Some("GB"));
+        assert_eq!(country_flag("Holland"), "🇳🇱");
+    }
+
+    #[test]
+    fn typos_are_corrected_or_suggested() {
+        assert_eq!(find("Germny").map(|c| c.code), Ok("DE"));
+        assert_eq!(find("Untied Kingdom").map(|c| c.code), Ok("GB"));
+        let codes = |input: &str| match find(input) {
+            Ok(country) => vec![country.code],
+            Err(suggestions) => suggestions.iter().map(|c| c.code).collect(),
+        };
+        assert_eq!(codes("Swe"), vec!["SE"]);
+        assert_eq!(codes("Austrai"), vec!["AT", "AU"]);
+        assert!(codes("Atlantis").is_empty());
+        assert!(find("zz").is_err());
+    }
+
+    #[test]
+    fn stored_countries_display_localized() {
+        assert_eq!(display_name("DE", Language::Nl), "Duitsland");
+        assert_eq!(display_name("Germany", Language::En), "Germany");
+        assert_eq!(display_name("Narnia", Language::Nl), "Narnia");
+    }
+}
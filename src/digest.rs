@@ -0,0 +1,463 @@
+use chrono::{DateTime, Datelike, Duration as ChronoDuration, Timelike, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+use serenity::{model::prelude::ChannelId, prelude::*};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self},
+    path::PathBuf,
+    time::Duration,
+};
+use tracing::{error, info};
+
+use crate::market::{median, MarketData, SaleRecord};
+use crate::render::Render;
+
+const TOP_N: usize = 5;
+/// How often the scheduler wakes up to check whether a digest is due.
+const CHECK_INTERVAL_SECS: u64 = 10 * 60;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigestPeriod {
+    Daily,
+    Weekly,
+}
+
+impl DigestPeriod {
+    pub fn length(&self) -> ChronoDuration {
+        match self {
+            DigestPeriod::Daily => ChronoDuration::days(1),
+            DigestPeriod::Weekly => ChronoDuration::weeks(1),
+        }
+    }
+
+    /// Catalogue key for the digest title.
+    pub fn title_key(&self) -> &'static str {
+        match self {
+            DigestPeriod::Daily => "digest.daily",
+            DigestPeriod::Weekly => "digest.weekly",
+        }
+    }
+
+    /// Identifies the day or ISO week a digest was posted for.
+    fn stamp(&self, now: DateTime<Utc>) -> String {
+        match self {
+            DigestPeriod::Daily => now.format("%Y-%m-%d").to_string(),
+            DigestPeriod::Weekly => {
+                let week = now.iso_week();
+                format!("{}-W{:02}", week.year(), week.week())
+            }
+        }
+    }
+}
+
+/// Which digests were already posted, so restarts don't post twice.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct DigestState {
+    #[serde(default)]
+    last_daily: Option<String>,
+    #[serde(default)]
+    last_weekly: Option<String>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("DIGEST_STATE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("digest_state.json")
+}
+
+fn load_state() -> DigestState {
+    let path = store_path();
+    if !path.exists() {
+        return DigestState::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => DigestState::default(),
+    }
+}
+
+fn save_state(state: &DigestState) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(state).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// A per-item price change between the previous and the current period.
+pub struct PriceMove {
+    pub name: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl PriceMove {
+    pub fn percent(&self) -> f64 {
+        (self.after - self.before) / self.before * 100.0
+    }
+}
+
+/// Market statistics for one digest period.
+pub struct Digest<'a> {
+    pub period: DigestPeriod,
+    pub sales: Vec<&'a SaleRecord>,
+    pub previous: Vec<&'a SaleRecord>,
+    /// Team members' IGNs, for the team activity section.
+    pub roster: Vec<String>,
+}
+
+impl<'a> Digest<'a> {
+    pub fn build(
+        data: &'a MarketData,
+        period: DigestPeriod,
+        roster: Vec<String>,
+        now: DateTime<Utc>,
+    ) -> Digest<'a> {
+        let end = now.timestamp();
+        let start = (now - period.length()).timestamp();
+        let prev_start = (now - period.length() - period.length()).timestamp();
+        Digest {
+            period,
+            sales: data
+                .sales
+                .iter()
+                .filter(|s| s.sold_at > start && s.sold_at <= end)
+                .collect(),
+            previous: data
+                .sales
+                .iter()
+                .filter(|s| s.sold_at > prev_start && s.sold_at <= start)
+                .collect(),
+            roster,
+        }
+    }
+
+    pub fn total_volume(&self) -> i64 {
+        self.sales.iter().map(|s| s.price).sum()
+    }
+
+    /// Items by summed sale price, with the number of sales.
+    pub fn top_items(&self) -> Vec<(String, i64, usize)> {
+        let mut items: HashMap<&str, (String, i64, usize)> = HashMap::new();
+        for sale in &self.sales {
+            let entry = items
+                .entry(sale.item_key.as_str())
+                .or_insert_with(|| (sale.item_name(), 0, 0));
+            entry.1 += sale.price;
+            entry.2 += 1;
+        }
+        let mut items: Vec<(String, i64, usize)> = items.into_values().collect();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        items.truncate(TOP_N);
+        items
+    }
+
+    /// Items whose median per-unit price changed the most since the previous period.
+    pub fn price_movers(&self) -> Vec<PriceMove> {
+        fn medians<'b>(sales: &[&'b SaleRecord]) -> HashMap<&'b str, (String, f64, usize)> {
+            let mut prices: HashMap<&str, (String, Vec<f64>)> = HashMap::new();
+            for sale in sales {
+                prices
+                    .entry(sale.item_key.as_str())
+                    .or_insert_with(|| (sale.item_name(), Vec::new()))
+                    .1
+                    .push(sale.unit_price());
+            }
+            prices
+                .into_iter()
+                .filter_map(|(key, (name, p))| {
+                    let samples = p.len();
+                    median(p).map(|m| (key, (name, m, samples)))
+                })
+                .collect()
+        }
+
+        let now = medians(&self.sales);
+        let before = medians(&self.previous);
+        let mut moves: Vec<PriceMove> = now
+            .into_iter()
+            .filter_map(|(key, (name, after, n))| {
+                let (_, before, m) = before.get(key)?;
+                if n < 2 || *m < 2 || *before <= 0.0 {
+                    return None;
+                }
+                Some(PriceMove {
+                    name,
+                    before: *before,
+                    after,
+                })
+            })
+            .filter(|m| m.after != m.before)
+            .collect();
+        moves.sort_by(|a, b| b.percent().abs().total_cmp(&a.percent().abs()));
+        moves.truncate(TOP_N);
+        moves
+    }
+
+    pub fn most_expensive(&self) -> Vec<&'a SaleRecord> {
+        let mut sales = self.sales.clone();
+        sales.sort_by_key(|s| std::cmp::Reverse(s.price));
+        sales.truncate(TOP_N);
+        sales
+    }
+
+    /// Sellers by number of sales, with their volume.
+    pub fn active_sellers(&self) -> Vec<(String, usize, i64)> {
+        let mut sellers: HashMap<String, (String, usize, i64)> = HashMap::new();
+        for sale in &self.sales {
+            let entry = sellers
+                .entry(sale.seller.to_ascii_lowercase())
+                .or_insert_with(|| (sale.seller.clone(), 0, 0));
+            entry.1 += 1;
+            entry.2 += sale.price;
+        }
+        let mut sellers: Vec<(String, usize, i64)> = sellers.into_values().collect();
+        sellers.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
+        sellers.truncate(TOP_N);
+        sellers
+    }
+
+    /// Sales and purchases per team member; members without activity are left out.
+    pub fn team_activity(&self) -> Vec<(String, usize, i64, usize)> {
+        let mut activity: Vec<(String, usize, i64, usize)> = self
+            .roster
+            .iter()
+            .map(|ign| {
+                let sold: Vec<&&SaleRecord> = self
+                    .sales
+                    .iter()
+                    .filter(|s| s.seller.eq_ignore_ascii_case(ign))
+                    .collect();
+                let bought = self
+                    .sales
+                    .iter()
+                    .filter(|s| {
+                        s.buyer
+                            .as_deref()
+                            .is_some_and(|b| b.eq_ignore_ascii_case(ign))
+                    })
+                    .count();
+                (
+                    ign.clone(),
+                    sold.len(),
+                    sold.iter().map(|s| s.price).sum(),
+                    bought,
+                )
+            })
+            .filter(|(_, sold, _, bought)| *sold > 0 || *bought > 0)
+            .collect();
+        activity.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        activity
+    }
+}
+
+async fn post_digest(
+    ctx: &Context,
+    channel_id: u64,
+    period: DigestPeriod,
+    now: DateTime<Utc>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let data = crate::market::load();
+    let roster: Vec<String> = crate::team::load()
+        .members
+        .into_iter()
+        .map(|m| m.ign)
+        .collect();
+    let digest = Digest::build(&data, period, roster, now);
+    // One message per embed, as Discord's total limit covers a whole message
+    for embed in digest.render(&crate::settings::guild_format()).to_embeds() {
+        ChannelId(channel_id)
+            .send_message(&ctx.http, |m| m.set_embed(embed))
+            .await?;
+    }
+    Ok(())
+}
+
+/// Posts the daily digest once per day after `hour_utc`, and the weekly
+/// digest on Mondays after the same hour.
+pub async fn run(ctx: Context, channel_id: u64, hour_utc: u32) {
+    loop {
+        let now = Utc::now();
+        if now.hour() >= hour_utc {
+            let mut state = load_state();
+            let mut due = Vec::new();
+            if state.last_daily.as_deref() != Some(DigestPeriod::Daily.stamp(now).as_str()) {
+                due.push(DigestPeriod::Daily);
+            }
+            if now.weekday() == Weekday::Mon
+                && state.last_weekly.as_deref() != Some(DigestPeriod::Weekly.stamp(now).as_str())
+            {
+                due.push(DigestPeriod::Weekly);
+            }
+            for period in due {
+                match post_digest(&ctx, channel_id, period, now).await {
+                    Ok(()) => {
+                        info!("Posted {:?} market digest", period);
+                        match period {
+                            DigestPeriod::Daily => state.last_daily = Some(period.stamp(now)),
+                            DigestPeriod::Weekly => state.last_weekly = Some(period.stamp(now)),
+                        }
+                        if let Err(e) = save_state(&state) {
+                            error!("Failed to save digest state: {:?}", e);
+                        }
+                    }
+                    Err(e) => error!("Failed to post market digest: {:?}", e),
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sale(item: &str, price: i64, seller: &str, sold_at: i64) -> SaleRecord {
+        SaleRecord {
+            item_key: format!("minecraft:{}", item),
+            item_id: format!("minecraft:{}", item),
+            name: item.to_string(),
+            label: item.to_string(),
+            count: 1,
+            price,
+            seller: seller.to_string(),
+            buyer: Some("Alex".into()),
+            sold_at,
+            ingested_at: false,
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.timestamp_opt(10 * 86_400, 0).unwrap()
+    }
+
+    /// Seconds since the epoch, `days` before [`now`].
+    fn ago(days: f64) -> i64 {
+        now().timestamp() - (days * 86_400.0) as i64
+    }
+
+    #[test]
+    fn build_splits_the_period_from_the_one_before() {
+        let data = MarketData {
+            sales: vec![
+                sale("diamond", 100, "Steve", ago(0.5)),
+                sale("diamond", 100, "Steve", ago(1.5)),
+                sale("diamond", 100, "Steve", ago(2.5)),
+                sale("diamond", 100, "Steve", ago(-0.5)),
+            ],
+        };
+        let digest = Digest::build(&data, DigestPeriod::Daily, Vec::new(), now());
+        assert_eq!(digest.sales.len(), 1);
+        assert_eq!(digest.previous.len(), 1);
+        assert_eq!(digest.previous[0].sold_at, ago(1.5));
+
+        let weekly = Digest::build(&data, DigestPeriod::Weekly, Vec::new(), now());
+        assert_eq!(weekly.sales.len(), 3);
+        assert_eq!(weekly.total_volume(), 300);
+    }
+
+    #[test]
+    fn top_items_rank_by_volume() {
+        let data = MarketData {
+            sales: vec![
+                sale("diamond", 100, "Steve", ago(0.1)),
+                sale("diamond", 150, "Steve", ago(0.2)),
+                sale("elytra", 200, "Steve", ago(0.3)),
+                sale("dirt", 1, "Steve", ago(0.4)),
+            ],
+        };
+        let digest = Digest::build(&data, DigestPeriod::Daily, Vec::new(), now());
+        assert_eq!(
+            digest.top_items(),
+            vec![
+                ("diamond".to_string(), 250, 2),
+                ("elytra".to_string(), 200, 1),
+                ("dirt".to_string(), 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn price_movers_need_two_sales_in_both_periods() {
+        let data = MarketData {
+            sales: vec![
+                // Diamond doubles, elytra drops by a quarter
+                sale("diamond", 100, "Steve", ago(1.2)),
+                sale("diamond", 100, "Steve", ago(1.4)),
+                sale("diamond", 200, "Steve", ago(0.2)),
+                sale("diamond", 200, "Steve", ago(0.4)),
+                sale("elytra", 400, "Steve", ago(1.2)),
+                sale("elytra", 400, "Steve", ago(1.4)),
+                sale("elytra", 300, "Steve", ago(0.2)),
+                sale("elytra", 300, "Steve", ago(0.4)),
+                // Only one sale before, so no trend
+                sale("dirt", 1, "Steve", ago(1.2)),
+                sale("dirt", 5, "Steve", ago(0.2)),
+                sale("dirt", 5, "Steve", ago(0.4)),
+                // Unchanged
+                sale("stone", 2, "Steve", ago(1.2)),
+                sale("stone", 2, "Steve", ago(1.4)),
+                sale("stone", 2, "Steve", ago(0.2)),
+                sale("stone", 2, "Steve", ago(0.4)),
+            ],
+        };
+        let digest = Digest::build(&data, DigestPeriod::Daily, Vec::new(), now());
+        let movers = digest.price_movers();
+        let names: Vec<&str> = movers.iter().map(|m| m.name.as_str()).collect();
+        assert_eq!(names, vec!["diamond", "elytra"]);
+        assert_eq!(movers[0].percent(), 100.0);
+        assert_eq!(movers[1].percent(), -25.0);
+    }
+
+    #[test]
+    fn sellers_are_merged_case_insensitively() {
+        let data = MarketData {
+            sales: vec![
+                sale("diamond", 100, "Steve", ago(0.1)),
+                sale("diamond", 100, "steve", ago(0.2)),
+                sale("elytra", 900, "Notch", ago(0.3)),
+                sale("dirt", 50, "Alex", ago(0.4)),
+            ],
+        };
+        let digest = Digest::build(&data, DigestPeriod::Daily, Vec::new(), now());
+        assert_eq!(
+            digest.active_sellers(),
+            vec![
+                ("Steve".to_string(), 2, 200),
+                ("Notch".to_string(), 1, 900),
+                ("Alex".to_string(), 1, 50),
+            ]
+        );
+    }
+
+    #[test]
+    fn team_activity_skips_idle_members() {
+        let data = MarketData {
+            sales: vec![
+                sale("diamond", 100, "Steve", ago(0.1)),
+                sale("elytra", 900, "Notch", ago(0.2)),
+            ],
+        };
+        let roster = vec![
+            "steve".to_string(),
+            "alex".to_string(),
+            "Herobrine".to_string(),
+        ];
+        let digest = Digest::build(&data, DigestPeriod::Daily, roster, now());
+        assert_eq!(
+            digest.team_activity(),
+            vec![
+                ("steve".to_string(), 1, 100, 0),
+                ("alex".to_string(), 0, 0, 2),
+            ]
+        );
+    }
+}
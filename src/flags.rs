@@ -0,0 +1,39 @@
+use reqwest::Client;
+use std::{collections::HashMap, path::PathBuf};
+
+use crate::countries;
+use crate::team::Team;
+
+fn cache_dir() -> PathBuf {
+    if let Ok(p) = std::env::var("FLAG_CACHE_DIR") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("flags")
+}
+
+/// Where flags come from; `{}` is replaced by the lowercase ISO code.
+fn flag_url(code: &str) -> String {
+    let template =
+        std::env::var("FLAG_URL").unwrap_or_else(|_| "https://flagcdn.com/w40/{}.png".to_string());
+    template.replace("{}", &code.to_lowercase())
+}
+
+/// Flags as PNGs for every known country on the roster, by ISO code.
+/// Countries whose flag can't be fetched are left out.
+pub async fn for_team(client: &Client, team: &Team) -> HashMap<String, Vec<u8>> {
+    let mut flags = HashMap::new();
+    for member in &team.members {
+        let code = match countries::exact(&member.country) {
+            Some(country) => country.code,
+            None => continue,
+        };
+        if flags.contains_key(code) {
+            continue;
+        }
+        let path = cache_dir().join(format!("{}.png", code.to_lowercase()));
+        if let Some(png) = crate::skins::cached_png(client, &flag_url(code), &path).await {
+            flags.insert(code.to_string(), png);
+        }
+    }
+    flags
+}
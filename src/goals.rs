@@ -0,0 +1,591 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    builder::CreateEmbed,
+    model::id::{ChannelId, UserId},
+    prelude::Context,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+use tracing::{error, info};
+
+use crate::api::fetch_stats;
+use crate::locale::Format;
+use crate::models::PlayerStats;
+use crate::render::Render;
+
+const MINUTE_MS: i64 = 60 * 1000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+
+pub struct GoalConfig {
+    /// Where reached goals and milestones are celebrated. Without it, goals
+    /// are celebrated by DM and milestones aren't announced.
+    pub channel_id: Option<u64>,
+    pub interval_minutes: u64,
+}
+
+/// Reads `GOAL_CHANNEL_ID` and `GOAL_CHECK_INTERVAL_MINUTES` (default 30);
+/// `None` when the interval is 0, which turns the checks off.
+pub fn config() -> Option<GoalConfig> {
+    let channel_id = std::env::var("GOAL_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok());
+    let interval_minutes = std::env::var("GOAL_CHECK_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(30);
+    (interval_minutes > 0).then_some(GoalConfig {
+        channel_id,
+        interval_minutes,
+    })
+}
+
+/// A stat goals and milestones can be set on.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum GoalStat {
+    Money,
+    Shards,
+    Kills,
+    MobsKilled,
+    Playtime,
+    PlacedBlocks,
+    BrokenBlocks,
+}
+
+impl GoalStat {
+    pub const ALL: [GoalStat; 7] = [
+        GoalStat::Money,
+        GoalStat::Shards,
+        GoalStat::Kills,
+        GoalStat::MobsKilled,
+        GoalStat::Playtime,
+        GoalStat::PlacedBlocks,
+        GoalStat::BrokenBlocks,
+    ];
+
+    pub fn parse(s: &str) -> Option<GoalStat> {
+        GoalStat::ALL
+            .into_iter()
+            .find(|stat| stat.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GoalStat::Money => "money",
+            GoalStat::Shards => "shards",
+            GoalStat::Kills => "kills",
+            GoalStat::MobsKilled => "mobs_killed",
+            GoalStat::Playtime => "playtime",
+            GoalStat::PlacedBlocks => "placed_blocks",
+            GoalStat::BrokenBlocks => "broken_blocks",
+        }
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            GoalStat::Money => "stats.money",
+            GoalStat::Shards => "stats.shards",
+            GoalStat::Kills => "stats.kills",
+            GoalStat::MobsKilled => "stats.mobs_killed",
+            GoalStat::Playtime => "stats.playtime",
+            GoalStat::PlacedBlocks => "stats.blocks_placed",
+            GoalStat::BrokenBlocks => "stats.blocks_broken",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            GoalStat::Money => "💰",
+            GoalStat::Shards => "💎",
+            GoalStat::Kills => "⚔️",
+            GoalStat::MobsKilled => "🐗",
+            GoalStat::Playtime => "🕒",
+            GoalStat::PlacedBlocks => "🧱",
+            GoalStat::BrokenBlocks => "⛏️",
+        }
+    }
+
+    pub fn value(&self, stats: &PlayerStats) -> Option<i64> {
+        match self {
+            GoalStat::Money => stats.money,
+            GoalStat::Shards => stats.shards,
+            GoalStat::Kills => stats.kills,
+            GoalStat::MobsKilled => stats.mobs_killed,
+            GoalStat::Playtime => stats.playtime_ms,
+            GoalStat::PlacedBlocks => stats.placed_blocks,
+            GoalStat::BrokenBlocks => stats.broken_blocks,
+        }
+    }
+
+    /// `$10.000.000`, `500h`, `2h 30m` or `1.000`. Playtime is kept in
+    /// milliseconds and shown rounded to the hour, or to the minute below 10h.
+    pub fn show(&self, value: i64, fmt: &Format) -> String {
+        match self {
+            GoalStat::Money => fmt.money(value),
+            GoalStat::Playtime if value < 10 * HOUR_MS => {
+                let minutes = (value + MINUTE_MS / 2) / MINUTE_MS;
+                match (minutes / 60, minutes % 60) {
+                    (hours, 0) => format!("{}h", hours),
+                    (0, minutes) => format!("{}m", minutes),
+                    (hours, minutes) => format!("{}h {}m", hours, minutes),
+                }
+            }
+            GoalStat::Playtime => format!("{}h", fmt.number((value + HOUR_MS / 2) / HOUR_MS)),
+            _ => fmt.number(value),
+        }
+    }
+
+    /// Reads a target such as `10M`, `2.5k`, `1.000.000` or, for playtime,
+    /// `500h`, `500` (hours) or `30d`.
+    pub fn parse_target(&self, input: &str) -> Option<i64> {
+        let input = input.trim().to_ascii_lowercase().replace([' ', '_'], "");
+        let (number, unit) = match input.char_indices().find(|(_, c)| c.is_ascii_alphabetic()) {
+            Some((i, _)) => input.split_at(i),
+            None => (input.as_str(), ""),
+        };
+        let scale = match (self, unit) {
+            (GoalStat::Playtime, "" | "h") => HOUR_MS,
+            (GoalStat::Playtime, "d") => 24 * HOUR_MS,
+            (GoalStat::Playtime, _) => return None,
+            (_, "") => 1,
+            (_, "k") => 1_000,
+            (_, "m") => 1_000_000,
+            (_, "b") => 1_000_000_000,
+            _ => return None,
+        };
+        let value = if scale == 1 {
+            // Without a suffix, dots and commas can only group thousands
+            number.replace(['.', ','], "").parse::<i64>().ok()? as f64
+        } else {
+            number.replace(',', ".").parse::<f64>().ok()?
+        };
+        let target = (value * scale as f64).round();
+        (target >= 1.0 && target < i64::MAX as f64).then_some(target as i64)
+    }
+
+    /// Milestones announced for team members.
+    pub fn milestones(&self) -> &'static [i64] {
+        match self {
+            GoalStat::Money => &[1_000_000, 10_000_000, 100_000_000, 1_000_000_000],
+            GoalStat::Shards => &[1_000, 10_000, 100_000],
+            GoalStat::Kills => &[100, 1_000, 10_000],
+            GoalStat::MobsKilled => &[1_000, 10_000, 100_000],
+            GoalStat::Playtime => &[100 * HOUR_MS, 500 * HOUR_MS, 1_000 * HOUR_MS],
+            GoalStat::PlacedBlocks | GoalStat::BrokenBlocks => &[10_000, 100_000, 1_000_000],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Goal {
+    /// Discord user who set it.
+    pub user_id: u64,
+    /// Their linked IGN when they set it.
+    pub ign: String,
+    pub stat: GoalStat,
+    pub target: i64,
+    pub set_at: DateTime<Utc>,
+    #[serde(default)]
+    pub reached: Option<DateTime<Utc>>,
+    /// The user's Discord locale, for the DM when it's reached.
+    #[serde(default)]
+    pub locale: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct GoalStore {
+    #[serde(default)]
+    goals: Vec<Goal>,
+    /// Milestones already announced, by lowercase IGN, as `stat:threshold`.
+    #[serde(default)]
+    milestones: BTreeMap<String, BTreeSet<String>>,
+}
+
+/// A user's goals with their latest stats, for `/goal list`.
+pub struct GoalProgress {
+    pub ign: String,
+    pub goals: Vec<Goal>,
+    pub stats: Option<PlayerStats>,
+}
+
+/// Something worth celebrating.
+pub enum Announcement {
+    Goal {
+        goal: Goal,
+        value: i64,
+    },
+    Milestone {
+        ign: String,
+        stat: GoalStat,
+        threshold: i64,
+    },
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("GOAL_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("goals.json")
+}
+
+/// Held around every read and write of the store, so the goal checks and
+/// `/goal` can't overwrite each other's changes.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn load() -> GoalStore {
+    let _guard = lock();
+    load_from(&store_path())
+}
+
+/// Loads, changes and saves the store at `path` in one go under the lock.
+/// `change` returns whether there is anything to save.
+fn update(path: &Path, change: impl FnOnce(&mut GoalStore) -> bool) -> io::Result<()> {
+    let _guard = lock();
+    let mut store = load_from(path);
+    if change(&mut store) {
+        save_to(path, &store)?;
+    }
+    Ok(())
+}
+
+fn load_from(path: &Path) -> GoalStore {
+    if !path.exists() {
+        return GoalStore::default();
+    }
+    match fs::read_to_string(path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => GoalStore::default(),
+    }
+}
+
+fn save_to(path: &Path, store: &GoalStore) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(store).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// A user's goals that haven't been reached yet.
+pub fn active_for(user_id: u64) -> Vec<Goal> {
+    load()
+        .goals
+        .into_iter()
+        .filter(|g| g.user_id == user_id && g.reached.is_none())
+        .collect()
+}
+
+/// Sets a goal, replacing the user's earlier goal on the same stat.
+pub fn set(goal: Goal) -> io::Result<()> {
+    update(&store_path(), |store| {
+        store
+            .goals
+            .retain(|g| g.reached.is_none() && !(g.user_id == goal.user_id && g.stat == goal.stat));
+        store.goals.push(goal);
+        true
+    })
+}
+
+/// Drops the user's goal on `stat`; `false` when they had none.
+pub fn remove(user_id: u64, stat: GoalStat) -> io::Result<bool> {
+    let mut removed = false;
+    update(&store_path(), |store| {
+        let before = store.goals.len();
+        store
+            .goals
+            .retain(|g| !(g.user_id == user_id && g.stat == stat && g.reached.is_none()));
+        removed = store.goals.len() != before;
+        removed
+    })?;
+    Ok(removed)
+}
+
+/// Takes the goals `stats` meets out of the store and returns them, marked
+/// reached, with the value that got there. Goals are only kept until they're
+/// announced, so reached goals saved by earlier versions are dropped too.
+fn reach_goals(
+    store: &mut GoalStore,
+    stats: &PlayerStats,
+    now: DateTime<Utc>,
+) -> Vec<Announcement> {
+    let mut reached = Vec::new();
+    store.goals.retain_mut(|goal| {
+        if goal.reached.is_some() {
+            return false;
+        }
+        if !goal.ign.eq_ignore_ascii_case(&stats.player) {
+            return true;
+        }
+        match goal.stat.value(stats).filter(|&v| v >= goal.target) {
+            Some(value) => {
+                goal.reached = Some(now);
+                reached.push(Announcement::Goal {
+                    goal: goal.clone(),
+                    value,
+                });
+                false
+            }
+            None => true,
+        }
+    });
+    reached
+}
+
+/// New milestones `stats` passed, at most one per stat. Milestones a player
+/// had already passed when first checked are remembered without being
+/// announced, so turning this on doesn't flood the channel.
+fn pass_milestones(store: &mut GoalStore, stats: &PlayerStats) -> Vec<Announcement> {
+    let first_check = !store.milestones.contains_key(&stats.player.to_lowercase());
+    let announced = store
+        .milestones
+        .entry(stats.player.to_lowercase())
+        .or_default();
+    let mut passed = Vec::new();
+    for stat in GoalStat::ALL {
+        let value = match stat.value(stats) {
+            Some(value) => value,
+            None => continue,
+        };
+        let mut highest = None;
+        for &threshold in stat.milestones().iter().filter(|&&t| value >= t) {
+            if announced.insert(format!("{}:{}", stat.as_str(), threshold)) {
+                highest = Some(threshold);
+            }
+        }
+        if let (Some(threshold), false) = (highest, first_check) {
+            passed.push(Announcement::Milestone {
+                ign: stats.player.clone(),
+                stat,
+                threshold,
+            });
+        }
+    }
+    passed
+}
+
+/// Checks one player's stats against the goals stored at `path` and, with
+/// `milestones`, against the milestones. Saves whenever something changed,
+/// including the silent baseline taken the first time a player is checked.
+fn check(
+    path: &Path,
+    stats: &PlayerStats,
+    milestones: bool,
+    now: DateTime<Utc>,
+) -> io::Result<Vec<Announcement>> {
+    let mut announcements = Vec::new();
+    update(path, |store| {
+        let goals = store.goals.len();
+        announcements = reach_goals(store, stats, now);
+        let mut changed = store.goals.len() != goals;
+        if milestones {
+            let key = stats.player.to_lowercase();
+            let known = store.milestones.get(&key).map(BTreeSet::len);
+            announcements.extend(pass_milestones(store, stats));
+            changed |= store.milestones.get(&key).map(BTreeSet::len) != known;
+        }
+        changed
+    })?;
+    Ok(announcements)
+}
+
+/// Sends `embed` to the user by DM, logging when they don't accept DMs.
+async fn direct_message(ctx: &Context, user_id: u64, embed: CreateEmbed) {
+    let channel = match UserId(user_id).create_dm_channel(&ctx.http).await {
+        Ok(channel) => channel,
+        Err(e) => {
+            error!("Failed to open DM with {}: {:?}", user_id, e);
+            return;
+        }
+    };
+    if let Err(e) = channel
+        .send_message(&ctx.http, |m| m.set_embed(embed))
+        .await
+    {
+        error!("Failed to send goal DM to {}: {:?}", user_id, e);
+    }
+}
+
+/// Checks everyone with an open goal and, when there's a channel for it,
+/// every team member for milestones.
+pub async fn run(ctx: Context, http_client: Client, donut_key: String, config: GoalConfig) {
+    loop {
+        let team = crate::team::load();
+        let mut igns: BTreeMap<String, String> = load()
+            .goals
+            .iter()
+            .filter(|g| g.reached.is_none())
+            .map(|g| (g.ign.to_lowercase(), g.ign.clone()))
+            .collect();
+        if config.channel_id.is_some() {
+            for member in &team.members {
+                igns.entry(member.ign.to_lowercase())
+                    .or_insert_with(|| member.ign.clone());
+            }
+        }
+
+        for ign in igns.into_values() {
+            let stats = match fetch_stats(&http_client, &donut_key, &ign).await {
+                Some(stats) => stats,
+                None => continue,
+            };
+            let milestones = config.channel_id.is_some() && team.member(&ign).is_some();
+            let announcements = match check(&store_path(), &stats, milestones, Utc::now()) {
+                Ok(announcements) => announcements,
+                Err(e) => {
+                    error!("Failed to save goals: {:?}", e);
+                    continue;
+                }
+            };
+
+            for announcement in announcements {
+                match (&announcement, config.channel_id) {
+                    (Announcement::Goal { goal, .. }, None) => {
+                        info!("Goal reached by {}", goal.ign);
+                        let fmt = crate::settings::format_for(goal.user_id, &goal.locale);
+                        let embed = announcement.render(&fmt).to_embed();
+                        direct_message(&ctx, goal.user_id, embed).await;
+                    }
+                    (_, Some(channel_id)) => {
+                        let fmt = crate::settings::guild_format();
+                        let embed = announcement.render(&fmt).to_embed();
+                        if let Err(e) = ChannelId(channel_id)
+                            .send_message(&ctx.http, |m| m.set_embed(embed))
+                            .await
+                        {
+                            error!("Failed to post goal announcement: {:?}", e);
+                        }
+                    }
+                    (Announcement::Milestone { .. }, None) => {}
+                }
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(
+            config.interval_minutes.saturating_mul(60),
+        ))
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn targets_take_suffixes_and_separators() {
+        assert_eq!(GoalStat::Money.parse_target("10M"), Some(10_000_000));
+        assert_eq!(GoalStat::Money.parse_target("2,5k"), Some(2_500));
+        assert_eq!(GoalStat::Kills.parse_target("1.000"), Some(1_000));
+        assert_eq!(GoalStat::Playtime.parse_target("500h"), Some(500 * HOUR_MS));
+        assert_eq!(GoalStat::Playtime.parse_target("2d"), Some(48 * HOUR_MS));
+        assert_eq!(GoalStat::Playtime.parse_target("5m"), None);
+        assert_eq!(GoalStat::Money.parse_target("lots"), None);
+        assert_eq!(GoalStat::Money.parse_target("0"), None);
+    }
+
+    #[test]
+    fn playtime_is_rounded_for_display() {
+        let fmt = Format::default();
+        let show = |ms: i64| GoalStat::Playtime.show(ms, &fmt);
+        assert_eq!(show(5 * HOUR_MS / 2), "2h 30m");
+        assert_eq!(show(2 * HOUR_MS), "2h");
+        assert_eq!(show(45 * MINUTE_MS), "45m");
+        assert_eq!(show(HOUR_MS * 119 / 2), "60h");
+        assert_eq!(show(500 * HOUR_MS), "500h");
+    }
+
+    #[test]
+    fn reached_goals_are_pruned() {
+        let path = std::env::temp_dir().join(format!("goals-prune-{}.json", std::process::id()));
+        let now = Utc::now();
+        let goal = |stat, target| Goal {
+            user_id: 1,
+            ign: "Steve".into(),
+            stat,
+            target,
+            set_at: now,
+            reached: None,
+            locale: String::new(),
+        };
+        let store = GoalStore {
+            goals: vec![goal(GoalStat::Kills, 100), goal(GoalStat::Money, 1_000)],
+            ..GoalStore::default()
+        };
+        save_to(&path, &store).unwrap();
+        let stats = PlayerStats {
+            player: "Steve".into(),
+            kills: Some(150),
+            money: Some(10),
+            ..PlayerStats::default()
+        };
+        let reached = check(&path, &stats, false, now).unwrap();
+        let left = load_from(&path).goals;
+        let _ = fs::remove_file(&path);
+        assert!(matches!(
+            reached.as_slice(),
+            [Announcement::Goal { value: 150, .. }]
+        ));
+        assert_eq!(left, vec![goal(GoalStat::Money, 1_000)]);
+    }
+
+    #[test]
+    fn milestones_start_from_where_players_are() {
+        let mut store = GoalStore::default();
+        let mut stats = PlayerStats {
+            player: "Steve".into(),
+            kills: Some(150),
+            ..PlayerStats::default()
+        };
+        // Already past 100 kills when first checked
+        assert!(pass_milestones(&mut store, &stats).is_empty());
+        stats.kills = Some(12_000);
+        let passed = pass_milestones(&mut store, &stats);
+        assert!(matches!(
+            passed.as_slice(),
+            [Announcement::Milestone {
+                stat: GoalStat::Kills,
+                threshold: 10_000,
+                ..
+            }]
+        ));
+        assert!(pass_milestones(&mut store, &stats).is_empty());
+    }
+
+    #[test]
+    fn milestone_baseline_is_saved() {
+        let path = std::env::temp_dir().join(format!("goals-test-{}.json", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let mut stats = PlayerStats {
+            player: "Steve".into(),
+            kills: Some(150),
+            ..PlayerStats::default()
+        };
+        let now = Utc::now();
+        // The first check only takes the baseline, but it has to stick
+        assert!(check(&path, &stats, true, now).unwrap().is_empty());
+        assert!(check(&path, &stats, true, now).unwrap().is_empty());
+        stats.kills = Some(1_200);
+        let passed = check(&path, &stats, true, now).unwrap();
+        let _ = fs::remove_file(&path);
+        assert!(matches!(
+            passed.as_slice(),
+            [Announcement::Milestone {
+                stat: GoalStat::Kills,
+                threshold: 1_000,
+                ..
+            }]
+        ));
+    }
+}
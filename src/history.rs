@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, path::PathBuf};
+
+use crate::audit::Change;
+use crate::team::Team;
+
+/// Older snapshots are dropped once there are more than this.
+const MAX_SNAPSHOTS: usize = 100;
+
+/// The roster as it was before a change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u64,
+    /// When the change that replaced this version was made.
+    pub at: DateTime<Utc>,
+    /// Discord user who made that change.
+    pub by: u64,
+    pub team: Team,
+}
+
+/// What restoring a snapshot would change, shown before `/team-restore` asks
+/// for confirmation.
+pub struct RestorePreview {
+    pub snapshot: Snapshot,
+    pub changes: Vec<Change>,
+}
+
+/// The reply to `/team-undo`.
+pub struct Undone {
+    pub snapshot: Snapshot,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct History {
+    /// Versions are never reused, even after an undo, so a restore preview
+    /// can tell whether the roster moved on since it was shown.
+    #[serde(default)]
+    last_version: u64,
+    #[serde(default)]
+    snapshots: Vec<Snapshot>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("HISTORY_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("team_history.json")
+}
+
+fn load() -> History {
+    let path = store_path();
+    if !path.exists() {
+        return History::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => History::default(),
+    }
+}
+
+fn save(history: &History) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(history).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// Keeps `team` as a new version, before the Discord user `by` changes it.
+pub fn push(team: &Team, by: u64) -> io::Result<()> {
+    let mut history = load();
+    history.last_version += 1;
+    history.snapshots.push(Snapshot {
+        version: history.last_version,
+        at: Utc::now(),
+        by,
+        team: team.clone(),
+    });
+    let excess = history.snapshots.len().saturating_sub(MAX_SNAPSHOTS);
+    history.snapshots.drain(..excess);
+    save(&history)
+}
+
+/// Removes and returns the newest snapshot.
+pub fn pop() -> io::Result<Option<Snapshot>> {
+    let mut history = load();
+    let snapshot = history.snapshots.pop();
+    if snapshot.is_some() {
+        save(&history)?;
+    }
+    Ok(snapshot)
+}
+
+/// The newest snapshot, left in place.
+pub fn latest() -> Option<Snapshot> {
+    load().snapshots.pop()
+}
+
+pub fn get(version: u64) -> Option<Snapshot> {
+    load().snapshots.into_iter().find(|s| s.version == version)
+}
+
+/// The newest version, which identifies the roster as it is now.
+pub fn latest_version() -> u64 {
+    load().snapshots.last().map_or(0, |s| s.version)
+}
+
+/// Every kept snapshot, newest first.
+pub fn list() -> Vec<Snapshot> {
+    let mut snapshots = load().snapshots;
+    snapshots.reverse();
+    snapshots
+}
@@ -0,0 +1,587 @@
+use serenity::builder::{CreateApplicationCommand, CreateApplicationCommandOption};
+use std::fmt::Display;
+
+use crate::locale::Language;
+
+/// Every user-facing message as `(key, English, Dutch)`. Placeholders are `{}`
+/// and are filled in order, so both translations must use the same number.
+const MESSAGES: &[(&str, &str, &str)] = &[
+    // Command descriptions, registered with Discord's localization fields
+    ("cmd.lookup", "Get player info from DonutSMP", "Spelerinfo opvragen bij DonutSMP"),
+    ("cmd.stats", "Get detailed stats/profile from DonutSMP", "Gedetailleerde statistieken opvragen bij DonutSMP"),
+    ("cmd.compare", "Compare the stats of two or more players side by side", "De statistieken van twee of meer spelers naast elkaar zetten"),
+    ("cmd.leaderboard", "Show DonutSMP leaderboards", "DonutSMP-ranglijsten tonen"),
+    ("cmd.auction", "Show auction house entries", "Aanbiedingen in het veilinghuis tonen"),
+    ("cmd.auction_transactions", "Show auction house transaction history", "Transactiegeschiedenis van het veilinghuis tonen"),
+    ("cmd.trader", "Show a player's auction listings, sales and purchases", "Veilingaanbiedingen, verkopen en aankopen van een speler tonen"),
+    ("cmd.link", "Link your Discord account to your in-game name", "Je Discord-account koppelen aan je in-game naam"),
+    ("cmd.goal", "Set personal stat goals and get celebrated when you reach them", "Persoonlijke doelen voor je statistieken stellen en gevierd worden als je ze haalt"),
+    ("cmd.goal.set", "Set a goal for your linked account, e.g. money 10M or playtime 500h", "Een doel stellen voor je gekoppelde account, bijv. money 10M of playtime 500h"),
+    ("cmd.goal.remove", "Drop one of your goals", "Een van je doelen laten vallen"),
+    ("cmd.goal.list", "Show your goals and how close you are", "Je doelen tonen en hoe dichtbij je bent"),
+    ("cmd.settings", "Choose your language and how numbers and durations are shown", "Kies je taal en hoe getallen en tijdsduren worden getoond"),
+    ("cmd.help", "Show all available commands with descriptions", "Alle beschikbare commando's met uitleg tonen"),
+    ("cmd.team_name", "Set or view the team name", "De teamnaam instellen of bekijken"),
+    ("cmd.team_add", "Add or update a team member; leave options out to fill in a form", "Een teamlid toevoegen of bijwerken; laat opties weg om een formulier in te vullen"),
+    ("cmd.team_remove", "Remove a team member by IGN", "Een teamlid verwijderen op IGN"),
+    ("cmd.team_list", "List the team and members", "Het team en de leden tonen"),
+    ("cmd.online", "Check which team members are online", "Bekijken welke teamleden online zijn"),
+    ("cmd.team_help", "Show team commands and usage", "Teamcommando's en gebruik tonen"),
+    ("cmd.team_profile", "Show a team member's profile with live status and stats", "Het profiel van een teamlid tonen met live status en statistieken"),
+    ("cmd.team_edit", "Change a member's details; only given fields change and `-` clears one", "Gegevens van een lid wijzigen; alleen opgegeven velden veranderen en `-` wist er een"),
+    ("cmd.team_countries", "Show how many team members come from each country", "Tonen hoeveel teamleden uit elk land komen"),
+    ("cmd.team_export", "Download the roster as a JSON or CSV file", "Het ledenbestand downloaden als JSON- of CSV-bestand"),
+    ("cmd.team_import", "Replace the roster from a JSON or CSV file after a preview (needs Manage Server)", "Het ledenbestand vervangen vanuit een JSON- of CSV-bestand na een voorbeeld (vereist Server beheren)"),
+    ("cmd.team_audit", "Browse the log of roster changes", "Het logboek van wijzigingen in het team bekijken"),
+    ("cmd.team_undo", "Undo the last roster change (needs Manage Server)", "De laatste wijziging in het team ongedaan maken (vereist Server beheren)"),
+    ("cmd.team_restore", "Restore an earlier version of the roster (needs Manage Server)", "Een eerdere versie van het team terugzetten (vereist Server beheren)"),
+    ("cmd.apply", "Apply to join the team", "Solliciteren om bij het team te komen"),
+    ("cmd.team_field", "Manage custom profile fields", "Eigen profielvelden beheren"),
+    ("cmd.team_field.define", "Add or change a custom field (needs Manage Server)", "Een eigen veld toevoegen of wijzigen (vereist Server beheren)"),
+    ("cmd.team_field.remove", "Remove a custom field (needs Manage Server)", "Een eigen veld verwijderen (vereist Server beheren)"),
+    ("cmd.team_field.set", "Set a member's value for a custom field", "De waarde van een eigen veld voor een lid instellen"),
+    ("cmd.team_field.list", "List the custom fields", "De eigen velden tonen"),
+    ("cmd.team_squad", "Manage squads within the team", "Squads binnen het team beheren"),
+    ("cmd.team_squad.create", "Create a squad (needs Manage Server)", "Een squad aanmaken (vereist Server beheren)"),
+    ("cmd.team_squad.remove", "Remove a squad; its members stay on the team (needs Manage Server)", "Een squad verwijderen; de leden blijven in het team (vereist Server beheren)"),
+    ("cmd.team_squad.assign", "Move a member into a squad, or out of theirs (needs Manage Server)", "Een lid in een squad plaatsen, of eruit halen (vereist Server beheren)"),
+    ("cmd.team_squad.lead", "Make a member a squad lead, or stop (needs Manage Server)", "Een lid squadleider maken, of niet meer (vereist Server beheren)"),
+    ("cmd.team_squad.list", "List the squads and their leads", "De squads en hun leiders tonen"),
+    ("opt.user", "Username or UUID", "Gebruikersnaam of UUID"),
+    ("opt.compare_player", "Player to compare", "Speler om te vergelijken"),
+    ("opt.stats_card", "Show a profile card image instead", "Toon in plaats daarvan een profielkaart als afbeelding"),
+    ("opt.leaderboard_type", "Leaderboard type", "Soort ranglijst"),
+    ("opt.page", "Page number (default 1)", "Paginanummer (standaard 1)"),
+    ("opt.search_examples", "Search for specific items (e.g. diamond, sword)", "Zoeken naar specifieke items (bijv. diamond, sword)"),
+    ("opt.search", "Search for specific items", "Zoeken naar specifieke items"),
+    ("opt.sort", "Sort order", "Sorteervolgorde"),
+    ("opt.ign", "In-game name", "In-game naam"),
+    ("opt.link_ign", "In-game name (omit to view current link)", "In-game naam (weglaten om je huidige koppeling te zien)"),
+    ("opt.goal_stat", "Stat to track", "Statistiek om te volgen"),
+    ("opt.goal_target", "Target, e.g. 10M, 2.5k or 500h for playtime", "Doel, bijv. 10M, 2.5k of 500h voor speeltijd"),
+    ("opt.numbers", "Number format", "Getalnotatie"),
+    ("opt.language", "Language for messages and units", "Taal voor berichten en eenheden"),
+    ("opt.server", "Set the server default instead (needs Manage Server)", "In plaats daarvan de serverstandaard instellen (vereist Server beheren)"),
+    ("opt.reset", "Forget your choices and use the server default", "Je keuzes vergeten en de serverstandaard gebruiken"),
+    ("opt.team_name", "New team name (omit to view current)", "Nieuwe teamnaam (weglaten om de huidige te zien)"),
+    ("opt.country", "Country", "Land"),
+    ("opt.skill", "Skills, comma separated", "Vaardigheden, gescheiden door komma's"),
+    ("opt.timezone", "Timezone, e.g. UTC+2 or Europe/Amsterdam", "Tijdzone, bijv. UTC+2 of Europe/Amsterdam"),
+    ("opt.joined", "Join date as YYYY-MM-DD (new members default to today)", "Datum van toetreden als JJJJ-MM-DD (standaard vandaag voor nieuwe leden)"),
+    ("opt.base", "In-game base or role note", "Notitie over de in-game basis of rol"),
+    ("opt.field_key", "Field name", "Veldnaam"),
+    ("opt.field_kind", "Kind of value", "Soort waarde"),
+    ("opt.field_label", "Label shown on profiles (defaults to the name)", "Label op profielen (standaard de naam)"),
+    ("opt.field_value", "Value (omit to clear)", "Waarde (weglaten om te wissen)"),
+    ("opt.rank", "Rank", "Rang"),
+    ("opt.audit_ign", "Only changes to this member", "Alleen wijzigingen aan dit lid"),
+    ("opt.days", "How many days back (default 30)", "Hoeveel dagen terug (standaard 30)"),
+    ("opt.version", "Version to restore", "Versie om terug te zetten"),
+    ("opt.squad", "Squad name", "Naam van de squad"),
+    ("opt.squad_name", "Name of the new squad", "Naam van de nieuwe squad"),
+    ("opt.layout", "How to lay out the list", "Hoe de lijst wordt weergegeven"),
+    ("opt.squad_filter", "Only show this squad", "Alleen deze squad tonen"),
+    ("opt.squad_assign", "Squad to move them into (omit to take them out)", "Squad om ze in te plaatsen (weglaten om ze eruit te halen)"),
+    ("opt.lead_remove", "Stop them being a lead instead", "Ze juist geen leider meer maken"),
+    ("opt.format", "File format (default JSON)", "Bestandsformaat (standaard JSON)"),
+    ("opt.import_file", "A .json or .csv file, e.g. from /team-export", "Een .json- of .csv-bestand, bijv. van /team-export"),
+    ("opt.about", "About", "Over"),
+    ("opt.discord", "Discord tag (e.g. Name#1234)", "Discord-tag (bijv. Naam#1234)"),
+    // Shared
+    ("common.error", "Error", "Fout"),
+    ("common.error_detail", "Error: {}", "Fout: {}"),
+    ("common.unknown", "Unknown", "Onbekend"),
+    ("common.none", "None", "Geen"),
+    ("common.no_data", "No data", "Geen gegevens"),
+    ("common.page", "Page {}", "Pagina {}"),
+    ("common.more", "*... and {} more*", "*... en nog {}*"),
+    ("common.unknown_command", "❌ Unknown command", "❌ Onbekend commando"),
+    ("common.help_footer", "💡 [brackets] for optional parameters, <brackets> for required parameters", "💡 [haakjes] voor optionele parameters, <haakjes> voor verplichte parameters"),
+    // API responses
+    ("api.status", "📊 Status", "📊 Status"),
+    ("api.message", "💬 Message", "💬 Bericht"),
+    ("api.unexpected", "❌ Unexpected response format", "❌ Onverwacht antwoordformaat"),
+    ("api.offline", "🔴 Player is offline", "🔴 Speler is offline"),
+    ("api.error_title", "DonutSMP API Error", "DonutSMP API-fout"),
+    ("api.unauthorized", "❌ **Authentication Error**: Invalid API key", "❌ **Authenticatiefout**: Ongeldige API-sleutel"),
+    ("api.forbidden", "❌ **Access Forbidden**: You don't have permission to access this endpoint", "❌ **Geen toegang**: Je hebt geen toestemming voor dit endpoint"),
+    ("api.not_found", "❌ **Not Found**: The requested resource doesn't exist", "❌ **Niet gevonden**: De opgevraagde gegevens bestaan niet"),
+    ("api.rate_limited", "❌ **Rate Limited**: Too many requests, please try again later", "❌ **Te veel verzoeken**: Probeer het later opnieuw"),
+    ("api.server_error", "❌ **Server Error**: The server encountered an error", "❌ **Serverfout**: De server gaf een fout"),
+    ("api.http_error", "❌ **Error**: API returned HTTP {}", "❌ **Fout**: API gaf HTTP {} terug"),
+    ("api.error_footer", "Status: {} | Path: {}", "Status: {} | Pad: {}"),
+    ("api.error_status", "❌ API Error: {}", "❌ API-fout: {}"),
+    ("api.parse_failed", "❌ Failed to parse response: {}", "❌ Antwoord kon niet worden gelezen: {}"),
+    // Lookup and stats
+    ("lookup.title", "🔍 Player Lookup: {}", "🔍 Speler opzoeken: {}"),
+    ("lookup.username", "Username", "Gebruikersnaam"),
+    ("lookup.location", "Location", "Locatie"),
+    ("lookup.rank", "Rank", "Rang"),
+    ("lookup.empty", "No lookup data for {}", "Geen gegevens gevonden voor {}"),
+    ("stats.title", "📊 Player Stats: {}", "📊 Spelersstatistieken: {}"),
+    ("stats.money", "Money", "Geld"),
+    ("stats.shards", "Shards", "Shards"),
+    ("stats.money_made", "Money made", "Geld verdiend"),
+    ("stats.money_spent", "Money spent", "Geld uitgegeven"),
+    ("stats.playtime", "Playtime", "Speeltijd"),
+    ("stats.kills", "Kills", "Kills"),
+    ("stats.deaths", "Deaths", "Doden"),
+    ("stats.mobs_killed", "Mobs killed", "Mobs gedood"),
+    ("stats.blocks_placed", "Blocks placed", "Blokken geplaatst"),
+    ("stats.blocks_broken", "Blocks broken", "Blokken gebroken"),
+    ("stats.kd", "K/D", "K/D"),
+    ("stats.money_per_hour", "Money per hour", "Geld per uur"),
+    ("stats.blocks_per_hour", "Blocks per hour", "Blokken per uur"),
+    ("stats.mobs_per_hour", "Mob kills per hour", "Mobs gedood per uur"),
+    ("stats.shards_per_hour", "Shards per hour", "Shards per uur"),
+    ("stats.net_economy", "Net economy", "Netto economie"),
+    ("stats.derived", "📐 Derived", "📐 Berekend"),
+    ("stats.top_percent", "top {}%", "top {}%"),
+    ("stats.bottom_percent", "bottom {}%", "onderste {}%"),
+    ("stats.percentile_note", "Percentiles compare with players seen on leaderboards and in lookups", "Percentielen vergelijken met spelers uit ranglijsten en opzoekingen"),
+    ("compare.title", "⚖️ Player comparison", "⚖️ Spelers vergelijken"),
+    ("compare.legend", "`*` leads the row", "`*` is de beste van de rij"),
+    ("compare.missing", "No stats found for: {}", "Geen statistieken gevonden voor: {}"),
+    ("compare.not_enough", "Need stats for at least two players to compare.", "Er zijn statistieken van minstens twee spelers nodig om te vergelijken."),
+    ("stats.empty", "No stats recorded for this player yet.", "Nog geen statistieken voor deze speler."),
+    ("stats.not_found_title", "❌ Stats not found for {}", "❌ Geen statistieken gevonden voor {}"),
+    ("stats.not_found", "Could not find stats for this player.", "Kon geen statistieken vinden voor deze speler."),
+    // Leaderboards
+    ("leaderboard.title", "{} {} Leaderboard (Page {})", "{} Ranglijst {} (pagina {})"),
+    ("leaderboard.footer", "Page {} • Showing {} entries", "Pagina {} • {} items getoond"),
+    ("leaderboard.empty", "No leaderboard entries found on this page.", "Geen ranglijstitems gevonden op deze pagina."),
+    ("leaderboard.not_found_title", "❌ {} Leaderboard not found", "❌ Ranglijst {} niet gevonden"),
+    ("leaderboard.not_found", "Could not load leaderboard data.", "Kon de ranglijst niet laden."),
+    ("leaderboard.money", "Money", "Geld"),
+    ("leaderboard.kills", "Kills", "Kills"),
+    ("leaderboard.deaths", "Deaths", "Doden"),
+    ("leaderboard.brokenblocks", "Blocks Broken", "Gebroken blokken"),
+    ("leaderboard.placedblocks", "Blocks Placed", "Geplaatste blokken"),
+    ("leaderboard.mobskilled", "Mobs Killed", "Gedode mobs"),
+    ("leaderboard.playtime", "Playtime", "Speeltijd"),
+    ("leaderboard.sell", "Money from Selling", "Geld uit verkopen"),
+    ("leaderboard.shards", "Shards", "Shards"),
+    ("leaderboard.shop", "Money Spent", "Geld uitgegeven"),
+    // Auction house
+    ("auction.title", "🏪 Auction House (Page {})", "🏪 Veilinghuis (pagina {})"),
+    ("auction.transactions_title", "📜 Auction Transactions (Page {})", "📜 Veilingtransacties (pagina {})"),
+    ("auction.search", "🔍 Search: '{}'", "🔍 Zoeken: '{}'"),
+    ("auction.sort", "📊 Sort: {}", "📊 Sortering: {}"),
+    ("auction.seller", "Seller: {}", "Verkoper: {}"),
+    ("auction.each", "{} each", "{} per stuk"),
+    ("auction.unknown_time", "Unknown time", "Onbekend tijdstip"),
+    ("auction.empty", "No auction entries found on this page.", "Geen veilingaanbiedingen gevonden op deze pagina."),
+    ("auction.transactions_empty", "No auction transactions found on this page.", "Geen veilingtransacties gevonden op deze pagina."),
+    ("sort.lowest_price", "💰 Lowest Price", "💰 Laagste prijs"),
+    ("sort.highest_price", "💸 Highest Price", "💸 Hoogste prijs"),
+    ("sort.recently_listed", "🕒 Recently Listed", "🕒 Recent aangeboden"),
+    ("sort.last_listed", "📅 Last Listed", "📅 Laatst aangeboden"),
+    // Trader profiles
+    ("trader.title", "🧾 Trader: {}", "🧾 Handelaar: {}"),
+    ("trader.no_listings", "No active listings found", "Geen actieve aanbiedingen gevonden"),
+    ("trader.listings", "🏪 Active Listings ({})", "🏪 Actieve aanbiedingen ({})"),
+    ("trader.sales", "📈 Recent Sales ({})", "📈 Recente verkopen ({})"),
+    ("trader.purchases", "🛒 Recent Purchases ({})", "🛒 Recente aankopen ({})"),
+    ("trader.none_recorded", "None recorded", "Niets vastgelegd"),
+    ("trader.sold_to", "to {}", "aan {}"),
+    ("trader.bought_from", "from {}", "van {}"),
+    ("trader.summary", "📊 Summary", "📊 Overzicht"),
+    ("trader.summary_body", "Total volume: **{}**\nAverage sale price: **{}**", "Totaal volume: **{}**\nGemiddelde verkoopprijs: **{}**"),
+    ("trader.most_traded", "🔥 Most Traded Items", "🔥 Meest verhandelde items"),
+    ("trader.partners", "🔗 Trading Partners", "🔗 Handelspartners"),
+    ("trader.partners_sold", "Sold to: {}", "Verkocht aan: {}"),
+    ("trader.partners_bought", "Bought from: {}", "Gekocht van: {}"),
+    ("trader.footer", "Sales and purchases come from transactions ingested by the bot", "Verkopen en aankopen komen uit de transacties die de bot heeft verzameld"),
+    ("trader.load_failed", "❌ Failed to load trading activity: {}", "❌ Handelsactiviteit kon niet worden geladen: {}"),
+    // Market digest
+    ("digest.daily", "📰 Daily Market Digest", "📰 Dagelijks marktoverzicht"),
+    ("digest.weekly", "🗞️ Weekly Market Digest", "🗞️ Wekelijks marktoverzicht"),
+    ("digest.empty", "No auction transactions were recorded in this period.", "Er zijn in deze periode geen veilingtransacties vastgelegd."),
+    ("digest.summary", "**{}** sales worth **{}**", "**{}** verkopen ter waarde van **{}**"),
+    ("digest.top_items", "📦 Top Items by Volume", "📦 Topitems op volume"),
+    ("digest.top_item", "**{}**. {} - **{}** ({} sales)", "**{}**. {} - **{}** ({} verkopen)"),
+    ("digest.movers", "💹 Biggest Price Movers", "💹 Grootste prijsbewegingen"),
+    ("digest.mover", "{} {} - {} → **{}** each ({})", "{} {} - {} → **{}** per stuk ({})"),
+    ("digest.expensive", "💎 Most Expensive Sales", "💎 Duurste verkopen"),
+    ("digest.sellers", "🏪 Most Active Sellers", "🏪 Actiefste verkopers"),
+    ("digest.seller", "**{}**. {} - {} sales ({})", "**{}**. {} - {} verkopen ({})"),
+    ("digest.team", "👥 Team Activity", "👥 Teamactiviteit"),
+    ("digest.team_member", "{} - {} sold ({}), {} bought", "{} - {} verkocht ({}), {} gekocht"),
+    ("digest.footer", "Based on transactions ingested by the bot", "Gebaseerd op de transacties die de bot heeft verzameld"),
+    // Channel alerts
+    ("snipe.title", "🎯 Underpriced: {}", "🎯 Onder de prijs: {}"),
+    ("snipe.price", "Price", "Prijs"),
+    ("snipe.median", "Recent median", "Recente mediaan"),
+    ("snipe.median_value", "{} each\n└ *{} sales*", "{} per stuk\n└ *{} verkopen*"),
+    ("snipe.margin", "Expected margin", "Verwachte marge"),
+    ("snipe.margin_value", "{} ({}% below median)", "{} ({}% onder de mediaan)"),
+    ("undercut.mention", "{} you've been undercut!", "{} je bent onderboden!"),
+    ("undercut.title", "📉 Undercut: {}", "📉 Onderboden: {}"),
+    ("undercut.yours", "Your listing", "Jouw aanbieding"),
+    ("undercut.theirs", "Competing listing", "Concurrerende aanbieding"),
+    ("undercut.by", "Undercut by", "Onderboden met"),
+    ("undercut.by_value", "{} each ({}%)", "{} per stuk ({}%)"),
+    ("listing.price", "{} ({} each)", "{} ({} per stuk)"),
+    // Account links and settings
+    ("link.title", "🔗 Account Link", "🔗 Accountkoppeling"),
+    ("link.linked", "Linked your account to **{}**", "Je account is gekoppeld aan **{}**"),
+    ("link.failed", "❌ Failed to save link: {}", "❌ Koppeling kon niet worden opgeslagen: {}"),
+    ("link.current", "Your account is linked to **{}**", "Je account is gekoppeld aan **{}**"),
+    ("link.none", "Your account is not linked yet. Use `/link <ign>`.", "Je account is nog niet gekoppeld. Gebruik `/link <ign>`."),
+    ("goal.list_title", "🎯 Goals of {}", "🎯 Doelen van {}"),
+    ("goal.none", "No goals yet. Set one with `/goal set`.", "Nog geen doelen. Stel er een in met `/goal set`."),
+    ("goal.no_progress", "Target {} · no value yet", "Doel {} · nog geen waarde"),
+    ("goal.stats_unavailable", "Stats couldn't be fetched right now", "Statistieken konden nu niet worden opgehaald"),
+    ("goal.not_linked", "❌ Goals follow your linked account. Use `/link <ign>` first.", "❌ Doelen volgen je gekoppelde account. Gebruik eerst `/link <ign>`."),
+    ("goal.unknown_stat", "❌ Pick one of the listed stats.", "❌ Kies een van de getoonde statistieken."),
+    ("goal.invalid_target", "❌ That target isn't a number I understand. Try something like `{}`.", "❌ Dat doel is geen getal dat ik begrijp. Probeer iets als `{}`."),
+    ("goal.already_reached", "🎉 You're already past {} {}, so pick a higher target.", "🎉 Je bent al voorbij {} {}, kies dus een hoger doel."),
+    ("goal.saved", "✅ Goal set: {} {}", "✅ Doel ingesteld: {} {}"),
+    ("goal.removed", "🗑️ Dropped your {} goal.", "🗑️ Je doel voor {} is weggehaald."),
+    ("goal.not_set", "❌ You have no {} goal.", "❌ Je hebt geen doel voor {}."),
+    ("goal.save_failed", "❌ Failed to save goals: {}", "❌ Doelen konden niet worden opgeslagen: {}"),
+    ("goal.reached_title", "🎯 Goal reached!", "🎯 Doel gehaald!"),
+    ("goal.reached", "{} (**{}**) reached their goal of {} {} **{}**!", "{} (**{}**) heeft het doel {} {} **{}** gehaald!"),
+    ("goal.reached_footer", "Now at {} · goal set on {}", "Nu op {} · doel gesteld op {}"),
+    ("goal.milestone_title", "🏅 Milestone", "🏅 Mijlpaal"),
+    ("goal.milestone", "**{}** passed {} **{}** {}!", "**{}** is voorbij {} **{}** {}!"),
+    ("settings.title", "⚙️ Settings", "⚙️ Instellingen"),
+    ("settings.needs_manage", "❌ Changing the server default needs the Manage Server permission.", "❌ Voor het wijzigen van de serverstandaard is de machtiging Server beheren nodig."),
+    ("settings.reset", "Your settings were reset to the server default.", "Je instellingen zijn teruggezet naar de serverstandaard."),
+    ("settings.failed", "❌ Failed to save settings: {}", "❌ Instellingen konden niet worden opgeslagen: {}"),
+    ("settings.server_updated", "Server default updated.", "Serverstandaard bijgewerkt."),
+    ("settings.updated", "Your settings were updated.", "Je instellingen zijn bijgewerkt."),
+    ("settings.hint", "Pick `numbers` and/or `language` to change how values are shown.", "Kies `numbers` en/of `language` om te wijzigen hoe waarden worden getoond."),
+    ("settings.server_heading", "Server default", "Serverstandaard"),
+    ("settings.user_heading", "Your format", "Jouw notatie"),
+    ("settings.summary", "Numbers: {} ({})\nLanguage: {}\nExample: {} • {}", "Getallen: {} ({})\nTaal: {}\nVoorbeeld: {} • {}"),
+    // Help
+    ("help.title", "🤖 DonutSMP Bot Commands", "🤖 DonutSMP-botcommando's"),
+    ("help.description", "Here are all available commands:", "Dit zijn alle beschikbare commando's:"),
+    ("help.player", "**👤 Player Commands**", "**👤 Spelercommando's**"),
+    (
+        "help.player_body",
+        "`/lookup <user>` - Get player info\n`/stats <user>` - Show player statistics\n`/compare <ign1> <ign2> [ign3] [ign4]` - Compare players side by side\n`/link [ign]` - Link your Discord account to your IGN\n`/goal set|remove|list` - Set stat goals and see your progress\n`/settings [numbers] [language]` - Choose your language and number format",
+        "`/lookup <user>` - Spelerinfo opvragen\n`/stats <user>` - Spelersstatistieken tonen\n`/compare <ign1> <ign2> [ign3] [ign4]` - Spelers naast elkaar vergelijken\n`/link [ign]` - Je Discord-account koppelen aan je IGN\n`/goal set|remove|list` - Doelen stellen en je voortgang bekijken\n`/settings [numbers] [language]` - Je taal en getalnotatie kiezen",
+    ),
+    ("help.leaderboard", "**🏆 Leaderboard Commands**", "**🏆 Ranglijstcommando's**"),
+    ("help.leaderboard_body", "`/leaderboard <type> [page]` - Show various leaderboards", "`/leaderboard <type> [page]` - Verschillende ranglijsten tonen"),
+    ("help.auction", "**🏪 Auction Commands**", "**🏪 Veilingcommando's**"),
+    (
+        "help.auction_body",
+        "`/auction [page] [search] [sort]` - Browse auction house\n`/auction-transactions [page] [search] [sort]` - View transaction history\n`/trader <ign>` - Show a player's trading activity",
+        "`/auction [page] [search] [sort]` - Door het veilinghuis bladeren\n`/auction-transactions [page] [search] [sort]` - Transactiegeschiedenis bekijken\n`/trader <ign>` - Handelsactiviteit van een speler tonen",
+    ),
+    ("help.team", "**👥 Team Commands**", "**👥 Teamcommando's**"),
+    ("help.team_body", "`/team-help` - Show team commands and usage\n`/apply` - Apply to join the team", "`/team-help` - Teamcommando's en gebruik tonen\n`/apply` - Solliciteren om bij het team te komen"),
+    ("help.other", "**ℹ️ Other Commands**", "**ℹ️ Overige commando's**"),
+    ("help.other_body", "`/help` - Show this help message", "`/help` - Dit helpbericht tonen"),
+    ("team_help.title", "👥 Team Commands", "👥 Teamcommando's"),
+    ("team_help.description", "Manage your team stored by the bot:", "Beheer het team dat de bot bijhoudt:"),
+    ("team_help.commands", "Commands", "Commando's"),
+    (
+        "team_help.body",
+        "`/team-name [name]` - View or set the team name\n`/team-add [ign] [country] [skill] [rank] [about] [discord] [timezone] [joined] [base]` - Add or update a member (a form asks for anything missing)\n`/team-edit <ign> [country] [skill] [rank] [about] [discord] [timezone] [joined] [base]` - Change some of a member's details (`-` clears one, no fields opens a form)\n`/team-remove <ign>` - Remove a member by IGN\n`/team-list [squad] [layout]` - Show members grouped by rank, compact, detailed or only who's online\n`/team-countries` - Show members per country\n`/team-export [format]` - Download the roster as JSON or CSV\n`/team-import <file>` - Replace the roster from a file, after a preview\n`/team-audit [ign] [days]` - Show who changed the roster and when\n`/team-undo` - Undo the last roster change\n`/team-restore <version>` - Restore an earlier version of the roster, after a preview\n`/team-profile <ign>` - Show a member's profile\n`/team-field define|remove|set|list` - Manage custom profile fields\n`/team-squad create|remove|assign|lead|list` - Manage squads and their leads\n`/online [squad]` - Check who is online in your team\n`/team-help` - Show this team help",
+        "`/team-name [name]` - De teamnaam bekijken of instellen\n`/team-add [ign] [country] [skill] [rank] [about] [discord] [timezone] [joined] [base]` - Een lid toevoegen of bijwerken (een formulier vraagt naar wat ontbreekt)\n`/team-edit <ign> [country] [skill] [rank] [about] [discord] [timezone] [joined] [base]` - Enkele gegevens van een lid wijzigen (`-` wist er een, zonder velden opent een formulier)\n`/team-remove <ign>` - Een lid verwijderen op IGN\n`/team-list [squad] [layout]` - Leden tonen, gegroepeerd op rang, compact, uitgebreid of alleen wie online is\n`/team-countries` - Leden per land tonen\n`/team-export [format]` - Het ledenbestand downloaden als JSON of CSV\n`/team-import <file>` - Het ledenbestand vervangen vanuit een bestand, na een voorbeeld\n`/team-audit [ign] [days]` - Tonen wie het team wanneer heeft gewijzigd\n`/team-undo` - De laatste wijziging in het team ongedaan maken\n`/team-restore <version>` - Een eerdere versie van het team terugzetten, na een voorbeeld\n`/team-profile <ign>` - Het profiel van een lid tonen\n`/team-field define|remove|set|list` - Eigen profielvelden beheren\n`/team-squad create|remove|assign|lead|list` - Squads en hun leiders beheren\n`/online [squad]` - Bekijken wie er in je team online is\n`/team-help` - Deze teamhulp tonen",
+    ),
+    // Team
+    ("rank.owner", "Owner", "Eigenaar"),
+    ("rank.admin", "Admin", "Beheerder"),
+    ("rank.member", "Member", "Lid"),
+    ("team.no_members", "No members yet. Use /team-add to add someone.", "Nog geen leden. Gebruik /team-add om iemand toe te voegen."),
+    ("team.member_country", "Country: {}", "Land: {}"),
+    ("team.member_skills", "Skills: {}", "Vaardigheden: {}"),
+    ("team.member_discord", "Discord: {}", "Discord: {}"),
+    ("team.member_about", "About: {}", "Over: {}"),
+    ("team.member_timezone", "Time zone: {}", "Tijdzone: {}"),
+    ("team.member_base", "Base: {}", "Basis: {}"),
+    ("team.member_joined", "Joined: {}", "Lid sinds: {}"),
+    ("team.nobody_online", "Nobody is online right now.", "Er is nu niemand online."),
+    ("team.member_squad", "Squad: {}", "Squad: {}"),
+    ("team.last_updated", "Last updated: {} (UTC)", "Laatst bijgewerkt: {} (UTC)"),
+    ("card.online_count", "{} of {} online", "{} van {} online"),
+    ("card.player", "Player", "Speler"),
+    ("card.location", "Location", "Locatie"),
+    ("card.online", "online", "online"),
+    ("card.offline", "offline", "offline"),
+    ("card.unknown", "unknown", "onbekend"),
+    ("card.no_members", "No members yet.", "Nog geen leden."),
+    ("card.no_squad", "No squad", "Geen squad"),
+    ("card.stats_subtitle", "DonutSMP player stats", "DonutSMP-spelerstatistieken"),
+    ("team.name_updated_title", "👥 Team Name Updated", "👥 Teamnaam bijgewerkt"),
+    ("team.name_set", "Team name set to: **{}**", "Teamnaam ingesteld op: **{}**"),
+    ("team.name_title", "👥 Team Name", "👥 Teamnaam"),
+    ("team.name_current", "Current team name: **{}**", "Huidige teamnaam: **{}**"),
+    ("team.save_failed", "❌ Failed to save: {}", "❌ Opslaan mislukt: {}"),
+    ("team.member_saved_title", "✅ Team Member Saved", "✅ Teamlid opgeslagen"),
+    ("team.member_added", "{}{} has been added to **{}** as {}.", "{}{} is toegevoegd aan **{}** als {}."),
+    ("team.member_updated", "{}{} has been updated to **{}** as {}.", "{}{} is bijgewerkt in **{}** als {}."),
+    ("team.member_save_failed", "❌ Failed to save member: {}", "❌ Teamlid kon niet worden opgeslagen: {}"),
+    ("team.removal_title", "🗑️ Team Member Removal", "🗑️ Teamlid verwijderen"),
+    ("team.removed", "Removed {} from {}", "{} verwijderd uit {}"),
+    ("team.not_found", "{} not found in {}", "{} niet gevonden in {}"),
+    ("team.remove_failed", "❌ Failed to remove member: {}", "❌ Teamlid kon niet worden verwijderd: {}"),
+    ("team.not_a_member", "❌ **{}** is not on the team.", "❌ **{}** zit niet in het team."),
+    ("team.member_edited", "✏️ Updated **{}**.", "✏️ **{}** bijgewerkt."),
+    ("team.edit_title", "✏️ Edit {}", "✏️ {} bewerken"),
+    ("team.edit_button", "✏️ Edit", "✏️ Bewerken"),
+    ("team.add_title", "➕ Add Team Member", "➕ Teamlid toevoegen"),
+    ("team.rank_placeholder", "Pick a rank", "Kies een rang"),
+    ("team.unknown_player", "❌ **{}** isn't a DonutSMP player.", "❌ **{}** is geen DonutSMP-speler."),
+    ("team.player_check_failed", "❌ Couldn't reach the DonutSMP API to check **{}**. Try again later.", "❌ De DonutSMP-API was niet bereikbaar om **{}** te controleren. Probeer het later opnieuw."),
+    ("team.unknown_country", "❌ **{}** isn't a country I know. Use its name or a two-letter code like NL.", "❌ **{}** is geen land dat ik ken. Gebruik de naam of een code van twee letters zoals NL."),
+    ("team.country_suggestions", "❌ **{}** isn't a country I know. Did you mean {}?", "❌ **{}** is geen land dat ik ken. Bedoelde je {}?"),
+    ("team.invalid_joined", "❌ **{}** is not a date; use YYYY-MM-DD.", "❌ **{}** is geen datum; gebruik JJJJ-MM-DD."),
+    ("export.done", "📤 **{}** — {} members as {}.", "📤 **{}** — {} leden als {}."),
+    ("import.needs_manage", "❌ Importing a roster needs the Manage Server permission.", "❌ Voor het importeren van een ledenbestand is de machtiging Server beheren nodig."),
+    ("import.too_large", "❌ **{}** is larger than 1 MB.", "❌ **{}** is groter dan 1 MB."),
+    ("import.unknown_format", "❌ **{}** isn't a .json or .csv file.", "❌ **{}** is geen .json- of .csv-bestand."),
+    ("import.download_failed", "❌ Couldn't download the file: {}", "❌ Het bestand kon niet worden gedownload: {}"),
+    ("import.invalid", "❌ **{}** has problems, so nothing was changed:", "❌ **{}** bevat fouten, dus er is niets gewijzigd:"),
+    ("import.more_errors", "…and {} more.", "…en nog {}."),
+    ("import.invalid_json", "The file isn't valid JSON: {}", "Het bestand is geen geldige JSON: {}"),
+    ("import.empty", "The file has no members.", "Het bestand bevat geen leden."),
+    ("import.unknown_column", "Unknown column `{}`.", "Onbekende kolom `{}`."),
+    ("import.row_error", "Row {}: {}", "Rij {}: {}"),
+    ("import.missing_ign", "every row needs an IGN.", "elke rij heeft een IGN nodig."),
+    ("import.duplicate_ign", "**{}** appears more than once.", "**{}** komt meer dan eens voor."),
+    ("import.invalid_rank", "rank must be owner, admin or member, not **{}**.", "rang moet owner, admin of member zijn, niet **{}**."),
+    ("import.unknown_squad", "there is no squad **{}**. Create it with `/team-squad create` first.", "er is geen squad **{}**. Maak die eerst aan met `/team-squad create`."),
+    ("import.preview_title", "📥 Import Preview", "📥 Voorbeeld import"),
+    ("import.summary", "{} added · {} updated · {} removed", "{} toegevoegd · {} bijgewerkt · {} verwijderd"),
+    ("import.added", "➕ Added", "➕ Toegevoegd"),
+    ("import.updated", "✏️ Updated", "✏️ Bijgewerkt"),
+    ("import.removed", "🗑️ Removed", "🗑️ Verwijderd"),
+    ("import.no_changes", "The file matches the current roster; there's nothing to import.", "Het bestand komt overeen met het huidige ledenbestand; er is niets te importeren."),
+    ("import.footer", "Nothing changes until you press Apply · expires in {} minutes", "Er verandert niets tot je op Toepassen drukt · verloopt over {} minuten"),
+    ("import.confirm", "✅ Apply", "✅ Toepassen"),
+    ("import.cancel", "Cancel", "Annuleren"),
+    ("import.applied", "✅ Imported the roster: {} added, {} updated, {} removed.", "✅ Ledenbestand geïmporteerd: {} toegevoegd, {} bijgewerkt, {} verwijderd."),
+    ("import.cancelled", "Import cancelled; nothing was changed.", "Import geannuleerd; er is niets gewijzigd."),
+    ("import.expired", "❌ This import expired or was already handled. Run `/team-import` again.", "❌ Deze import is verlopen of al afgehandeld. Voer `/team-import` opnieuw uit."),
+    ("import.not_yours", "❌ Only the person who started this import can apply or cancel it.", "❌ Alleen wie deze import startte, kan hem toepassen of annuleren."),
+    ("import.stale", "❌ The roster changed since this preview. Run `/team-import` again.", "❌ Het ledenbestand is gewijzigd sinds dit voorbeeld. Voer `/team-import` opnieuw uit."),
+    ("import.save_failed", "❌ Failed to save the import: {}", "❌ De import kon niet worden opgeslagen: {}"),
+    ("audit.title", "📜 Roster Audit Log", "📜 Logboek teamwijzigingen"),
+    ("audit.title_member", "📜 Audit Log — {}", "📜 Logboek — {}"),
+    ("audit.empty", "No roster changes in the last {} days.", "Geen wijzigingen in het team in de afgelopen {} dagen."),
+    ("audit.footer", "{} changes · last {} days", "{} wijzigingen · afgelopen {} dagen"),
+    ("audit.footer_limited", "Latest {} of {} changes · last {} days", "Laatste {} van {} wijzigingen · afgelopen {} dagen"),
+    ("audit.member_added", "➕ Added **{}**", "➕ **{}** toegevoegd"),
+    ("audit.member_removed", "🗑️ Removed **{}**", "🗑️ **{}** verwijderd"),
+    ("audit.member_updated", "✏️ Updated **{}**", "✏️ **{}** bijgewerkt"),
+    ("audit.renamed", "🏷️ Renamed the team from **{}** to **{}**", "🏷️ Team hernoemd van **{}** naar **{}**"),
+    ("audit.field_added", "🧩 Added the field **{}** ({})", "🧩 Veld **{}** ({}) toegevoegd"),
+    ("audit.field_changed", "🧩 Changed the field **{}** ({}) to **{}** ({})", "🧩 Veld **{}** ({}) gewijzigd in **{}** ({})"),
+    ("audit.field_removed", "🧩 Removed the field **{}**", "🧩 Veld **{}** verwijderd"),
+    ("audit.squad_added", "🛡️ Created the squad **{}**", "🛡️ Squad **{}** aangemaakt"),
+    ("audit.squad_removed", "🛡️ Removed the squad **{}**", "🛡️ Squad **{}** verwijderd"),
+    ("audit.squad_leads", "🛡️ Changed the leads of **{}**: {} → {}", "🛡️ Leiders van **{}** gewijzigd: {} → {}"),
+    ("history.needs_manage", "❌ Undoing and restoring roster changes needs the Manage Server permission.", "❌ Voor het ongedaan maken en terugzetten van wijzigingen is de machtiging Server beheren nodig."),
+    ("history.version_choice", "v{} · {} · {} members", "v{} · {} · {} leden"),
+    ("undo.nothing", "❌ There's no roster change to undo.", "❌ Er is geen wijziging in het team om ongedaan te maken."),
+    ("undo.title", "↩️ Last Change Undone", "↩️ Laatste wijziging ongedaan gemaakt"),
+    ("undo.intro", "Reverted the change made on {} by <@{}>:", "De wijziging van {} door <@{}> is teruggedraaid:"),
+    ("undo.footer", "Back to version {}", "Terug naar versie {}"),
+    ("restore.title", "⏪ Restore Version {}", "⏪ Versie {} terugzetten"),
+    ("restore.intro", "The roster as it was on {}, before a change by <@{}>. Restoring it would:", "Het team zoals het was op {}, vóór een wijziging door <@{}>. Terugzetten zou:"),
+    ("restore.footer", "Nothing changes until you press Restore", "Er verandert niets tot je op Terugzetten drukt"),
+    ("restore.confirm", "⏪ Restore", "⏪ Terugzetten"),
+    ("restore.unknown_version", "❌ There's no version {}. Pick one from the list.", "❌ Er is geen versie {}. Kies er een uit de lijst."),
+    ("restore.no_changes", "Version {} matches the roster as it is now.", "Versie {} komt overeen met het team zoals het nu is."),
+    ("restore.done", "✅ Restored version {}. Use `/team-undo` to go back.", "✅ Versie {} teruggezet. Gebruik `/team-undo` om terug te gaan."),
+    ("restore.cancelled", "Restore cancelled; nothing was changed.", "Terugzetten geannuleerd; er is niets gewijzigd."),
+    ("restore.stale", "❌ The roster changed since this preview. Run `/team-restore` again.", "❌ Het team is gewijzigd sinds dit voorbeeld. Voer `/team-restore` opnieuw uit."),
+    ("restore.not_yours", "❌ Only the person who asked for this restore can confirm or cancel it.", "❌ Alleen wie om dit terugzetten vroeg, kan het bevestigen of annuleren."),
+    ("application.modal_title", "📝 Apply to the Team", "📝 Solliciteren bij het team"),
+    ("application.about_label", "Why do you want to join?", "Waarom wil je erbij komen?"),
+    ("application.title", "📝 Application #{} — {}", "📝 Sollicitatie #{} — {}"),
+    ("application.applicant", "Applicant", "Sollicitant"),
+    ("application.submitted_at", "Submitted", "Ingediend"),
+    ("application.no_stats", "Stats are unavailable right now.", "Statistieken zijn nu niet beschikbaar."),
+    ("application.pending", "Waiting for review · expires {}", "Wacht op beoordeling · verloopt {}"),
+    ("application.accepted_by", "Accepted by <@{}>", "Aangenomen door <@{}>"),
+    ("application.rejected_by", "Rejected by <@{}>", "Afgewezen door <@{}>"),
+    ("application.expired", "Expired before anyone reviewed it", "Verlopen voordat iemand ernaar keek"),
+    ("application.state.pending", "pending", "in behandeling"),
+    ("application.state.accepted", "accepted", "aangenomen"),
+    ("application.state.rejected", "rejected", "afgewezen"),
+    ("application.state.expired", "expired", "verlopen"),
+    ("application.accept", "✅ Accept", "✅ Aannemen"),
+    ("application.reject", "❌ Reject", "❌ Afwijzen"),
+    ("application.refresh", "🔄 Refresh stats", "🔄 Statistieken vernieuwen"),
+    ("application.closed", "❌ Applications are closed right now.", "❌ Sollicitaties zijn op dit moment gesloten."),
+    ("application.already_pending", "❌ Your application #{} is still waiting for review.", "❌ Je sollicitatie #{} wacht nog op beoordeling."),
+    ("application.already_member", "❌ **{}** is already on the team.", "❌ **{}** zit al in het team."),
+    ("application.submitted", "✅ Application #{} sent! You'll get a DM once it's reviewed, or after {} days if nobody does.", "✅ Sollicitatie #{} verstuurd! Je krijgt een DM zodra ernaar gekeken is, of na {} dagen als niemand dat doet."),
+    ("application.post_failed", "❌ Couldn't post your application for review: {}", "❌ Je sollicitatie kon niet ter beoordeling worden geplaatst: {}"),
+    ("application.needs_manage", "❌ Accepting and rejecting applications needs the Manage Server permission.", "❌ Voor het aannemen en afwijzen van sollicitaties is de machtiging Server beheren nodig."),
+    ("application.already_decided", "❌ This application is already {}.", "❌ Deze sollicitatie is al {}."),
+    ("application.accepted_dm", "✅ Welcome to **{}**! Your application as {} was accepted.", "✅ Welkom bij **{}**! Je sollicitatie als {} is aangenomen."),
+    ("application.rejected_dm", "Your application to **{}** as {} wasn't accepted this time.", "Je sollicitatie bij **{}** als {} is deze keer niet aangenomen."),
+    ("application.expired_dm", "⌛ Your application as {} expired before anyone reviewed it. Feel free to `/apply` again.", "⌛ Je sollicitatie als {} is verlopen voordat iemand ernaar keek. Je kunt opnieuw `/apply` gebruiken."),
+    ("countries.title", "🌍 {} — Countries", "🌍 {} — Landen"),
+    ("countries.footer", "{} countries · {} members", "{} landen · {} leden"),
+    // Profiles and custom fields
+    ("profile.online", "🟢 Online", "🟢 Online"),
+    ("profile.online_at", "🟢 Online — {}", "🟢 Online — {}"),
+    ("profile.offline", "🔴 Offline", "🔴 Offline"),
+    ("profile.unknown", "⚪ Status unknown", "⚪ Status onbekend"),
+    ("profile.ign", "IGN", "IGN"),
+    ("profile.rank", "Rank", "Rang"),
+    ("profile.country", "Country", "Land"),
+    ("profile.timezone", "Timezone", "Tijdzone"),
+    ("profile.local_time", "{} (now {})", "{} (nu {})"),
+    ("profile.joined", "Joined", "Lid sinds"),
+    ("profile.discord", "Discord", "Discord"),
+    ("profile.skills", "Skills", "Vaardigheden"),
+    ("profile.base", "Base", "Basis"),
+    ("profile.squad", "Squad", "Squad"),
+    ("profile.squad_lead", "{} ⭐ lead", "{} ⭐ leider"),
+    ("profile.about", "About", "Over"),
+    ("profile.custom", "Custom fields", "Eigen velden"),
+    ("profile.stats", "📊 Stats", "📊 Statistieken"),
+    ("field.kind.text", "Text", "Tekst"),
+    ("field.kind.number", "Number", "Getal"),
+    ("field.kind.date", "Date", "Datum"),
+    ("field.kind.link", "Link", "Link"),
+    ("field.kind.toggle", "Yes/No", "Ja/nee"),
+    ("field.yes", "Yes", "Ja"),
+    ("field.no", "No", "Nee"),
+    ("field.title", "🏷️ Custom Fields", "🏷️ Eigen velden"),
+    ("field.needs_manage", "❌ Defining fields needs the Manage Server permission.", "❌ Voor het beheren van velden is de machtiging Server beheren nodig."),
+    ("field.defined", "Added the field **{}** ({}).", "Veld **{}** ({}) toegevoegd."),
+    ("field.redefined", "Updated the field **{}** ({}).", "Veld **{}** ({}) bijgewerkt."),
+    ("field.removed", "Removed the field **{}** and its values.", "Veld **{}** en de waarden ervan verwijderd."),
+    ("field.unknown", "❌ There is no field **{}**. See `/team-field list`.", "❌ Er is geen veld **{}**. Zie `/team-field list`."),
+    ("field.set", "**{}** for {} is now {}.", "**{}** voor {} is nu {}."),
+    ("field.cleared", "Cleared **{}** for {}.", "**{}** voor {} gewist."),
+    ("field.none", "No custom fields yet. Admins can add one with `/team-field define`.", "Nog geen eigen velden. Beheerders kunnen er een toevoegen met `/team-field define`."),
+    ("field.list_entry", "`{}` - {} ({})", "`{}` - {} ({})"),
+    ("squad.title", "🛡️ Squads", "🛡️ Squads"),
+    ("squad.needs_manage", "❌ Creating and removing squads, moving members and choosing leads needs the Manage Server permission.", "❌ Voor het beheren van squads, hun leden en leiders is de machtiging Server beheren nodig."),
+    ("squad.unknown", "❌ There is no squad **{}**. See `/team-squad list`.", "❌ Er is geen squad **{}**. Zie `/team-squad list`."),
+    ("squad.invalid_name", "❌ A squad name needs 1 to {} characters.", "❌ Een squadnaam moet 1 tot {} tekens hebben."),
+    ("squad.exists", "❌ There already is a squad **{}**.", "❌ Er is al een squad **{}**."),
+    ("squad.created", "✅ Created the squad **{}**. Add members with `/team-squad assign`.", "✅ Squad **{}** aangemaakt. Voeg leden toe met `/team-squad assign`."),
+    ("squad.removed", "🗑️ Removed the squad **{}**. Its members are still on the team.", "🗑️ Squad **{}** verwijderd. De leden zitten nog in het team."),
+    ("squad.assigned", "✅ **{}** is now in **{}**.", "✅ **{}** zit nu in **{}**."),
+    ("squad.unassigned", "✅ **{}** is no longer in a squad.", "✅ **{}** zit niet meer in een squad."),
+    ("squad.lead_added", "⭐ **{}** now leads **{}**.", "⭐ **{}** leidt nu **{}**."),
+    ("squad.lead_removed", "**{}** no longer leads **{}**.", "**{}** leidt **{}** niet meer."),
+    ("squad.none", "No squads yet. Admins can add one with `/team-squad create`.", "Nog geen squads. Beheerders kunnen er een toevoegen met `/team-squad create`."),
+    ("squad.list_entry", "**{}** · {} members · leads: {}", "**{}** · {} leden · leiders: {}"),
+    ("squad.no_squad", "👤 No squad", "👤 Geen squad"),
+    ("field.invalid_number", "❌ **{}** is not a number.", "❌ **{}** is geen getal."),
+    ("field.invalid_date", "❌ **{}** is not a date; use YYYY-MM-DD.", "❌ **{}** is geen datum; gebruik JJJJ-MM-DD."),
+    ("field.invalid_link", "❌ **{}** is not a link starting with https://.", "❌ **{}** is geen link die begint met https://."),
+    ("field.invalid_toggle", "❌ **{}** is not yes or no.", "❌ **{}** is geen ja of nee."),
+    ("field.save_failed", "❌ Failed to save field: {}", "❌ Veld kon niet worden opgeslagen: {}"),
+];
+
+/// The translation of `key`, if the catalogue has one.
+pub fn lookup(language: Language, key: &str) -> Option<&'static str> {
+    MESSAGES
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, en, nl)| match language {
+            Language::En => *en,
+            Language::Nl => *nl,
+        })
+}
+
+/// The translation of `key`, falling back to English and then to the key itself
+/// so a missing entry shows up in the embed instead of an empty string.
+pub fn text(language: Language, key: &str) -> &str {
+    lookup(language, key)
+        .filter(|s| !s.is_empty())
+        .or_else(|| lookup(Language::En, key))
+        .unwrap_or(key)
+}
+
+/// Replaces each `{}` in `template` with the next argument.
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            out.push_str(&arg.to_string());
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// Sets a command or option description from the catalogue, with the
+/// translations in Discord's `description_localizations`.
+pub trait Describe {
+    fn describe(&mut self, key: &str) -> &mut Self;
+}
+
+impl Describe for CreateApplicationCommand {
+    fn describe(&mut self, key: &str) -> &mut Self {
+        self.description(text(Language::En, key));
+        if let Some(nl) = lookup(Language::Nl, key) {
+            self.description_localized(Language::Nl.discord_locale(), nl);
+        }
+        self
+    }
+}
+
+impl Describe for CreateApplicationCommandOption {
+    fn describe(&mut self, key: &str) -> &mut Self {
+        self.description(text(Language::En, key));
+        if let Some(nl) = lookup(Language::Nl, key) {
+            self.description_localized(Language::Nl.discord_locale(), nl);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn keys_are_unique() {
+        let mut seen = HashSet::new();
+        for (key, _, _) in MESSAGES {
+            assert!(seen.insert(*key), "duplicate key {}", key);
+        }
+    }
+
+    #[test]
+    fn translations_keep_their_placeholders() {
+        for (key, en, nl) in MESSAGES {
+            assert!(!en.is_empty() && !nl.is_empty(), "empty text for {}", key);
+            assert_eq!(
+                en.matches("{}").count(),
+                nl.matches("{}").count(),
+                "placeholder count differs for {}",
+                key
+            );
+        }
+    }
+
+    #[test]
+    fn descriptions_fit_discord() {
+        for (key, en, nl) in MESSAGES {
+            if key.starts_with("cmd.") || key.starts_with("opt.") {
+                assert!(en.chars().count() <= 100, "{} is too long", key);
+                assert!(nl.chars().count() <= 100, "{} (nl) is too long", key);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_and_fallbacks() {
+        assert_eq!(fill("{} of {}", &[&1, &"two"]), "1 of two");
+        assert_eq!(text(Language::Nl, "common.page"), "Pagina {}");
+        assert_eq!(text(Language::Nl, "missing.key"), "missing.key");
+    }
+}
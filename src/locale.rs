@@ -0,0 +1,258 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How large numbers are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberStyle {
+    /// `1.234.567`
+    #[default]
+    Dot,
+    /// `1,234,567`
+    Comma,
+    /// `1 234 567`, grouped with non-breaking spaces so Discord never wraps inside a number
+    Space,
+    /// `1.2M`
+    Compact,
+}
+
+impl NumberStyle {
+    pub const ALL: [NumberStyle; 4] = [
+        NumberStyle::Dot,
+        NumberStyle::Comma,
+        NumberStyle::Space,
+        NumberStyle::Compact,
+    ];
+
+    pub fn parse(s: &str) -> Option<NumberStyle> {
+        NumberStyle::ALL
+            .into_iter()
+            .find(|style| style.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NumberStyle::Dot => "dot",
+            NumberStyle::Comma => "comma",
+            NumberStyle::Space => "space",
+            NumberStyle::Compact => "compact",
+        }
+    }
+
+    pub fn example(&self) -> &'static str {
+        match self {
+            NumberStyle::Dot => "1.234.567",
+            NumberStyle::Comma => "1,234,567",
+            NumberStyle::Space => "1 234 567",
+            NumberStyle::Compact => "1.2M",
+        }
+    }
+}
+
+/// Language for messages and units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    #[default]
+    En,
+    Nl,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::En, Language::Nl];
+
+    pub fn parse(s: &str) -> Option<Language> {
+        Language::ALL
+            .into_iter()
+            .find(|lang| lang.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::En => "en",
+            Language::Nl => "nl",
+        }
+    }
+
+    /// The language Discord reports for a client locale such as `en-US` or `nl`.
+    pub fn from_discord(locale: &str) -> Option<Language> {
+        let primary = locale.split('-').next().unwrap_or_default();
+        Language::ALL
+            .into_iter()
+            .find(|lang| lang.as_str().eq_ignore_ascii_case(primary))
+    }
+
+    /// Locale code used for Discord's localization fields.
+    pub fn discord_locale(&self) -> &'static str {
+        match self {
+            Language::En => "en-US",
+            Language::Nl => "nl",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::En => "English",
+            Language::Nl => "Nederlands",
+        }
+    }
+
+    /// Day, hour, minute and second suffixes.
+    fn duration_units(&self) -> [&'static str; 4] {
+        match self {
+            Language::En => ["d", "h", "m", "s"],
+            Language::Nl => ["d", "u", "m", "s"],
+        }
+    }
+}
+
+/// A reader's formatting preferences, applied by every formatter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Format {
+    pub numbers: NumberStyle,
+    pub language: Language,
+}
+
+impl Format {
+    pub fn number(&self, number: i64) -> String {
+        let separator = match self.numbers {
+            NumberStyle::Dot => ".",
+            NumberStyle::Comma => ",",
+            NumberStyle::Space => "\u{a0}",
+            NumberStyle::Compact => return compact(number),
+        };
+        let digits = number.unsigned_abs().to_string();
+        let groups: Vec<&str> = digits
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|group| std::str::from_utf8(group).unwrap_or_default())
+            .collect();
+        let sign = if number < 0 { "-" } else { "" };
+        format!("{}{}", sign, groups.join(separator))
+    }
+
+    /// `$1.234.567` in the reader's number style.
+    pub fn money(&self, amount: i64) -> String {
+        format!("${}", self.number(amount))
+    }
+
+    /// `3d 4h 12m`, `4h 12m`, `12m` or `40s` for a duration in milliseconds.
+    pub fn duration_ms(&self, ms: i64) -> String {
+        let [d, h, m, s] = self.language.duration_units();
+        let seconds = ms / 1000;
+        let minutes = seconds / 60;
+        let hours = minutes / 60;
+        let days = hours / 24;
+        if days > 0 {
+            format!("{}{} {}{} {}{}", days, d, hours % 24, h, minutes % 60, m)
+        } else if hours > 0 {
+            format!("{}{} {}{}", hours, h, minutes % 60, m)
+        } else if minutes > 0 {
+            format!("{}{}", minutes, m)
+        } else {
+            format!("{}{}", seconds, s)
+        }
+    }
+}
+
+impl Format {
+    /// The reader's translation of a catalogue message.
+    pub fn t<'a>(&self, key: &'a str) -> &'a str {
+        crate::i18n::text(self.language, key)
+    }
+
+    /// [`Format::t`] with its `{}` placeholders filled in order.
+    pub fn tf(&self, key: &str, args: &[&dyn Display]) -> String {
+        crate::i18n::fill(self.t(key), args)
+    }
+
+    /// A calendar date, day first for Dutch readers.
+    pub fn date(&self, date: NaiveDate) -> String {
+        match self.language {
+            Language::En => date.format("%Y-%m-%d").to_string(),
+            Language::Nl => date.format("%d-%m-%Y").to_string(),
+        }
+    }
+
+    /// Date and time in UTC, day first for Dutch readers.
+    pub fn datetime(&self, at: DateTime<Utc>) -> String {
+        match self.language {
+            Language::En => at.format("%Y-%m-%d %H:%M:%SZ").to_string(),
+            Language::Nl => at.format("%d-%m-%Y %H:%M:%SZ").to_string(),
+        }
+    }
+}
+
+/// `950`, `12.3K`, `1.2M`, `4B`: one decimal, dropped when it's zero.
+fn compact(number: i64) -> String {
+    const SUFFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "B"), (1e6, "M"), (1e3, "K")];
+    let value = number as f64;
+    if value.abs() < 1e3 {
+        return number.to_string();
+    }
+    for (size, suffix) in SUFFIXES {
+        // Compare after rounding so 999_950 becomes `1M` rather than `1000K`
+        if (value.abs() / size * 10.0).round() >= 10.0 {
+            let scaled = format!("{:.1}", value / size);
+            let scaled = scaled.strip_suffix(".0").unwrap_or(&scaled);
+            return format!("{}{}", scaled, suffix);
+        }
+    }
+    number.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with(numbers: NumberStyle) -> Format {
+        Format {
+            numbers,
+            ..Format::default()
+        }
+    }
+
+    #[test]
+    fn number_styles() {
+        assert_eq!(with(NumberStyle::Dot).number(1_234_567), "1.234.567");
+        assert_eq!(with(NumberStyle::Comma).number(1_234_567), "1,234,567");
+        assert_eq!(
+            with(NumberStyle::Space).number(1_234_567),
+            "1\u{a0}234\u{a0}567"
+        );
+        assert_eq!(with(NumberStyle::Compact).number(1_234_567), "1.2M");
+        assert_eq!(with(NumberStyle::Dot).number(-1_234_567), "-1.234.567");
+        assert_eq!(with(NumberStyle::Dot).number(999), "999");
+    }
+
+    #[test]
+    fn compact_rounding() {
+        let fmt = with(NumberStyle::Compact);
+        assert_eq!(fmt.number(950), "950");
+        assert_eq!(fmt.number(12_340), "12.3K");
+        assert_eq!(fmt.number(999_950), "1M");
+        assert_eq!(fmt.number(4_000_000_000), "4B");
+        assert_eq!(fmt.number(-2_500_000), "-2.5M");
+    }
+
+    #[test]
+    fn durations_use_language_units() {
+        let ms = ((26 * 60) + 5) * 60 * 1000;
+        assert_eq!(Format::default().duration_ms(ms), "1d 2h 5m");
+        let nl = Format {
+            language: Language::Nl,
+            ..Format::default()
+        };
+        assert_eq!(nl.duration_ms(ms), "1d 2u 5m");
+        assert_eq!(nl.duration_ms(40_000), "40s");
+    }
+
+    #[test]
+    fn discord_locales() {
+        assert_eq!(Language::from_discord("nl"), Some(Language::Nl));
+        assert_eq!(Language::from_discord("en-GB"), Some(Language::En));
+        assert_eq!(Language::from_discord("de"), None);
+    }
+}
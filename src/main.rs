@@ -1,10 +1,30 @@
 mod api;
+mod applications;
+mod auction;
+mod audit;
+mod card;
 mod commands;
 mod components;
 mod constants;
-mod formatters;
-mod response_formatters;
+mod countries;
+mod digest;
+mod flags;
+mod goals;
+mod history;
+mod i18n;
+mod locale;
+mod market;
+mod models;
+mod players;
+mod render;
+mod roster;
+mod settings;
+mod skins;
+mod snipe;
+mod status;
 mod team;
+mod trader;
+mod undercut;
 
 use dotenv::dotenv;
 use reqwest::Client;
@@ -13,6 +33,7 @@ use serenity::{
     client::ClientBuilder,
     model::{
         application::interaction::Interaction,
+        channel::AttachmentType,
         gateway::Ready,
         prelude::{ChannelId, MessageId},
     },
@@ -22,9 +43,10 @@ use std::env;
 use tracing::{error, info};
 
 use chrono::Utc;
-use commands::{handle_command, handle_component, register_all_commands};
-use serde_json::Value;
-use std::collections::{HashMap, HashSet};
+use commands::{
+    handle_autocomplete, handle_command, handle_component, handle_modal, register_all_commands,
+};
+use render::Render;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
@@ -34,7 +56,19 @@ struct Handler {
     donut_api_key: String,
     online_channel_id: Option<u64>,
     online_interval_minutes: u64,
-    last_online_message_id: Arc<Mutex<Option<MessageId>>>,
+    last_online_message_id: Arc<Mutex<Vec<MessageId>>>,
+    undercut_channel_id: Option<u64>,
+    undercut_interval_minutes: u64,
+    undercut_scan_pages: u32,
+    market_ingest_interval_minutes: u64,
+    market_ingest_pages: u32,
+    leaderboard_snapshot_interval_minutes: u64,
+    leaderboard_snapshot_pages: u32,
+    snipe_interval_minutes: u64,
+    snipe_scan_pages: u32,
+    digest_channel_id: Option<u64>,
+    digest_hour_utc: u32,
+    audit_channel_id: Option<u64>,
 }
 
 #[async_trait]
@@ -62,177 +96,148 @@ impl EventHandler for Handler {
             let interval = self.online_interval_minutes;
             tokio::spawn(async move {
                 loop {
-                    let mut team = crate::team::load();
-                    let mut online_set: HashSet<String> = HashSet::new();
-                    let mut location_map: HashMap<String, String> = HashMap::new();
-                    for m in &team.members {
-                        let path = format!("/v1/lookup/{}", m.ign.replace(" ", "%20"));
-                        let url = format!("https://api.donutsmp.net{}", path);
-                        let res = http_client
-                            .get(&url)
-                            .bearer_auth(&api_key)
-                            .timeout(Duration::from_secs(10))
-                            .send()
-                            .await;
-                        if let Ok(resp) = res {
-                            if resp.status().is_success() {
-                                let text = resp.text().await.unwrap_or_default();
-                                if let Ok(json) = serde_json::from_str::<Value>(&text) {
-                                    let loc = json
-                                        .get("result")
-                                        .and_then(|r| r.get("location"))
-                                        .and_then(|v| v.as_str())
-                                        .or_else(|| json.get("location").and_then(|v| v.as_str()));
-                                    if let Some(location) = loc {
-                                        location_map.insert(m.ign.clone(), location.to_string());
-                                    }
-                                }
-                                online_set.insert(m.ign.clone());
-                            }
-                        }
-                    }
-
-                    team.members
-                        .sort_by(|a, b| match a.rank.sort_key().cmp(&b.rank.sort_key()) {
-                            std::cmp::Ordering::Equal => {
-                                a.ign.to_ascii_lowercase().cmp(&b.ign.to_ascii_lowercase())
-                            }
-                            other => other,
-                        });
+                    let team = crate::team::load();
+                    let presence =
+                        crate::status::fetch_presences(&http_client, &api_key, &team).await;
                     let chan = ChannelId(channel_id);
 
-                    let prev_id_opt = {
-                        let guard = last_msg.lock().await;
-                        (*guard).clone()
+                    let prev_ids = {
+                        let mut guard = last_msg.lock().await;
+                        std::mem::take(&mut *guard)
                     };
-                    if let Some(prev_id) = prev_id_opt {
+                    for prev_id in prev_ids {
                         let _ = chan.delete_message(&ctx_clone.http, prev_id).await;
                     }
 
-                    match chan
-                        .send_message(&ctx_clone.http, |m| {
-                            m.embed(|e| {
-                                let mut e = e
-                                    .title(format!("👥 {}", team.name))
-                                    .color(crate::constants::EMBED_COLOR_ACCENT);
-                                if team.members.is_empty() {
-                                    e = e.description(
-                                        "No members yet. Use /team-add to add someone.",
-                                    );
-                                } else {
-                                    let mut owners: Vec<(String, String)> = Vec::new();
-                                    let mut admins: Vec<(String, String)> = Vec::new();
-                                    let mut members: Vec<(String, String)> = Vec::new();
-
-                                    for m in &team.members {
-                                        let flag = crate::team::country_flag(&m.country);
-                                        let country_display = if flag.is_empty() {
-                                            m.country.clone()
-                                        } else {
-                                            format!("{} ({})", m.country, flag)
-                                        };
-                                        let discord = if m.discord_tag.is_empty() {
-                                            "-".to_string()
-                                        } else {
-                                            m.discord_tag.clone()
-                                        };
-                                        let mut value = format!(
-                                            "Country: {}\nSkill: {}\nDiscord: {}",
-                                            country_display, m.skill, discord
-                                        );
-                                        if !m.about.is_empty() {
-                                            value.push_str(&format!("\nAbout: {}", m.about));
-                                        }
-                                        let online = online_set.contains(&m.ign);
-                                        let name = if online {
-                                            if let Some(loc) = location_map.get(&m.ign) {
-                                                format!("{}    [ 🟢 - {}]", m.ign, loc)
-                                            } else {
-                                                format!("{}    [ 🟢 ]", m.ign)
-                                            }
-                                        } else {
-                                            format!("{}    [ 🔴 ]", m.ign)
-                                        };
-                                        let entry = (name, value);
-                                        match m.rank {
-                                            crate::team::Rank::Owner => owners.push(entry),
-                                            crate::team::Rank::Admin => admins.push(entry),
-                                            crate::team::Rank::Member => members.push(entry),
-                                        }
-                                    }
-
-                                    if !owners.is_empty() {
-                                        let count = owners.len();
-                                        e = e.field(
-                                            format!("👑 Owner ({})", count),
-                                            crate::constants::ZWSP,
-                                            false,
-                                        );
-                                        for (name, val) in owners.iter() {
-                                            e = e.field(name.clone(), val.clone(), false);
-                                        }
-                                    }
-                                    if !owners.is_empty()
-                                        && (!admins.is_empty() || !members.is_empty())
-                                    {
-                                        e = e.field(
-                                            crate::constants::ZWSP,
-                                            crate::constants::ZWSP,
-                                            false,
-                                        );
-                                    }
-                                    if !admins.is_empty() {
-                                        let count = admins.len();
-                                        e = e.field(
-                                            format!("🛡️ Admin ({})", count),
-                                            crate::constants::ZWSP,
-                                            false,
-                                        );
-                                        for (name, val) in admins.iter() {
-                                            e = e.field(name.clone(), val.clone(), false);
-                                        }
-                                    }
-                                    if !admins.is_empty() && !members.is_empty() {
-                                        e = e.field(
-                                            crate::constants::ZWSP,
-                                            crate::constants::ZWSP,
-                                            false,
-                                        );
-                                    }
-                                    if !members.is_empty() {
-                                        let count = members.len();
-                                        e = e.field(
-                                            format!("👤 Member ({})", count),
-                                            crate::constants::ZWSP,
-                                            false,
-                                        );
-                                        for (name, val) in members.iter() {
-                                            e = e.field(name.clone(), val.clone(), false);
-                                        }
-                                    }
+                    let fmt = crate::settings::guild_format();
+                    let status = crate::status::TeamStatus {
+                        team,
+                        presence,
+                        config: crate::status::config(),
+                        at: Some(Utc::now()),
+                    };
+                    let mut posted = Vec::new();
+                    if status.config.image {
+                        let flags = crate::flags::for_team(&http_client, &status.team).await;
+                        match crate::card::team_status_card(&status, &flags, &fmt) {
+                            Ok(png) => {
+                                let file = AttachmentType::Bytes {
+                                    data: png.into(),
+                                    filename: "team_status.png".to_string(),
+                                };
+                                match chan
+                                    .send_message(&ctx_clone.http, |m| {
+                                        m.add_file(file).embed(|e| {
+                                            e.image("attachment://team_status.png")
+                                                .color(crate::constants::EMBED_COLOR_ACCENT)
+                                        })
+                                    })
+                                    .await
+                                {
+                                    Ok(msg) => posted.push(msg.id),
+                                    Err(e) => error!("Failed to post online status: {:?}", e),
                                 }
-                                e = e.footer(|f| {
-                                    let ts = Utc::now().format("%Y-%m-%d %H:%M:%SZ").to_string();
-                                    f.text(format!("Last updated: {} (UTC)", ts))
-                                });
-                                e
-                            })
-                        })
-                        .await
-                    {
-                        Ok(msg) => {
-                            let mut guard = last_msg.lock().await;
-                            *guard = Some(msg.id);
+                            }
+                            Err(e) => error!("Failed to draw team status card: {:?}", e),
                         }
-                        Err(e) => {
-                            error!("Failed to post online status: {:?}", e);
+                    } else {
+                        // One message per embed, since the 6000 character limit spans a whole message
+                        for embed in status.render(&fmt).to_embeds() {
+                            match chan
+                                .send_message(&ctx_clone.http, |m| m.set_embed(embed))
+                                .await
+                            {
+                                Ok(msg) => posted.push(msg.id),
+                                Err(e) => {
+                                    error!("Failed to post online status: {:?}", e);
+                                    break;
+                                }
+                            }
                         }
                     }
+                    *last_msg.lock().await = posted;
 
                     tokio::time::sleep(Duration::from_secs(interval.saturating_mul(60))).await;
                 }
             });
         }
+
+        // Spawn background task to alert team members whose listings got undercut
+        if let Some(channel_id) = self.undercut_channel_id {
+            tokio::spawn(crate::undercut::run(
+                ctx.clone(),
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                channel_id,
+                self.undercut_interval_minutes,
+                self.undercut_scan_pages,
+            ));
+        }
+
+        // Spawn background task to keep the local auction price history fresh
+        if self.market_ingest_interval_minutes > 0 {
+            tokio::spawn(crate::market::run_ingest(
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                self.market_ingest_interval_minutes,
+                self.market_ingest_pages,
+            ));
+        }
+
+        // Spawn background task to snapshot leaderboards for stat percentiles
+        if self.leaderboard_snapshot_interval_minutes > 0 {
+            tokio::spawn(crate::players::run_snapshots(
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                self.leaderboard_snapshot_interval_minutes,
+                self.leaderboard_snapshot_pages,
+            ));
+        }
+
+        // Spawn background task to flag underpriced new listings (opt-in)
+        if crate::snipe::config().is_some() {
+            tokio::spawn(crate::snipe::run(
+                ctx.clone(),
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                self.snipe_interval_minutes,
+                self.snipe_scan_pages,
+            ));
+        }
+
+        // Spawn background task to post daily/weekly market digests
+        if let Some(channel_id) = self.digest_channel_id {
+            tokio::spawn(crate::digest::run(
+                ctx.clone(),
+                channel_id,
+                self.digest_hour_utc,
+            ));
+        }
+
+        // Spawn background task to mirror roster changes to a moderation channel
+        if let Some(channel_id) = self.audit_channel_id {
+            tokio::spawn(crate::audit::run_mirror(ctx.clone(), channel_id));
+        }
+
+        // Spawn background task to expire applications nobody reviewed in time
+        if let Some(config) = crate::applications::config() {
+            tokio::spawn(crate::applications::run_expiry(
+                ctx.clone(),
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                config.expiry_days,
+            ));
+        }
+
+        // Spawn background task to celebrate reached goals and team milestones
+        if let Some(config) = crate::goals::config() {
+            tokio::spawn(crate::goals::run(
+                ctx.clone(),
+                self.http_client.clone(),
+                self.donut_api_key.clone(),
+                config,
+            ));
+        }
     }
 
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
@@ -242,10 +247,11 @@ impl EventHandler for Handler {
                     handle_command(&self.http_client, &self.donut_api_key, &ctx, &cmd).await
                 {
                     error!("Command handling error: {:?}", e);
+                    let fmt = crate::settings::format_for(cmd.user.id.0, &cmd.locale);
                     if let Err(e2) = cmd
                         .create_interaction_response(&ctx.http, |r| {
                             r.interaction_response_data(|d| {
-                                d.content(format!("Error: {}", e)).ephemeral(true)
+                                d.content(fmt.tf("common.error_detail", &[&e])).ephemeral(true)
                             })
                         })
                         .await
@@ -259,10 +265,29 @@ impl EventHandler for Handler {
                     handle_component(&self.http_client, &self.donut_api_key, &ctx, &component).await
                 {
                     error!("Component handling error: {:?}", e);
+                    let fmt = crate::settings::format_for(component.user.id.0, &component.locale);
                     if let Err(e2) = component
                         .create_interaction_response(&ctx.http, |r| {
                             r.interaction_response_data(|d| {
-                                d.content(format!("Error: {}", e)).ephemeral(true)
+                                d.content(fmt.tf("common.error_detail", &[&e])).ephemeral(true)
+                            })
+                        })
+                        .await
+                    {
+                        error!("Failed to send error response: {:?}", e2);
+                    }
+                }
+            }
+            Interaction::ModalSubmit(modal) => {
+                if let Err(e) =
+                    handle_modal(&self.http_client, &self.donut_api_key, &ctx, &modal).await
+                {
+                    error!("Modal handling error: {:?}", e);
+                    let fmt = crate::settings::format_for(modal.user.id.0, &modal.locale);
+                    if let Err(e2) = modal
+                        .create_interaction_response(&ctx.http, |r| {
+                            r.interaction_response_data(|d| {
+                                d.content(fmt.tf("common.error_detail", &[&e])).ephemeral(true)
                             })
                         })
                         .await
@@ -271,6 +296,11 @@ impl EventHandler for Handler {
                     }
                 }
             }
+            Interaction::Autocomplete(autocomplete) => {
+                if let Err(e) = handle_autocomplete(&ctx, &autocomplete).await {
+                    error!("Autocomplete handling error: {:?}", e);
+                }
+            }
             _ => {}
         }
     }
@@ -290,6 +320,51 @@ async fn main() {
         .ok()
         .and_then(|s| s.parse::<u64>().ok())
         .unwrap_or(10);
+    let undercut_channel_id = env::var("UNDERCUT_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok());
+    let undercut_interval_minutes = env::var("UNDERCUT_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(15);
+    let undercut_scan_pages = env::var("UNDERCUT_SCAN_PAGES")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(10);
+    let market_ingest_interval_minutes = env::var("MARKET_INGEST_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(10);
+    let market_ingest_pages = env::var("MARKET_INGEST_PAGES")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(5);
+    let leaderboard_snapshot_interval_minutes = env::var("LEADERBOARD_SNAPSHOT_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(360);
+    let leaderboard_snapshot_pages = env::var("LEADERBOARD_SNAPSHOT_PAGES")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(3);
+    let snipe_interval_minutes = env::var("SNIPE_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(5);
+    let snipe_scan_pages = env::var("SNIPE_SCAN_PAGES")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(2);
+    let digest_channel_id = env::var("DIGEST_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok());
+    let digest_hour_utc = env::var("DIGEST_HOUR_UTC")
+        .ok()
+        .and_then(|s| s.parse::<u32>().ok())
+        .unwrap_or(9);
+    let audit_channel_id = env::var("AUDIT_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok());
 
     let http_client = Client::builder()
         .user_agent("donutsmp-rs-bot/0.1")
@@ -301,7 +376,19 @@ async fn main() {
         donut_api_key: donut_api_key.clone(),
         online_channel_id,
         online_interval_minutes,
-        last_online_message_id: Arc::new(Mutex::new(None)),
+        last_online_message_id: Arc::new(Mutex::new(Vec::new())),
+        undercut_channel_id,
+        undercut_interval_minutes,
+        undercut_scan_pages,
+        market_ingest_interval_minutes,
+        market_ingest_pages,
+        leaderboard_snapshot_interval_minutes,
+        leaderboard_snapshot_pages,
+        snipe_interval_minutes,
+        snipe_scan_pages,
+        digest_channel_id,
+        digest_hour_utc,
+        audit_channel_id,
     };
 
     let intents = GatewayIntents::GUILDS;
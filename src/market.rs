@@ -0,0 +1,257 @@
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    fs,
+    io::{self},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+use tracing::warn;
+
+use crate::auction::Sale;
+
+/// One ingested auction transaction, reduced to what price history needs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaleRecord {
+    pub item_key: String,
+    pub item_id: String,
+    /// Item name with enchants, without the stack count.
+    #[serde(default)]
+    pub name: String,
+    pub label: String,
+    pub count: i64,
+    pub price: i64,
+    pub seller: String,
+    #[serde(default)]
+    pub buyer: Option<String>,
+    /// Unix seconds; the ingestion time when the API didn't send one.
+    pub sold_at: i64,
+    /// Whether `sold_at` is the ingestion time rather than the API's.
+    #[serde(default)]
+    pub ingested_at: bool,
+}
+
+impl SaleRecord {
+    pub fn unit_price(&self) -> f64 {
+        self.price as f64 / self.count.max(1) as f64
+    }
+
+    /// Name for per-item summaries; falls back to the item ID for old records.
+    pub fn item_name(&self) -> String {
+        if self.name.is_empty() {
+            self.item_id.replace("minecraft:", "").replace("_", " ")
+        } else {
+            self.name.clone()
+        }
+    }
+
+    /// Includes the API's `sold_at`, so repeat sales stay apart. An
+    /// ingestion time is left out, or the same sale would be stored again on
+    /// every run.
+    fn dedup_key(&self) -> String {
+        let sold_at = if self.ingested_at {
+            String::new()
+        } else {
+            self.sold_at.to_string()
+        };
+        format!(
+            "{}|{}|{}|{}|{}",
+            self.item_key,
+            self.price,
+            self.seller.to_ascii_lowercase(),
+            self.buyer
+                .as_deref()
+                .unwrap_or_default()
+                .to_ascii_lowercase(),
+            sold_at
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MarketData {
+    #[serde(default)]
+    pub sales: Vec<SaleRecord>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("MARKET_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("market_data.json")
+}
+
+/// How long ingested sales are kept (`MARKET_HISTORY_DAYS`, default 30).
+fn history_days() -> i64 {
+    std::env::var("MARKET_HISTORY_DAYS")
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(30)
+}
+
+/// Held around every read and write of the history, so the background
+/// ingest, `/auction-transactions` and `/trader` can't drop each other's sales.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+pub fn load() -> MarketData {
+    let _guard = lock();
+    read()
+}
+
+/// Loads, changes and saves the history in one go under the lock.
+fn update(change: impl FnOnce(&mut MarketData)) -> io::Result<()> {
+    let _guard = lock();
+    let mut data = read();
+    change(&mut data);
+    write(&data)
+}
+
+fn read() -> MarketData {
+    let path = store_path();
+    if !path.exists() {
+        return MarketData::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => MarketData::default(),
+    }
+}
+
+fn write(data: &MarketData) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string(data).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// Adds sales to the local price history, skipping ones already ingested.
+/// Returns how many new sales were stored.
+pub fn ingest(sales: &[Sale]) -> io::Result<usize> {
+    let now = Utc::now().timestamp();
+    let mut added = 0;
+    update(|data| {
+        let mut known: HashSet<String> = data.sales.iter().map(|s| s.dedup_key()).collect();
+        for sale in sales {
+            let record = SaleRecord {
+                item_key: sale.listing.item_key(),
+                item_id: sale.listing.item_id.clone(),
+                name: sale.listing.name(),
+                label: sale.listing.label(),
+                count: sale.listing.count,
+                price: sale.listing.price,
+                seller: sale.listing.seller.clone(),
+                buyer: sale.buyer.clone(),
+                sold_at: sale.sold_at.unwrap_or(now),
+                ingested_at: sale.sold_at.is_none(),
+            };
+            if known.insert(record.dedup_key()) {
+                data.sales.push(record);
+                added += 1;
+            }
+        }
+        let cutoff = now - history_days() * 86_400;
+        data.sales.retain(|s| s.sold_at >= cutoff);
+    })?;
+    Ok(added)
+}
+
+/// Median of a set of prices; `None` when empty.
+pub fn median(mut prices: Vec<f64>) -> Option<f64> {
+    if prices.is_empty() {
+        return None;
+    }
+    prices.sort_by(|a, b| a.total_cmp(b));
+    let mid = prices.len() / 2;
+    if prices.len() % 2 == 1 {
+        Some(prices[mid])
+    } else {
+        Some((prices[mid - 1] + prices[mid]) / 2.0)
+    }
+}
+
+/// Median per-unit price of an item since `since` (Unix seconds), with the sample size.
+pub fn median_unit_price(data: &MarketData, item_key: &str, since: i64) -> Option<(f64, usize)> {
+    let prices: Vec<f64> = data
+        .sales
+        .iter()
+        .filter(|s| s.item_key == item_key && s.sold_at >= since)
+        .map(|s| s.unit_price())
+        .collect();
+    let samples = prices.len();
+    median(prices).map(|m| (m, samples))
+}
+
+/// Periodically pulls the newest transaction pages into the local price history.
+pub async fn run_ingest(http_client: Client, api_key: String, interval_minutes: u64, pages: u32) {
+    loop {
+        for page in 1..=pages {
+            let path = format!("/v1/auction/transactions/{}", page);
+            match crate::api::fetch_auction_json(&http_client, &api_key, &path, None, None).await {
+                Ok(json) => {
+                    let sales = crate::auction::parse_sales(&json);
+                    if sales.is_empty() {
+                        break;
+                    }
+                    if let Err(e) = ingest(&sales) {
+                        warn!("Failed to store market history: {:?}", e);
+                    }
+                }
+                Err(e) => {
+                    warn!("Market ingest failed on page {}: {:?}", page, e);
+                    break;
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval_minutes.saturating_mul(60))).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedup_ignores_only_ingestion_times() {
+        let sale = |sold_at, ingested_at, buyer: &str| SaleRecord {
+            item_key: "minecraft:diamond".into(),
+            item_id: "minecraft:diamond".into(),
+            name: "Diamond".into(),
+            label: "Diamond".into(),
+            count: 1,
+            price: 500,
+            seller: "Steve".into(),
+            buyer: Some(buyer.into()),
+            sold_at,
+            ingested_at,
+        };
+        assert_eq!(
+            sale(100, true, "Alex").dedup_key(),
+            sale(200, true, "alex").dedup_key()
+        );
+        assert_eq!(
+            sale(100, false, "Alex").dedup_key(),
+            sale(100, false, "alex").dedup_key()
+        );
+        // The same trade again later is a second sale
+        assert_ne!(
+            sale(100, false, "Alex").dedup_key(),
+            sale(200, false, "Alex").dedup_key()
+        );
+        assert_ne!(
+            sale(100, true, "Alex").dedup_key(),
+            sale(100, true, "Notch").dedup_key()
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::auction::{Listing, Sale};
+
+/// Reads a number the API sends either as a JSON number or as a numeric string.
+pub fn api_number(value: &Value) -> Option<i64> {
+    value
+        .as_i64()
+        .or_else(|| value.as_str().and_then(|s| s.trim().parse::<i64>().ok()))
+        .or_else(|| value.as_f64().map(|f| f as i64))
+}
+
+/// The `status`/`message` pair the API returns instead of a `result`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ApiMessage {
+    pub status: Option<String>,
+    pub message: Option<String>,
+}
+
+impl ApiMessage {
+    pub fn from_json(json: &Value) -> ApiMessage {
+        ApiMessage {
+            status: json.get("status").map(|s| match s.as_str() {
+                Some(text) => text.to_string(),
+                None => s.to_string(),
+            }),
+            message: json
+                .get("message")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+        }
+    }
+}
+
+/// `/v1/lookup/{user}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerLookup {
+    pub query: String,
+    pub username: Option<String>,
+    pub location: Option<String>,
+    pub rank: Option<String>,
+}
+
+impl PlayerLookup {
+    pub fn from_json(json: &Value, query: &str) -> Option<PlayerLookup> {
+        let result = json.get("result")?.as_object()?;
+        if result.is_empty() {
+            return None;
+        }
+        let text = |key: &str| {
+            result
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string())
+        };
+        Some(PlayerLookup {
+            query: query.to_string(),
+            username: text("username"),
+            location: text("location"),
+            rank: text("rank"),
+        })
+    }
+}
+
+/// Whether a player is on the server, as far as `/v1/lookup/{user}` can tell.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Presence {
+    Online { location: Option<String> },
+    /// The lookup endpoint answers 500 for offline players.
+    Offline,
+    /// The API couldn't be reached or answered with another error.
+    Unknown,
+}
+
+/// `/v1/stats/{user}`; counters arrive as strings, playtime in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PlayerStats {
+    pub player: String,
+    pub money: Option<i64>,
+    pub shards: Option<i64>,
+    pub money_made_from_sell: Option<i64>,
+    pub money_spent_on_shop: Option<i64>,
+    pub playtime_ms: Option<i64>,
+    pub kills: Option<i64>,
+    pub deaths: Option<i64>,
+    pub mobs_killed: Option<i64>,
+    pub placed_blocks: Option<i64>,
+    pub broken_blocks: Option<i64>,
+}
+
+impl PlayerStats {
+    pub fn from_json(json: &Value, player: &str) -> Option<PlayerStats> {
+        let stats = json.get("result")?;
+        if !stats.is_object() {
+            return None;
+        }
+        let number = |key: &str| stats.get(key).and_then(api_number);
+        Some(PlayerStats {
+            player: player.to_string(),
+            money: number("money"),
+            shards: number("shards"),
+            money_made_from_sell: number("money_made_from_sell"),
+            money_spent_on_shop: number("money_spent_on_shop"),
+            playtime_ms: number("playtime"),
+            kills: number("kills"),
+            deaths: number("deaths"),
+            mobs_killed: number("mobs_killed"),
+            placed_blocks: number("placed_blocks"),
+            broken_blocks: number("broken_blocks"),
+        })
+    }
+
+    /// Kills per death; with no deaths, just the kills.
+    pub fn kd_ratio(&self) -> Option<f64> {
+        let kills = self.kills? as f64;
+        match self.deaths? {
+            0 => Some(kills),
+            deaths => Some(kills / deaths as f64),
+        }
+    }
+
+    fn playtime_hours(&self) -> Option<f64> {
+        self.playtime_ms
+            .filter(|&ms| ms > 0)
+            .map(|ms| ms as f64 / 3_600_000.0)
+    }
+
+    /// Money per hour played.
+    pub fn money_per_hour(&self) -> Option<f64> {
+        Some(self.money? as f64 / self.playtime_hours()?)
+    }
+
+    /// Blocks placed and broken per hour played.
+    pub fn blocks_per_hour(&self) -> Option<f64> {
+        if self.placed_blocks.is_none() && self.broken_blocks.is_none() {
+            return None;
+        }
+        let blocks = self.placed_blocks.unwrap_or(0) + self.broken_blocks.unwrap_or(0);
+        Some(blocks as f64 / self.playtime_hours()?)
+    }
+
+    /// Mobs killed per hour played.
+    pub fn mobs_per_hour(&self) -> Option<f64> {
+        Some(self.mobs_killed? as f64 / self.playtime_hours()?)
+    }
+
+    /// Shards earned per hour played.
+    pub fn shards_per_hour(&self) -> Option<f64> {
+        Some(self.shards? as f64 / self.playtime_hours()?)
+    }
+
+    /// Money made from selling minus money spent on the shop.
+    pub fn net_economy(&self) -> Option<i64> {
+        Some(self.money_made_from_sell? - self.money_spent_on_shop?)
+    }
+
+    /// The stats under their leaderboard names, as in [`LEADERBOARD_KINDS`].
+    pub fn leaderboard_values(&self) -> [(&'static str, Option<i64>); 10] {
+        [
+            ("money", self.money),
+            ("kills", self.kills),
+            ("deaths", self.deaths),
+            ("brokenblocks", self.broken_blocks),
+            ("placedblocks", self.placed_blocks),
+            ("mobskilled", self.mobs_killed),
+            ("playtime", self.playtime_ms),
+            ("sell", self.money_made_from_sell),
+            ("shards", self.shards),
+            ("shop", self.money_spent_on_shop),
+        ]
+    }
+
+    /// Stats pieced together from leaderboard values, as kept for seen players.
+    pub fn from_leaderboard_values(player: &str, values: &BTreeMap<String, i64>) -> PlayerStats {
+        let get = |kind: &str| values.get(kind).copied();
+        PlayerStats {
+            player: player.to_string(),
+            money: get("money"),
+            shards: get("shards"),
+            money_made_from_sell: get("sell"),
+            money_spent_on_shop: get("shop"),
+            playtime_ms: get("playtime"),
+            kills: get("kills"),
+            deaths: get("deaths"),
+            mobs_killed: get("mobskilled"),
+            placed_blocks: get("placedblocks"),
+            broken_blocks: get("brokenblocks"),
+        }
+    }
+}
+
+/// A stat `/stats` works out from the counters, ranked against other players.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Derived {
+    KdRatio,
+    MobsPerHour,
+    BlocksPerHour,
+    NetEconomy,
+    ShardsPerHour,
+}
+
+impl Derived {
+    pub const ALL: [Derived; 5] = [
+        Derived::KdRatio,
+        Derived::MobsPerHour,
+        Derived::BlocksPerHour,
+        Derived::NetEconomy,
+        Derived::ShardsPerHour,
+    ];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Derived::KdRatio => "stats.kd",
+            Derived::MobsPerHour => "stats.mobs_per_hour",
+            Derived::BlocksPerHour => "stats.blocks_per_hour",
+            Derived::NetEconomy => "stats.net_economy",
+            Derived::ShardsPerHour => "stats.shards_per_hour",
+        }
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self {
+            Derived::KdRatio => "🎯",
+            Derived::MobsPerHour => "🐗",
+            Derived::BlocksPerHour => "🧱",
+            Derived::NetEconomy => "💹",
+            Derived::ShardsPerHour => "💎",
+        }
+    }
+
+    pub fn value(&self, stats: &PlayerStats) -> Option<f64> {
+        match self {
+            Derived::KdRatio => stats.kd_ratio(),
+            Derived::MobsPerHour => stats.mobs_per_hour(),
+            Derived::BlocksPerHour => stats.blocks_per_hour(),
+            Derived::NetEconomy => stats.net_economy().map(|v| v as f64),
+            Derived::ShardsPerHour => stats.shards_per_hour(),
+        }
+    }
+}
+
+/// `/stats` output: the counters, what follows from them and how that ranks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatsView {
+    pub stats: PlayerStats,
+    /// Percentile among players the bot has seen, for derived stats with
+    /// enough players to compare against.
+    pub percentiles: Vec<(Derived, f64)>,
+}
+
+/// Players side by side for `/compare`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Comparison {
+    pub players: Vec<PlayerStats>,
+    /// Names the API had no stats for.
+    pub missing: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub username: String,
+    /// `None` when the API value isn't numeric; `raw` is shown instead.
+    pub value: Option<i64>,
+    pub raw: String,
+}
+
+/// `/v1/leaderboards/{type}/{page}`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leaderboard {
+    pub kind: String,
+    pub page: u32,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// Entries per leaderboard page as served by the API.
+pub const LEADERBOARD_PAGE_SIZE: u32 = 20;
+
+/// Every leaderboard the API serves, by the name used in its path.
+pub const LEADERBOARD_KINDS: [&str; 10] = [
+    "money",
+    "kills",
+    "deaths",
+    "brokenblocks",
+    "placedblocks",
+    "mobskilled",
+    "playtime",
+    "sell",
+    "shards",
+    "shop",
+];
+
+impl Leaderboard {
+    pub fn from_json(json: &Value, kind: &str, page: u32) -> Option<Leaderboard> {
+        let entries = json.get("result")?.as_array()?;
+        Some(Leaderboard {
+            kind: kind.to_string(),
+            page,
+            entries: entries
+                .iter()
+                .map(|entry| {
+                    let value = entry.get("value");
+                    LeaderboardEntry {
+                        username: entry
+                            .get("username")
+                            .and_then(|v| v.as_str())
+                            .unwrap_or("Unknown")
+                            .to_string(),
+                        value: value.and_then(api_number),
+                        raw: match value {
+                            Some(Value::String(s)) => s.clone(),
+                            Some(v) => v.to_string(),
+                            None => "0".to_string(),
+                        },
+                    }
+                })
+                .collect(),
+        })
+    }
+
+    pub fn emoji(&self) -> &'static str {
+        match self.kind.as_str() {
+            "money" => "💰",
+            "kills" => "⚔️",
+            "deaths" => "💀",
+            "brokenblocks" => "⛏️",
+            "placedblocks" => "🧱",
+            "mobskilled" => "👹",
+            "playtime" => "⏰",
+            "sell" => "💰",
+            "shards" => "💎",
+            "shop" => "🛒",
+            _ => "🏆",
+        }
+    }
+
+    /// Catalogue key for the leaderboard's name, see [`crate::i18n`].
+    pub fn name_key(&self) -> &'static str {
+        match self.kind.as_str() {
+            "money" => "leaderboard.money",
+            "kills" => "leaderboard.kills",
+            "deaths" => "leaderboard.deaths",
+            "brokenblocks" => "leaderboard.brokenblocks",
+            "placedblocks" => "leaderboard.placedblocks",
+            "mobskilled" => "leaderboard.mobskilled",
+            "playtime" => "leaderboard.playtime",
+            "sell" => "leaderboard.sell",
+            "shards" => "leaderboard.shards",
+            "shop" => "leaderboard.shop",
+            _ => "common.unknown",
+        }
+    }
+}
+
+/// Entries per auction/transaction page shown by the bot.
+pub const AUCTION_PAGE_SIZE: u32 = 10;
+
+/// `/v1/auction/list/{page}`
+#[derive(Debug, Clone)]
+pub struct AuctionPage {
+    pub page: u32,
+    pub listings: Vec<Listing>,
+}
+
+impl AuctionPage {
+    pub fn from_json(json: &Value, page: u32) -> Option<AuctionPage> {
+        let items = json.get("result")?.as_array()?;
+        Some(AuctionPage {
+            page,
+            listings: items.iter().filter_map(Listing::from_value).collect(),
+        })
+    }
+}
+
+/// `/v1/auction/transactions/{page}`
+#[derive(Debug, Clone)]
+pub struct TransactionPage {
+    pub page: u32,
+    pub sales: Vec<Sale>,
+}
+
+impl TransactionPage {
+    pub fn from_json(json: &Value, page: u32) -> Option<TransactionPage> {
+        let items = json.get("result")?.as_array()?;
+        Some(TransactionPage {
+            page,
+            sales: items.iter().filter_map(Sale::from_value).collect(),
+        })
+    }
+}
@@ -0,0 +1,402 @@
+use chrono::Utc;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+use tracing::warn;
+
+use crate::models::{Derived, Leaderboard, PlayerStats, LEADERBOARD_KINDS};
+
+/// Discord caps autocomplete responses at 25 choices.
+pub const MAX_SUGGESTIONS: usize = 25;
+
+/// Percentiles are only shown once this many other players have the stat.
+pub const MIN_PERCENTILE_PLAYERS: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeenPlayer {
+    pub ign: String,
+    /// Unix timestamp (seconds) of the last time the name showed up in API data.
+    pub last_seen: i64,
+    /// Latest value per leaderboard (`money`, `kills`, ...), from leaderboard
+    /// pages and stats lookups.
+    #[serde(default)]
+    pub stats: BTreeMap<String, i64>,
+}
+
+impl SeenPlayer {
+    pub fn to_stats(&self) -> PlayerStats {
+        PlayerStats::from_leaderboard_values(&self.ign, &self.stats)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PlayerIndex {
+    /// Keyed by lowercase IGN.
+    #[serde(default)]
+    pub seen: HashMap<String, SeenPlayer>,
+    /// Discord user ID -> linked IGN.
+    #[serde(default)]
+    pub links: HashMap<String, String>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("PLAYER_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("player_data.json")
+}
+
+/// Held for every read and write of the store, so snapshot passes, lookups
+/// and `/link` can't overwrite each other's changes.
+static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn load() -> PlayerIndex {
+    let _guard = lock();
+    read()
+}
+
+/// Loads, changes and saves the index in one go under the lock. `change`
+/// returns whether there is anything to save.
+fn update(change: impl FnOnce(&mut PlayerIndex) -> bool) -> io::Result<()> {
+    let _guard = lock();
+    let mut index = read();
+    if change(&mut index) {
+        write(&index)?;
+    }
+    Ok(())
+}
+
+fn read() -> PlayerIndex {
+    let path = store_path();
+    if !path.exists() {
+        return PlayerIndex::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => PlayerIndex::default(),
+    }
+}
+
+fn write(index: &PlayerIndex) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(index).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// The entry for `name`, created if it's new. Keeps the latest spelling.
+fn seen_entry<'a>(index: &'a mut PlayerIndex, name: &str) -> &'a mut SeenPlayer {
+    let seen = index
+        .seen
+        .entry(name.to_ascii_lowercase())
+        .or_insert_with(|| SeenPlayer {
+            ign: name.to_string(),
+            last_seen: 0,
+            stats: BTreeMap::new(),
+        });
+    seen.ign = name.to_string();
+    seen
+}
+
+/// Marks every given name as seen right now.
+pub fn record_seen<'a, I>(names: I) -> io::Result<()>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let now = Utc::now().timestamp();
+    update(|index| {
+        let mut changed = false;
+        for name in names {
+            let name = name.trim();
+            if name.is_empty() || name.eq_ignore_ascii_case("unknown") {
+                continue;
+            }
+            let seen = seen_entry(index, name);
+            seen.last_seen = now;
+            changed = true;
+        }
+        changed
+    })
+}
+
+/// Records the usernames of a `/v1/leaderboards/{kind}/...` response, with
+/// their values as a snapshot to rank players against.
+pub fn record_leaderboard(json: &Value, kind: &str) -> io::Result<()> {
+    let leaderboard = match Leaderboard::from_json(json, kind, 0) {
+        Some(leaderboard) if !leaderboard.entries.is_empty() => leaderboard,
+        _ => return Ok(()),
+    };
+    let snapshot = LEADERBOARD_KINDS.contains(&kind);
+    let now = Utc::now().timestamp();
+    update(|index| {
+        for entry in &leaderboard.entries {
+            let name = entry.username.trim();
+            if name.is_empty() || name.eq_ignore_ascii_case("unknown") {
+                continue;
+            }
+            let seen = seen_entry(index, name);
+            seen.last_seen = now;
+            if let (true, Some(value)) = (snapshot, entry.value) {
+                seen.stats.insert(kind.to_string(), value);
+            }
+        }
+        true
+    })
+}
+
+/// Records a player's full stats as their snapshot.
+pub fn record_stats(stats: &PlayerStats) -> io::Result<()> {
+    let name = stats.player.trim();
+    // `/stats` also takes UUIDs, which are longer than any IGN
+    if name.is_empty() || name.len() > 16 {
+        return Ok(());
+    }
+    update(|index| {
+        let seen = seen_entry(index, name);
+        seen.last_seen = Utc::now().timestamp();
+        for (kind, value) in stats.leaderboard_values() {
+            if let Some(value) = value {
+                seen.stats.insert(kind.to_string(), value);
+            }
+        }
+        true
+    })
+}
+
+/// Where `value` ranks among the other seen players' snapshots, from 0 (below
+/// everyone) to 100 (above everyone). `None` with fewer than
+/// [`MIN_PERCENTILE_PLAYERS`] to compare against.
+pub fn percentile(index: &PlayerIndex, stat: Derived, value: f64, ign: &str) -> Option<f64> {
+    let others: Vec<f64> = index
+        .seen
+        .values()
+        .filter(|seen| !seen.ign.eq_ignore_ascii_case(ign))
+        .filter_map(|seen| stat.value(&seen.to_stats()))
+        .collect();
+    if others.len() < MIN_PERCENTILE_PLAYERS {
+        return None;
+    }
+    let below = others.iter().filter(|&&v| v < value).count() as f64;
+    let equal = others.iter().filter(|&&v| v == value).count() as f64;
+    Some((below + equal / 2.0) / others.len() as f64 * 100.0)
+}
+
+/// Percentiles for every derived stat the player has.
+pub fn percentiles(stats: &PlayerStats) -> Vec<(Derived, f64)> {
+    let index = load();
+    Derived::ALL
+        .into_iter()
+        .filter_map(|stat| {
+            let value = stat.value(stats)?;
+            percentile(&index, stat, value, &stats.player).map(|p| (stat, p))
+        })
+        .collect()
+}
+
+/// Takes a snapshot of the first `pages` pages of every leaderboard, so
+/// percentiles cover more than the players people happened to look up.
+pub async fn run_snapshots(
+    http_client: Client,
+    api_key: String,
+    interval_minutes: u64,
+    pages: u32,
+) {
+    loop {
+        for kind in LEADERBOARD_KINDS {
+            for page in 1..=pages {
+                match crate::api::fetch_leaderboard(&http_client, &api_key, kind, page).await {
+                    Some(json) => {
+                        if let Err(e) = record_leaderboard(&json, kind) {
+                            warn!("Failed to store {} leaderboard snapshot: {:?}", kind, e);
+                        }
+                    }
+                    None => {
+                        warn!("Leaderboard snapshot failed for {} page {}", kind, page);
+                        break;
+                    }
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval_minutes.saturating_mul(60))).await;
+    }
+}
+
+/// Records sellers and buyers of auction listings or transactions.
+pub fn record_auction_items(items: &[Value]) -> io::Result<()> {
+    let mut names: Vec<&str> = Vec::new();
+    for item in items {
+        for (obj_key, flat_key) in [("seller", "seller_name"), ("buyer", "buyer_name")] {
+            if let Some(name) = item
+                .get(obj_key)
+                .and_then(|p| p.get("name"))
+                .and_then(|v| v.as_str())
+                .or_else(|| item.get(flat_key).and_then(|v| v.as_str()))
+            {
+                names.push(name);
+            }
+        }
+    }
+    record_seen(names)
+}
+
+pub fn link(user_id: u64, ign: &str) -> io::Result<()> {
+    update(|index| {
+        index.links.insert(user_id.to_string(), ign.trim().to_string());
+        true
+    })
+}
+
+pub fn linked_ign(user_id: u64) -> Option<String> {
+    load().links.get(&user_id.to_string()).cloned()
+}
+
+/// Reverse lookup of [`link`]: the Discord user linked to an IGN, if any.
+pub fn discord_id_for(ign: &str) -> Option<u64> {
+    load()
+        .links
+        .iter()
+        .find(|(_, linked)| linked.eq_ignore_ascii_case(ign))
+        .and_then(|(user_id, _)| user_id.parse().ok())
+}
+
+/// Suggests IGNs matching `partial` for the given Discord user.
+/// The caller's linked account comes first, then names by most recently seen.
+pub fn suggest(partial: &str, user_id: u64) -> Vec<String> {
+    let roster = crate::team::load().members.into_iter().map(|m| m.ign);
+    rank_suggestions(&load(), roster, partial, user_id)
+}
+
+/// [`suggest`] over a given index and roster.
+fn rank_suggestions(
+    index: &PlayerIndex,
+    roster: impl IntoIterator<Item = String>,
+    partial: &str,
+    user_id: u64,
+) -> Vec<String> {
+    let needle = partial.trim().to_ascii_lowercase();
+
+    // (ign, last_seen) keyed by lowercase name so roster and seen entries merge
+    let mut candidates: HashMap<String, (String, i64)> = HashMap::new();
+    for ign in roster {
+        candidates
+            .entry(ign.to_ascii_lowercase())
+            .or_insert((ign, 0));
+    }
+    for (key, seen) in &index.seen {
+        let entry = candidates
+            .entry(key.clone())
+            .or_insert((seen.ign.clone(), seen.last_seen));
+        entry.1 = entry.1.max(seen.last_seen);
+    }
+    let linked = index.links.get(&user_id.to_string()).cloned();
+    if let Some(ign) = &linked {
+        candidates
+            .entry(ign.to_ascii_lowercase())
+            .or_insert((ign.clone(), 0));
+    }
+
+    let linked_key = linked.map(|s| s.to_ascii_lowercase());
+    let mut matches: Vec<(bool, i64, String)> = candidates
+        .into_iter()
+        .filter(|(key, _)| key.contains(&needle))
+        .map(|(key, (ign, last_seen))| (Some(&key) == linked_key.as_ref(), last_seen, ign))
+        .collect();
+    matches.sort_by(|a, b| {
+        b.0.cmp(&a.0)
+            .then(b.1.cmp(&a.1))
+            .then_with(|| a.2.to_ascii_lowercase().cmp(&b.2.to_ascii_lowercase()))
+    });
+    matches
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, ign)| ign)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(kills_and_deaths: &[(i64, i64)]) -> PlayerIndex {
+        let mut index = PlayerIndex::default();
+        for (i, &(kills, deaths)) in kills_and_deaths.iter().enumerate() {
+            let player = seen_entry(&mut index, &format!("player{}", i));
+            player.stats.insert("kills".into(), kills);
+            player.stats.insert("deaths".into(), deaths);
+        }
+        index
+    }
+
+    #[test]
+    fn percentile_ranks_against_other_players() {
+        // K/D 1 to 10 for ten players
+        let index = index(&(1..=10).map(|k| (k, 1)).collect::<Vec<_>>());
+        assert_eq!(percentile(&index, Derived::KdRatio, 10.5, "Steve"), Some(100.0));
+        assert_eq!(percentile(&index, Derived::KdRatio, 5.0, "Steve"), Some(45.0));
+        assert_eq!(percentile(&index, Derived::KdRatio, 0.5, "Steve"), Some(0.0));
+        // The player's own snapshot doesn't count
+        assert_eq!(percentile(&index, Derived::KdRatio, 5.0, "player0"), None);
+    }
+
+    fn seen(names: &[(&str, i64)]) -> PlayerIndex {
+        let mut index = PlayerIndex::default();
+        for &(name, last_seen) in names {
+            seen_entry(&mut index, name).last_seen = last_seen;
+        }
+        index
+    }
+
+    #[test]
+    fn suggestions_put_the_linked_account_first_then_recent_names() {
+        let mut index = seen(&[("Alex", 300), ("Steve", 100), ("Notch", 200)]);
+        index.links.insert("42".into(), "steve".into());
+        let roster = vec!["Herobrine".to_string()];
+
+        assert_eq!(
+            rank_suggestions(&index, roster.clone(), "", 42),
+            vec!["Steve", "Alex", "Notch", "Herobrine"]
+        );
+        // Someone else gets plain recency
+        assert_eq!(
+            rank_suggestions(&index, roster, "", 7),
+            vec!["Alex", "Notch", "Steve", "Herobrine"]
+        );
+    }
+
+    #[test]
+    fn suggestions_merge_names_case_insensitively() {
+        let index = seen(&[("Steve", 100)]);
+        let roster = vec!["STEVE".to_string(), "Stevie".to_string()];
+        // The roster spelling wins, but the seen time still counts
+        assert_eq!(
+            rank_suggestions(&index, roster, "steV", 1),
+            vec!["STEVE", "Stevie"]
+        );
+    }
+
+    #[test]
+    fn suggestions_are_capped() {
+        let names: Vec<(String, i64)> = (0..40).map(|i| (format!("Player{:02}", i), i)).collect();
+        let names: Vec<(&str, i64)> = names.iter().map(|(n, t)| (n.as_str(), *t)).collect();
+        let suggestions = rank_suggestions(&seen(&names), Vec::new(), "player", 1);
+        assert_eq!(suggestions.len(), MAX_SUGGESTIONS);
+        assert_eq!(suggestions[0], "Player39");
+    }
+}
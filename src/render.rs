@@ -0,0 +1,2451 @@
+use std::fmt;
+
+use serde_json::Value;
+use serenity::builder::CreateEmbed;
+
+use crate::applications::{ApplicationState, ApplicationView};
+use crate::audit::{AuditEntry, AuditView, Change};
+use crate::constants::{EMBED_COLOR_ACCENT, EMBED_COLOR_ERROR, EMBED_COLOR_ERROR_ALT, ZWSP};
+use crate::countries::{country_flag, display_name};
+use crate::digest::Digest;
+use crate::goals::{Announcement, GoalProgress};
+use crate::history::{RestorePreview, Undone};
+use crate::locale::Format;
+use crate::market::SaleRecord;
+use crate::models::{
+    ApiMessage, AuctionPage, Comparison, Derived, Leaderboard, PlayerLookup, PlayerStats, Presence,
+    StatsView, TransactionPage, AUCTION_PAGE_SIZE, LEADERBOARD_PAGE_SIZE,
+};
+use crate::roster::{changed_fields, RosterDiff, IMPORT_EXPIRY_MINUTES};
+use crate::status::{Group, Layout, TeamStatus};
+use crate::team::{
+    parse_date, utc_offset, CountryBreakdown, Detail, FieldKind, Profile, Squad, TeamMember,
+};
+use crate::trader::{Ranked, TraderProfile};
+
+// Discord embed limits, counted in characters.
+pub const TITLE_LIMIT: usize = 256;
+pub const DESCRIPTION_LIMIT: usize = 4096;
+pub const FIELD_NAME_LIMIT: usize = 256;
+pub const FIELD_VALUE_LIMIT: usize = 1024;
+pub const FIELD_COUNT_LIMIT: usize = 25;
+pub const FOOTER_LIMIT: usize = 2048;
+pub const EMBED_TOTAL_LIMIT: usize = 6000;
+
+// Room kept free on every page for the " (i/n)" title suffix of split pages
+const PART_SUFFIX_RESERVE: usize = 10;
+
+// Language-neutral so truncation never needs the reader's format
+const TRUNCATED: &str = "\n*…*";
+
+/// Cuts `text` to at most `limit` characters, never inside a UTF-8 character.
+/// Prefers the last line break so entries aren't cut in half.
+pub fn truncate_chars(text: &str, limit: usize, marker: &str) -> String {
+    if text.chars().count() <= limit {
+        return text.to_string();
+    }
+    let keep = limit.saturating_sub(marker.chars().count());
+    let end = text
+        .char_indices()
+        .nth(keep)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len());
+    let cut = &text[..end];
+    let cut = match cut.rfind('\n') {
+        Some(i) if i > 0 => &cut[..i],
+        _ => cut,
+    };
+    format!("{}{}", cut.trim_end(), marker)
+}
+
+/// Splits `text` into chunks of at most `limit` characters, breaking at blank
+/// lines first, then line breaks, then spaces, and never inside a UTF-8 character.
+pub fn split_text(text: &str, limit: usize) -> Vec<String> {
+    if text.chars().count() <= limit {
+        return vec![text.to_string()];
+    }
+    for separator in ["\n\n", "\n", " "] {
+        if !text.contains(separator) {
+            continue;
+        }
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+        for part in text.split(separator) {
+            if part.chars().count() > limit {
+                if !current.is_empty() {
+                    chunks.push(std::mem::take(&mut current));
+                }
+                chunks.extend(split_text(part, limit));
+            } else if current.is_empty() {
+                current = part.to_string();
+            } else if current.chars().count() + separator.len() + part.chars().count() <= limit {
+                current.push_str(separator);
+                current.push_str(part);
+            } else {
+                chunks.push(std::mem::replace(&mut current, part.to_string()));
+            }
+        }
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+        return chunks;
+    }
+    // A single unbroken word: cut on character boundaries
+    let chars: Vec<char> = text.chars().collect();
+    chars
+        .chunks(limit.max(1))
+        .map(|chunk| chunk.iter().collect())
+        .collect()
+}
+
+/// Empty apart from whitespace or the zero-width spacer Discord needs for blank fields.
+fn is_blank(text: &str) -> bool {
+    text.trim_matches(|c: char| c.is_whitespace() || c == '\u{200B}')
+        .is_empty()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub value: String,
+    pub inline: bool,
+}
+
+/// Everything an embed shows, before Discord's limits are applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub fields: Vec<Field>,
+    pub footer: Option<String>,
+    pub color: u32,
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        Page {
+            title: None,
+            description: None,
+            fields: Vec::new(),
+            footer: None,
+            color: EMBED_COLOR_ACCENT,
+        }
+    }
+}
+
+impl Page {
+    pub fn new() -> Page {
+        Page::default()
+    }
+
+    /// The one layout used whenever a page of results comes back empty.
+    pub fn empty(emoji: &str, message: &str) -> Page {
+        Page::new().description(format!("{} {}", emoji, message))
+    }
+
+    /// Shown when the API answered without a usable `result`.
+    pub fn api_message(message: &ApiMessage, fmt: &Format) -> Page {
+        let mut page = Page::new();
+        if let Some(status) = &message.status {
+            page = page.field(fmt.t("api.status"), status, true);
+        }
+        if let Some(text) = &message.message {
+            page = page.field(fmt.t("api.message"), text, false);
+        }
+        if page.fields.is_empty() {
+            page = page.description(fmt.t("api.unexpected"));
+        }
+        page.color(EMBED_COLOR_ERROR)
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Page {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Page {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn field(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+        inline: bool,
+    ) -> Page {
+        self.fields.push(Field {
+            name: name.into(),
+            value: value.into(),
+            inline,
+        });
+        self
+    }
+
+    pub fn footer(mut self, footer: impl Into<String>) -> Page {
+        self.footer = Some(footer.into());
+        self
+    }
+
+    pub fn color(mut self, color: u32) -> Page {
+        self.color = color;
+        self
+    }
+
+    /// The page with every part cut to Discord's embed limits.
+    pub fn limited(&self) -> Page {
+        let mut page = Page {
+            title: self
+                .title
+                .as_deref()
+                .map(|t| truncate_chars(t, TITLE_LIMIT, "...")),
+            description: self
+                .description
+                .as_deref()
+                .map(|d| truncate_chars(d, DESCRIPTION_LIMIT, TRUNCATED)),
+            fields: self
+                .fields
+                .iter()
+                .take(FIELD_COUNT_LIMIT)
+                .map(|f| Field {
+                    name: if is_blank(&f.name) {
+                        ZWSP.to_string()
+                    } else {
+                        truncate_chars(&f.name, FIELD_NAME_LIMIT, "...")
+                    },
+                    value: if is_blank(&f.value) {
+                        ZWSP.to_string()
+                    } else {
+                        truncate_chars(&f.value, FIELD_VALUE_LIMIT, TRUNCATED)
+                    },
+                    inline: f.inline,
+                })
+                .collect(),
+            footer: self
+                .footer
+                .as_deref()
+                .map(|f| truncate_chars(f, FOOTER_LIMIT, "...")),
+            color: self.color,
+        };
+
+        // Over the combined limit: drop trailing fields, then shorten the description
+        while page.total_chars() > EMBED_TOTAL_LIMIT && !page.fields.is_empty() {
+            page.fields.pop();
+        }
+        if page.total_chars() > EMBED_TOTAL_LIMIT {
+            if let Some(description) = page.description.take() {
+                let budget = EMBED_TOTAL_LIMIT.saturating_sub(page.total_chars());
+                page.description = Some(truncate_chars(&description, budget, TRUNCATED));
+            }
+        }
+        page
+    }
+
+    /// Spreads the page over as many embeds as Discord's limits require.
+    /// The description is split at entry boundaries and fields are never cut
+    /// between pages; continuation pages get `(2/3)` style titles and only the
+    /// last page keeps the footer.
+    pub fn split(&self) -> Vec<Page> {
+        let footer = self
+            .footer
+            .as_deref()
+            .map(|f| truncate_chars(f, FOOTER_LIMIT, "..."));
+        let footer_len = footer.as_deref().map_or(0, |f| f.chars().count());
+        let continuation = || Page {
+            title: self.title.clone(),
+            color: self.color,
+            ..Page::default()
+        };
+
+        let mut pages = Vec::new();
+        let mut current = continuation();
+        if let Some(description) = &self.description {
+            for chunk in split_text(description, DESCRIPTION_LIMIT) {
+                if current.description.is_some() {
+                    pages.push(std::mem::replace(&mut current, continuation()));
+                }
+                current.description = Some(chunk);
+            }
+        }
+
+        for field in &self.fields {
+            let name = truncate_chars(&field.name, FIELD_NAME_LIMIT, "...");
+            for (i, value) in split_text(&field.value, FIELD_VALUE_LIMIT)
+                .into_iter()
+                .enumerate()
+            {
+                let field = Field {
+                    name: if i == 0 {
+                        name.clone()
+                    } else {
+                        ZWSP.to_string()
+                    },
+                    value,
+                    inline: field.inline,
+                };
+                let spacer = is_blank(&field.name) && is_blank(&field.value);
+                let field_len = field.name.chars().count() + field.value.chars().count();
+                if current.fields.len() >= FIELD_COUNT_LIMIT
+                    || current.total_chars() + field_len + footer_len + PART_SUFFIX_RESERVE
+                        > EMBED_TOTAL_LIMIT
+                {
+                    pages.push(std::mem::replace(&mut current, continuation()));
+                }
+                // Blank spacer fields are pointless at the top of a new page
+                if spacer && current.description.is_none() && current.fields.is_empty() {
+                    continue;
+                }
+                current.fields.push(field);
+            }
+        }
+        current.footer = footer;
+        pages.push(current);
+
+        let total = pages.len();
+        if total > 1 {
+            for (i, page) in pages.iter_mut().enumerate() {
+                if let Some(title) = &page.title {
+                    let suffix = format!(" ({}/{})", i + 1, total);
+                    let limit = TITLE_LIMIT - suffix.chars().count();
+                    page.title = Some(format!("{}{}", truncate_chars(title, limit, "..."), suffix));
+                }
+            }
+        }
+        pages
+    }
+
+    /// One embed per page from [`Page::split`].
+    pub fn to_embeds(&self) -> Vec<CreateEmbed> {
+        self.split().iter().map(Page::to_embed).collect()
+    }
+
+    /// Characters Discord counts towards the 6000 character embed limit.
+    pub fn total_chars(&self) -> usize {
+        let count = |s: &Option<String>| s.as_deref().map_or(0, |s| s.chars().count());
+        count(&self.title)
+            + count(&self.description)
+            + count(&self.footer)
+            + self
+                .fields
+                .iter()
+                .map(|f| f.name.chars().count() + f.value.chars().count())
+                .sum::<usize>()
+    }
+
+    /// Writes the page into a serenity embed, within Discord's limits.
+    pub fn apply(&self, embed: &mut CreateEmbed) {
+        let page = self.limited();
+        if let Some(title) = &page.title {
+            embed.title(title);
+        }
+        if let Some(description) = &page.description {
+            embed.description(description);
+        }
+        for field in &page.fields {
+            embed.field(&field.name, &field.value, field.inline);
+        }
+        if let Some(footer) = &page.footer {
+            embed.footer(|f| f.text(footer));
+        }
+        embed.color(page.color);
+    }
+
+    pub fn to_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::default();
+        self.apply(&mut embed);
+        embed
+    }
+}
+
+/// Plain-text view of a page, used by the snapshot tests.
+impl fmt::Display for Page {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "color: #{:06x}", self.color)?;
+        if let Some(title) = &self.title {
+            writeln!(f, "title: {}", title)?;
+        }
+        if let Some(description) = &self.description {
+            writeln!(f, "description:\n{}", description)?;
+        }
+        for field in &self.fields {
+            let kind = if field.inline {
+                "inline field"
+            } else {
+                "field"
+            };
+            writeln!(f, "{}: {}\n{}", kind, field.name, field.value)?;
+        }
+        if let Some(footer) = &self.footer {
+            writeln!(f, "footer: {}", footer)?;
+        }
+        Ok(())
+    }
+}
+
+/// A typed API model that knows how to present itself as an embed.
+pub trait Render {
+    fn render(&self, fmt: &Format) -> Page;
+}
+
+/// Renders a parsed model, or the API's status/message when parsing failed.
+pub fn render_or_message<T: Render>(model: Option<T>, json: &Value, fmt: &Format) -> Page {
+    match model {
+        Some(model) => model.render(fmt),
+        None => Page::api_message(&ApiMessage::from_json(json), fmt),
+    }
+}
+
+impl Render for PlayerLookup {
+    fn render(&self, fmt: &Format) -> Page {
+        let mut page = Page::new();
+        if let Some(username) = &self.username {
+            page = page.field(fmt.t("lookup.username"), username, true);
+        }
+        if let Some(location) = &self.location {
+            page = page.field(fmt.t("lookup.location"), location, true);
+        }
+        if let Some(rank) = &self.rank {
+            page = page.field(fmt.t("lookup.rank"), rank, true);
+        }
+        if page.fields.is_empty() {
+            page = page.description(fmt.tf("lookup.empty", &[&self.query]));
+        }
+        page
+    }
+}
+
+impl Render for PlayerStats {
+    fn render(&self, fmt: &Format) -> Page {
+        let sections: [&[(&str, &str, Option<String>)]; 5] = [
+            &[
+                ("💰", "stats.money", self.money.map(|v| fmt.money(v))),
+                ("💎", "stats.shards", self.shards.map(|v| fmt.number(v))),
+            ],
+            &[
+                (
+                    "📈",
+                    "stats.money_made",
+                    self.money_made_from_sell.map(|v| fmt.money(v)),
+                ),
+                (
+                    "🛒",
+                    "stats.money_spent",
+                    self.money_spent_on_shop.map(|v| fmt.money(v)),
+                ),
+            ],
+            &[(
+                "🕒",
+                "stats.playtime",
+                self.playtime_ms.map(|v| fmt.duration_ms(v)),
+            )],
+            &[
+                ("⚔️", "stats.kills", self.kills.map(|v| fmt.number(v))),
+                ("💀", "stats.deaths", self.deaths.map(|v| fmt.number(v))),
+                ("🐗", "stats.mobs_killed", self.mobs_killed.map(|v| fmt.number(v))),
+            ],
+            &[
+                (
+                    "🧱",
+                    "stats.blocks_placed",
+                    self.placed_blocks.map(|v| fmt.number(v)),
+                ),
+                (
+                    "⛏️",
+                    "stats.blocks_broken",
+                    self.broken_blocks.map(|v| fmt.number(v)),
+                ),
+            ],
+        ];
+
+        let description = sections
+            .iter()
+            .map(|section| {
+                section
+                    .iter()
+                    .filter_map(|(emoji, label, value)| {
+                        value
+                            .as_ref()
+                            .map(|v| format!("{} **{}:** {}", emoji, fmt.t(label), v))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .filter(|section| !section.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let page = Page::new().title(fmt.tf("stats.title", &[&self.player]));
+        if description.is_empty() {
+            page.description(fmt.t("stats.empty"))
+        } else {
+            page.description(description)
+        }
+    }
+}
+
+impl Render for StatsView {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = self.stats.render(fmt);
+        let lines: Vec<String> = Derived::ALL
+            .into_iter()
+            .filter_map(|stat| {
+                let value = stat.value(&self.stats)?;
+                let shown = match stat {
+                    Derived::KdRatio => format!("{:.2}", value),
+                    Derived::NetEconomy => fmt.money(value as i64),
+                    // Rates below 10 an hour would round to nothing
+                    _ if value.abs() < 10.0 => format!("{:.1}", value),
+                    _ => fmt.number(value.round() as i64),
+                };
+                let label = fmt.t(stat.label_key());
+                let mut line = format!("{} **{}:** {}", stat.emoji(), label, shown);
+                if let Some(&(_, p)) = self.percentiles.iter().find(|(s, _)| *s == stat) {
+                    let rank = if p >= 50.0 {
+                        let top = (100.0 - p).ceil().max(1.0) as i64;
+                        fmt.tf("stats.top_percent", &[&top])
+                    } else {
+                        let bottom = p.ceil().max(1.0) as i64;
+                        fmt.tf("stats.bottom_percent", &[&bottom])
+                    };
+                    line.push_str(&format!(" · {}", rank));
+                }
+                Some(line)
+            })
+            .collect();
+        if lines.is_empty() {
+            return page;
+        }
+        let page = page.field(fmt.t("stats.derived"), lines.join("\n"), false);
+        if self.percentiles.is_empty() {
+            page
+        } else {
+            page.footer(fmt.t("stats.percentile_note"))
+        }
+    }
+}
+
+/// Shown when `/stats` gets no `result` back for a player.
+pub fn stats_not_found(player: &str, fmt: &Format) -> Page {
+    Page::new()
+        .title(fmt.tf("stats.not_found_title", &[&player]))
+        .description(fmt.t("stats.not_found"))
+        .color(EMBED_COLOR_ERROR_ALT)
+}
+
+/// How a compared stat is shown.
+#[derive(Clone, Copy)]
+enum Unit {
+    Money,
+    Count,
+    Duration,
+    Ratio,
+}
+
+fn as_f64(value: Option<i64>) -> Option<f64> {
+    value.map(|v| v as f64)
+}
+
+type StatValue = fn(&PlayerStats) -> Option<f64>;
+
+/// Every stat `/compare` shows: label, unit, whether less is better, value.
+const COMPARED: &[(&str, Unit, bool, StatValue)] = &[
+    ("stats.money", Unit::Money, false, |s| as_f64(s.money)),
+    ("stats.shards", Unit::Count, false, |s| as_f64(s.shards)),
+    ("stats.money_made", Unit::Money, false, |s| {
+        as_f64(s.money_made_from_sell)
+    }),
+    ("stats.money_spent", Unit::Money, false, |s| {
+        as_f64(s.money_spent_on_shop)
+    }),
+    ("stats.playtime", Unit::Duration, false, |s| {
+        as_f64(s.playtime_ms)
+    }),
+    ("stats.kills", Unit::Count, false, |s| as_f64(s.kills)),
+    ("stats.deaths", Unit::Count, true, |s| as_f64(s.deaths)),
+    ("stats.kd", Unit::Ratio, false, PlayerStats::kd_ratio),
+    ("stats.mobs_killed", Unit::Count, false, |s| {
+        as_f64(s.mobs_killed)
+    }),
+    ("stats.blocks_placed", Unit::Count, false, |s| {
+        as_f64(s.placed_blocks)
+    }),
+    ("stats.blocks_broken", Unit::Count, false, |s| {
+        as_f64(s.broken_blocks)
+    }),
+    (
+        "stats.money_per_hour",
+        Unit::Money,
+        false,
+        PlayerStats::money_per_hour,
+    ),
+    (
+        "stats.blocks_per_hour",
+        Unit::Count,
+        false,
+        PlayerStats::blocks_per_hour,
+    ),
+];
+
+impl Render for Comparison {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.t("compare.title"));
+        let missing = (!self.missing.is_empty())
+            .then(|| fmt.tf("compare.missing", &[&self.missing.join(", ")]));
+        if self.players.len() < 2 {
+            let mut description = fmt.t("compare.not_enough").to_string();
+            if let Some(missing) = missing {
+                description.push_str(&format!("\n{}", missing));
+            }
+            return page.description(description).color(EMBED_COLOR_ERROR_ALT);
+        }
+
+        let show = |unit: Unit, value: f64| match unit {
+            Unit::Money => fmt.money(value.round() as i64),
+            Unit::Count => fmt.number(value.round() as i64),
+            Unit::Duration => fmt.duration_ms(value as i64),
+            Unit::Ratio => format!("{:.2}", value),
+        };
+        // (label, cells); a cell is the shown value and whether it leads
+        let rows: Vec<(&str, Vec<(String, bool)>)> = COMPARED
+            .iter()
+            .map(|&(label, unit, less_is_better, value)| {
+                let values: Vec<Option<f64>> = self.players.iter().map(value).collect();
+                let known: Vec<f64> = values.iter().flatten().copied().collect();
+                let pick = if less_is_better { f64::min } else { f64::max };
+                let best = known.iter().copied().reduce(pick);
+                // Nobody leads when they're all the same
+                let best = best.filter(|&b| known.len() > 1 && known.iter().any(|&v| v != b));
+                let cells = values
+                    .iter()
+                    .map(|v| match v {
+                        Some(v) => (show(unit, *v), Some(*v) == best),
+                        None => ("-".to_string(), false),
+                    })
+                    .collect();
+                (fmt.t(label), cells)
+            })
+            .collect();
+
+        let label_width = rows
+            .iter()
+            .map(|(l, _)| l.chars().count())
+            .max()
+            .unwrap_or(0);
+        let widths: Vec<usize> = self
+            .players
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                rows.iter()
+                    .map(|(_, cells)| cells[i].0.chars().count())
+                    .chain(std::iter::once(p.player.chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let mut table = format!("{:<1$}", "", label_width);
+        for (player, width) in self.players.iter().zip(&widths) {
+            table.push_str(&format!("  {:>1$} ", player.player, width));
+        }
+        for (label, cells) in &rows {
+            table.push_str(&format!("\n{:<1$}", label, label_width));
+            for ((value, leads), width) in cells.iter().zip(&widths) {
+                let marker = if *leads { '*' } else { ' ' };
+                table.push_str(&format!("  {:>1$}{2}", value, width, marker));
+            }
+        }
+
+        let mut description = format!("```\n{}\n```\n{}", table, fmt.t("compare.legend"));
+        if let Some(missing) = missing {
+            description.push_str(&format!("\n{}", missing));
+        }
+        page.description(description)
+    }
+}
+
+/// `▰▰▰▱▱▱▱▱▱▱ 30%`, capped at a full bar.
+fn progress_bar(value: i64, target: i64) -> String {
+    const WIDTH: usize = 10;
+    let ratio = (value.max(0) as f64 / target.max(1) as f64).min(1.0);
+    let filled = (ratio * WIDTH as f64).floor() as usize;
+    format!(
+        "{}{} {}%",
+        "▰".repeat(filled),
+        "▱".repeat(WIDTH - filled),
+        (ratio * 100.0).floor()
+    )
+}
+
+impl Render for GoalProgress {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.tf("goal.list_title", &[&self.ign]));
+        if self.goals.is_empty() {
+            return page.description(fmt.t("goal.none"));
+        }
+        let mut page = self.goals.iter().fold(page, |page, goal| {
+            let value = self.stats.as_ref().and_then(|s| goal.stat.value(s));
+            let progress = match value {
+                Some(value) => format!(
+                    "{} / {}\n{}",
+                    goal.stat.show(value, fmt),
+                    goal.stat.show(goal.target, fmt),
+                    progress_bar(value, goal.target)
+                ),
+                None => fmt.tf("goal.no_progress", &[&goal.stat.show(goal.target, fmt)]),
+            };
+            page.field(
+                format!("{} {}", goal.stat.emoji(), fmt.t(goal.stat.label_key())),
+                progress,
+                false,
+            )
+        });
+        if self.stats.is_none() {
+            page = page.footer(fmt.t("goal.stats_unavailable"));
+        }
+        page
+    }
+}
+
+impl Render for Announcement {
+    fn render(&self, fmt: &Format) -> Page {
+        match self {
+            Announcement::Goal { goal, value } => Page::new()
+                .title(fmt.t("goal.reached_title"))
+                .description(fmt.tf(
+                    "goal.reached",
+                    &[
+                        &format!("<@{}>", goal.user_id),
+                        &goal.ign,
+                        &goal.stat.emoji(),
+                        &fmt.t(goal.stat.label_key()),
+                        &goal.stat.show(goal.target, fmt),
+                    ],
+                ))
+                .footer(fmt.tf(
+                    "goal.reached_footer",
+                    &[
+                        &goal.stat.show(*value, fmt),
+                        &fmt.date(goal.set_at.date_naive()),
+                    ],
+                )),
+            Announcement::Milestone {
+                ign,
+                stat,
+                threshold,
+            } => Page::new()
+                .title(fmt.t("goal.milestone_title"))
+                .description(fmt.tf(
+                    "goal.milestone",
+                    &[
+                        ign,
+                        &stat.emoji(),
+                        &stat.show(*threshold, fmt),
+                        &fmt.t(stat.label_key()),
+                    ],
+                )),
+        }
+    }
+}
+
+impl Render for Leaderboard {
+    fn render(&self, fmt: &Format) -> Page {
+        let title = fmt.tf(
+            "leaderboard.title",
+            &[&self.emoji(), &fmt.t(self.name_key()), &self.page],
+        );
+        if self.entries.is_empty() {
+            return Page::empty(self.emoji(), fmt.t("leaderboard.empty")).title(title);
+        }
+
+        let start_position = (self.page.max(1) - 1) * LEADERBOARD_PAGE_SIZE + 1;
+        let shown = self.entries.len().min(LEADERBOARD_PAGE_SIZE as usize);
+        let description = self
+            .entries
+            .iter()
+            .take(shown)
+            .enumerate()
+            .map(|(i, entry)| {
+                let position = start_position + i as u32;
+                let value = match entry.value {
+                    Some(value) => match self.kind.as_str() {
+                        "money" | "sell" | "shop" => fmt.money(value),
+                        "playtime" => fmt.duration_ms(value),
+                        _ => fmt.number(value),
+                    },
+                    None => entry.raw.clone(),
+                };
+                let medal = match position {
+                    1 => "🥇",
+                    2 => "🥈",
+                    3 => "🥉",
+                    _ => "  ",
+                };
+                format!("{} **#{}** {} - {}", medal, position, entry.username, value)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        Page::new()
+            .title(title)
+            .description(description)
+            .footer(fmt.tf("leaderboard.footer", &[&self.page, &shown]))
+    }
+}
+
+/// Shown when a leaderboard page can't be parsed.
+pub fn leaderboard_not_found(kind: &str, fmt: &Format) -> Page {
+    Page::new()
+        .title(fmt.tf("leaderboard.not_found_title", &[&kind]))
+        .description(fmt.t("leaderboard.not_found"))
+        .color(EMBED_COLOR_ERROR)
+}
+
+impl Render for AuctionPage {
+    fn render(&self, fmt: &Format) -> Page {
+        if self.listings.is_empty() {
+            return Page::empty("🏪", fmt.t("auction.empty"));
+        }
+        let start_index = (self.page.max(1) - 1) * AUCTION_PAGE_SIZE;
+        let description = self
+            .listings
+            .iter()
+            .take(AUCTION_PAGE_SIZE as usize)
+            .enumerate()
+            .map(|(i, listing)| {
+                format!(
+                    "**{}**. {} - **{}**\n└ *{}*",
+                    start_index + i as u32 + 1,
+                    listing.label(),
+                    fmt.money(listing.price),
+                    fmt.tf("auction.seller", &[&listing.seller])
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Page::new().description(description)
+    }
+}
+
+impl Render for TransactionPage {
+    fn render(&self, fmt: &Format) -> Page {
+        if self.sales.is_empty() {
+            return Page::empty("📜", fmt.t("auction.transactions_empty"));
+        }
+        let start_index = (self.page.max(1) - 1) * AUCTION_PAGE_SIZE;
+        let description = self
+            .sales
+            .iter()
+            .take(AUCTION_PAGE_SIZE as usize)
+            .enumerate()
+            .map(|(i, sale)| {
+                let listing = &sale.listing;
+                let unit_str = if listing.count > 1 {
+                    format!(
+                        " ({})",
+                        fmt.tf("auction.each", &[&fmt.money(listing.price / listing.count)])
+                    )
+                } else {
+                    String::new()
+                };
+                // Discord renders <t:…:R> as a live relative time in the reader's timezone
+                let time_str = match sale.sold_at {
+                    Some(ts) => format!("<t:{}:R>", ts),
+                    None => fmt.t("auction.unknown_time").to_string(),
+                };
+                format!(
+                    "**{}**. {} - **{}**{}\n└ *🛒 {} → 🏪 {} | {}*",
+                    start_index + i as u32 + 1,
+                    listing.label(),
+                    fmt.money(listing.price),
+                    unit_str,
+                    sale.buyer.as_deref().unwrap_or(fmt.t("common.unknown")),
+                    listing.seller,
+                    time_str
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Page::new().description(description)
+    }
+}
+
+/// How many listings, sales or names each `/trader` field shows.
+const TRADER_LINES: usize = 5;
+
+/// The newest `records` with the other side of each trade, or "None recorded".
+fn trade_lines(
+    records: &[SaleRecord],
+    fmt: &Format,
+    counterparty: impl Fn(&SaleRecord) -> String,
+) -> String {
+    if records.is_empty() {
+        return fmt.t("trader.none_recorded").to_string();
+    }
+    let mut lines: Vec<String> = records
+        .iter()
+        .take(TRADER_LINES)
+        .map(|r| {
+            format!(
+                "{} - **{}**\n└ *{} | <t:{}:R>*",
+                r.label,
+                fmt.money(r.price),
+                counterparty(r),
+                r.sold_at
+            )
+        })
+        .collect();
+    if records.len() > TRADER_LINES {
+        lines.push(fmt.tf("common.more", &[&(records.len() - TRADER_LINES)]));
+    }
+    lines.join("\n")
+}
+
+fn ranked_line(entries: &Ranked) -> String {
+    entries
+        .iter()
+        .take(TRADER_LINES)
+        .map(|(name, n)| format!("{} ({})", name, n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Render for TraderProfile {
+    fn render(&self, fmt: &Format) -> Page {
+        let listings = if self.listings.is_empty() {
+            fmt.t("trader.no_listings").to_string()
+        } else {
+            let mut lines: Vec<String> = self
+                .listings
+                .iter()
+                .take(TRADER_LINES)
+                .map(|l| format!("{} - **{}**", l.label(), fmt.money(l.price)))
+                .collect();
+            if self.listings.len() > TRADER_LINES {
+                lines.push(fmt.tf("common.more", &[&(self.listings.len() - TRADER_LINES)]));
+            }
+            lines.join("\n")
+        };
+        let average = self
+            .average_sale_price()
+            .map(|p| fmt.money(p))
+            .unwrap_or_else(|| "-".to_string());
+        let mut page = Page::new()
+            .title(fmt.tf("trader.title", &[&self.ign]))
+            .field(
+                fmt.tf("trader.listings", &[&self.listings.len()]),
+                listings,
+                false,
+            )
+            .field(
+                fmt.tf("trader.sales", &[&self.sales.len()]),
+                trade_lines(&self.sales, fmt, |r| {
+                    fmt.tf(
+                        "trader.sold_to",
+                        &[&r.buyer.as_deref().unwrap_or(fmt.t("common.unknown"))],
+                    )
+                }),
+                false,
+            )
+            .field(
+                fmt.tf("trader.purchases", &[&self.purchases.len()]),
+                trade_lines(&self.purchases, fmt, |r| {
+                    fmt.tf("trader.bought_from", &[&r.seller])
+                }),
+                false,
+            )
+            .field(
+                fmt.t("trader.summary"),
+                fmt.tf(
+                    "trader.summary_body",
+                    &[&fmt.money(self.total_volume()), &average],
+                ),
+                false,
+            );
+
+        let most_traded = self.most_traded();
+        if !most_traded.is_empty() {
+            page = page.field(
+                fmt.t("trader.most_traded"),
+                ranked_line(&most_traded),
+                false,
+            );
+        }
+        let (buyers, sellers) = self.partners();
+        let mut partners = Vec::new();
+        if !buyers.is_empty() {
+            partners.push(fmt.tf("trader.partners_sold", &[&ranked_line(&buyers)]));
+        }
+        if !sellers.is_empty() {
+            partners.push(fmt.tf("trader.partners_bought", &[&ranked_line(&sellers)]));
+        }
+        if !partners.is_empty() {
+            page = page.field(fmt.t("trader.partners"), partners.join("\n"), false);
+        }
+        page.footer(fmt.t("trader.footer"))
+    }
+}
+
+/// `lines` one per line, or "No data" when there are none.
+fn lines_or_none(lines: Vec<String>, fmt: &Format) -> String {
+    if lines.is_empty() {
+        fmt.t("common.no_data").to_string()
+    } else {
+        lines.join("\n")
+    }
+}
+
+impl Render for Digest<'_> {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.t(self.period.title_key()));
+        if self.sales.is_empty() {
+            return page.description(fmt.t("digest.empty"));
+        }
+
+        let top_items = self
+            .top_items()
+            .iter()
+            .enumerate()
+            .map(|(i, (name, volume, n))| {
+                fmt.tf("digest.top_item", &[&(i + 1), name, &fmt.money(*volume), n])
+            })
+            .collect();
+        let movers = self
+            .price_movers()
+            .iter()
+            .map(|m| {
+                let arrow = if m.after > m.before { "📈" } else { "📉" };
+                fmt.tf(
+                    "digest.mover",
+                    &[
+                        &arrow,
+                        &m.name,
+                        &fmt.money(m.before.round() as i64),
+                        &fmt.money(m.after.round() as i64),
+                        &format!("{:+.1}%", m.percent()),
+                    ],
+                )
+            })
+            .collect();
+        let expensive = self
+            .most_expensive()
+            .iter()
+            .map(|s| {
+                format!(
+                    "{} - **{}**\n└ *{} → {} | <t:{}:R>*",
+                    s.label,
+                    fmt.money(s.price),
+                    s.seller,
+                    s.buyer.as_deref().unwrap_or(fmt.t("common.unknown")),
+                    s.sold_at
+                )
+            })
+            .collect();
+        let sellers = self
+            .active_sellers()
+            .iter()
+            .enumerate()
+            .map(|(i, (name, n, volume))| {
+                fmt.tf("digest.seller", &[&(i + 1), name, n, &fmt.money(*volume)])
+            })
+            .collect();
+        let team = self
+            .team_activity()
+            .iter()
+            .map(|(ign, sold, volume, bought)| {
+                fmt.tf(
+                    "digest.team_member",
+                    &[ign, sold, &fmt.money(*volume), bought],
+                )
+            })
+            .collect();
+
+        page.description(fmt.tf(
+            "digest.summary",
+            &[
+                &fmt.number(self.sales.len() as i64),
+                &fmt.money(self.total_volume()),
+            ],
+        ))
+        .field(
+            fmt.t("digest.top_items"),
+            lines_or_none(top_items, fmt),
+            false,
+        )
+        .field(fmt.t("digest.movers"), lines_or_none(movers, fmt), false)
+        .field(
+            fmt.t("digest.expensive"),
+            lines_or_none(expensive, fmt),
+            false,
+        )
+        .field(fmt.t("digest.sellers"), lines_or_none(sellers, fmt), false)
+        .field(fmt.t("digest.team"), lines_or_none(team, fmt), false)
+        .footer(fmt.t("digest.footer"))
+    }
+}
+
+/// A custom field value in the reader's format.
+fn custom_value(kind: FieldKind, value: &str, fmt: &Format) -> String {
+    match kind {
+        FieldKind::Number => match value.parse::<f64>() {
+            Ok(n) if n.fract() == 0.0 && n.abs() < 1e15 => fmt.number(n as i64),
+            _ => value.to_string(),
+        },
+        FieldKind::Date => parse_date(value)
+            .map(|d| fmt.date(d))
+            .unwrap_or_else(|| value.to_string()),
+        FieldKind::Toggle if value == "yes" => fmt.t("field.yes").to_string(),
+        FieldKind::Toggle => fmt.t("field.no").to_string(),
+        FieldKind::Text | FieldKind::Link => value.to_string(),
+    }
+}
+
+impl Render for Profile {
+    fn render(&self, fmt: &Format) -> Page {
+        let member = &self.member;
+        let status = match &self.presence {
+            Presence::Online {
+                location: Some(location),
+            } => fmt.tf("profile.online_at", &[location]),
+            Presence::Online { location: None } => fmt.t("profile.online").to_string(),
+            Presence::Offline => fmt.t("profile.offline").to_string(),
+            Presence::Unknown => fmt.t("profile.unknown").to_string(),
+        };
+        let mut page = Page::new()
+            .title(format!("{} {}", member.rank.emoji(), member.ign))
+            .description(status)
+            .field(fmt.t("profile.rank"), fmt.t(member.rank.label_key()), true);
+
+        if !member.country.is_empty() {
+            let name = display_name(&member.country, fmt.language);
+            let flag = country_flag(&member.country);
+            let country = if flag.is_empty() {
+                name
+            } else {
+                format!("{} {}", flag, name)
+            };
+            page = page.field(fmt.t("profile.country"), country, true);
+        }
+        if !member.timezone.is_empty() {
+            let timezone = match utc_offset(&member.timezone) {
+                Some(offset) => fmt.tf(
+                    "profile.local_time",
+                    &[
+                        &member.timezone,
+                        &self.at.with_timezone(&offset).format("%H:%M"),
+                    ],
+                ),
+                None => member.timezone.clone(),
+            };
+            page = page.field(fmt.t("profile.timezone"), timezone, true);
+        }
+        if let Some(joined) = member.joined {
+            let since = joined
+                .and_hms_opt(0, 0, 0)
+                .map(|start| format!(" (<t:{}:R>)", start.and_utc().timestamp()))
+                .unwrap_or_default();
+            page = page.field(
+                fmt.t("profile.joined"),
+                format!("{}{}", fmt.date(joined), since),
+                true,
+            );
+        }
+        if !member.squad.is_empty() {
+            let squad = if self.lead {
+                fmt.tf("profile.squad_lead", &[&member.squad])
+            } else {
+                member.squad.clone()
+            };
+            page = page.field(fmt.t("profile.squad"), squad, true);
+        }
+        let discord = match self.discord_id {
+            Some(id) => format!("<@{}>", id),
+            None => member.discord_tag.clone(),
+        };
+        if !discord.is_empty() {
+            page = page.field(fmt.t("profile.discord"), discord, true);
+        }
+        for field in &self.fields {
+            if let Some(value) = member.custom.get(&field.key) {
+                page = page.field(&field.label, custom_value(field.kind, value, fmt), true);
+            }
+        }
+
+        if !member.skills.is_empty() {
+            let skills = member
+                .skills
+                .iter()
+                .map(|skill| format!("`{}`", skill))
+                .collect::<Vec<_>>()
+                .join(" ");
+            page = page.field(fmt.t("profile.skills"), skills, false);
+        }
+        if !member.base.is_empty() {
+            page = page.field(fmt.t("profile.base"), &member.base, false);
+        }
+        if !member.about.is_empty() {
+            page = page.field(fmt.t("profile.about"), &member.about, false);
+        }
+
+        if let Some(stats) = &self.stats {
+            let lines = main_stats(stats, fmt);
+            if !lines.is_empty() {
+                page = page.field(fmt.t("profile.stats"), lines.join("\n"), false);
+            }
+        }
+        page
+    }
+}
+
+/// Money, playtime, kills and deaths, one line each, for profiles and
+/// applications.
+fn main_stats(stats: &PlayerStats, fmt: &Format) -> Vec<String> {
+    [
+        ("💰", "stats.money", stats.money.map(|v| fmt.money(v))),
+        (
+            "🕒",
+            "stats.playtime",
+            stats.playtime_ms.map(|v| fmt.duration_ms(v)),
+        ),
+        ("⚔️", "stats.kills", stats.kills.map(|v| fmt.number(v))),
+        ("💀", "stats.deaths", stats.deaths.map(|v| fmt.number(v))),
+    ]
+    .into_iter()
+    .filter_map(|(emoji, label, value)| {
+        value.map(|v| format!("{} **{}:** {}", emoji, fmt.t(label), v))
+    })
+    .collect()
+}
+
+impl Render for ApplicationView {
+    fn render(&self, fmt: &Format) -> Page {
+        let application = &self.application;
+        let reviewer = application.reviewed_by.unwrap_or_default();
+        let status = match application.state {
+            ApplicationState::Pending => fmt.tf(
+                "application.pending",
+                &[&format!(
+                    "<t:{}:R>",
+                    application.expires(self.expiry_days).timestamp()
+                )],
+            ),
+            ApplicationState::Accepted => fmt.tf("application.accepted_by", &[&reviewer]),
+            ApplicationState::Rejected => fmt.tf("application.rejected_by", &[&reviewer]),
+            ApplicationState::Expired => fmt.t("application.expired").to_string(),
+        };
+        let color = match application.state {
+            ApplicationState::Rejected | ApplicationState::Expired => EMBED_COLOR_ERROR_ALT,
+            _ => EMBED_COLOR_ACCENT,
+        };
+        let mut page = Page::new()
+            .title(fmt.tf("application.title", &[&application.id, &application.ign]))
+            .description(format!("{} {}", application.state.emoji(), status))
+            .field(
+                fmt.t("application.applicant"),
+                format!("<@{}> ({})", application.user_id, application.user_tag),
+                true,
+            )
+            .field(
+                fmt.t("profile.country"),
+                format!(
+                    "{} {}",
+                    country_flag(&application.country),
+                    display_name(&application.country, fmt.language)
+                )
+                .trim()
+                .to_string(),
+                true,
+            )
+            .field(
+                fmt.t("application.submitted_at"),
+                fmt.datetime(application.submitted),
+                true,
+            );
+        if !application.skills.is_empty() {
+            let skills = application
+                .skills
+                .iter()
+                .map(|skill| format!("`{}`", skill))
+                .collect::<Vec<_>>()
+                .join(" ");
+            page = page.field(fmt.t("profile.skills"), skills, false);
+        }
+        if !application.about.is_empty() {
+            page = page.field(fmt.t("profile.about"), &application.about, false);
+        }
+        let stats = self
+            .stats
+            .as_ref()
+            .map(|stats| main_stats(stats, fmt))
+            .filter(|lines| !lines.is_empty())
+            .map(|lines| lines.join("\n"))
+            .unwrap_or_else(|| fmt.t("application.no_stats").to_string());
+        page.field(fmt.t("profile.stats"), stats, false)
+            .color(color)
+    }
+}
+
+impl TeamStatus {
+    /// Members under their rank or squad heading, as the layout shows them.
+    fn headed_groups<'a>(
+        &self,
+        team: &'a crate::team::Team,
+        fmt: &Format,
+    ) -> Vec<(String, Vec<&'a TeamMember>)> {
+        self.groups(team)
+            .into_iter()
+            .map(|(group, members)| {
+                let heading = match group {
+                    Group::Rank(rank) => format!("{} {}", rank.emoji(), fmt.t(rank.label_key())),
+                    Group::Squad(Some(squad)) => format!("🛡️ {}", squad.name),
+                    Group::Squad(None) => fmt.t("squad.no_squad").to_string(),
+                };
+                (heading, members)
+            })
+            .collect()
+    }
+
+    /// `⭐ 🟢 **Steve** · 🇳🇱 · PvP, Building`
+    fn compact_line(&self, team: &crate::team::Team, member: &TeamMember, fmt: &Format) -> String {
+        let mut line = String::new();
+        match self.presence_of(&member.ign) {
+            Some(Presence::Online { .. }) => line.push_str("🟢 "),
+            Some(_) => line.push_str("🔴 "),
+            None => {}
+        }
+        if team.is_lead(member) {
+            line.push_str("⭐ ");
+        }
+        line.push_str(&format!("**{}**", member.ign));
+        if let Some(Presence::Online {
+            location: Some(location),
+        }) = self.presence_of(&member.ign)
+        {
+            line.push_str(&format!(" — {}", location));
+        }
+        for detail in &self.config.details {
+            let value = match detail {
+                // The heading already says which squad
+                Detail::Squad if self.config.group_by_squad => None,
+                Detail::Country => {
+                    let flag = country_flag(&member.country);
+                    if flag.is_empty() {
+                        detail.value(member, fmt)
+                    } else {
+                        Some(flag)
+                    }
+                }
+                Detail::About | Detail::Base => detail
+                    .value(member, fmt)
+                    .map(|v| truncate_chars(&v.replace('\n', " "), 40, "…")),
+                _ => detail.value(member, fmt),
+            };
+            if let Some(value) = value {
+                line.push_str(&format!(" · {}", value));
+            }
+        }
+        line
+    }
+
+    /// `Steve    [ 🟢 - overworld]`, as the team post has always shown it.
+    fn detailed_name(&self, team: &crate::team::Team, member: &TeamMember) -> String {
+        let ign = if team.is_lead(member) {
+            format!("⭐ {}", member.ign)
+        } else {
+            member.ign.clone()
+        };
+        match self.presence_of(&member.ign) {
+            Some(Presence::Online {
+                location: Some(location),
+            }) => format!("{}    [ 🟢 - {}]", ign, location),
+            Some(Presence::Online { location: None }) => format!("{}    [ 🟢 ]", ign),
+            Some(_) => format!("{}    [ 🔴 ]", ign),
+            None => ign,
+        }
+    }
+}
+
+impl Render for TeamStatus {
+    fn render(&self, fmt: &Format) -> Page {
+        let team = self.sorted_team();
+        let mut page = Page::new().title(format!("👥 {}", team.name));
+        if let Some(at) = self.at {
+            page = page.footer(fmt.tf("team.last_updated", &[&fmt.datetime(at)]));
+        }
+        if team.members.is_empty() {
+            return page.description(fmt.t("team.no_members"));
+        }
+
+        let groups = self.headed_groups(&team, fmt);
+        if self.config.layout == Layout::Detailed {
+            for (heading, members) in groups {
+                if !page.fields.is_empty() {
+                    page = page.field(ZWSP, ZWSP, false);
+                }
+                page = page.field(
+                    format!("{} ({})", heading, fmt.number(members.len() as i64)),
+                    ZWSP,
+                    false,
+                );
+                for member in members {
+                    page = page.field(
+                        self.detailed_name(&team, member),
+                        member.detail_lines(&self.config.details, fmt),
+                        false,
+                    );
+                }
+            }
+            return page;
+        }
+
+        let blocks: Vec<String> = groups
+            .into_iter()
+            .map(|(heading, members)| {
+                let lines: Vec<String> = members
+                    .into_iter()
+                    .map(|m| self.compact_line(&team, m, fmt))
+                    .collect();
+                format!(
+                    "**{} ({})**\n{}",
+                    heading,
+                    fmt.number(lines.len() as i64),
+                    lines.join("\n")
+                )
+            })
+            .collect();
+        if blocks.is_empty() {
+            page.description(fmt.t("team.nobody_online"))
+        } else {
+            page.description(blocks.join("\n\n"))
+        }
+    }
+}
+
+impl Render for CountryBreakdown {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.tf("countries.title", &[&self.team_name]));
+        if self.countries.is_empty() {
+            return page.description(fmt.t("team.no_members"));
+        }
+        let lines: Vec<String> = self
+            .countries
+            .iter()
+            .map(|(country, igns)| {
+                let (flag, name) = if country.is_empty() {
+                    ("❔".to_string(), fmt.t("common.unknown").to_string())
+                } else {
+                    let flag = country_flag(country);
+                    let flag = if flag.is_empty() {
+                        "🏳️".into()
+                    } else {
+                        flag
+                    };
+                    (flag, display_name(country, fmt.language))
+                };
+                format!(
+                    "{} **{}** — {}\n└ {}",
+                    flag,
+                    name,
+                    fmt.number(igns.len() as i64),
+                    igns.join(", ")
+                )
+            })
+            .collect();
+        let known = self.countries.iter().filter(|(c, _)| !c.is_empty()).count();
+        let members: usize = self.countries.iter().map(|(_, igns)| igns.len()).sum();
+        page.description(lines.join("\n")).footer(fmt.tf(
+            "countries.footer",
+            &[&fmt.number(known as i64), &fmt.number(members as i64)],
+        ))
+    }
+}
+
+impl Render for RosterDiff {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.t("import.preview_title"));
+        if self.is_empty() {
+            return page.description(fmt.t("import.no_changes"));
+        }
+        let named = |ign: &str, country: &str| {
+            let flag = country_flag(country);
+            if flag.is_empty() {
+                format!("**{}**", ign)
+            } else {
+                format!("{} **{}**", flag, ign)
+            }
+        };
+        let mut page = page.description(fmt.tf(
+            "import.summary",
+            &[
+                &fmt.number(self.added.len() as i64),
+                &fmt.number(self.updated.len() as i64),
+                &fmt.number(self.removed.len() as i64),
+            ],
+        ));
+        if !self.added.is_empty() {
+            let lines: Vec<String> = self
+                .added
+                .iter()
+                .map(|m| {
+                    format!(
+                        "{} — {}",
+                        named(&m.ign, &m.country),
+                        fmt.t(m.rank.label_key())
+                    )
+                })
+                .collect();
+            page = page.field(fmt.t("import.added"), lines.join("\n"), false);
+        }
+        if !self.updated.is_empty() {
+            let lines: Vec<String> = self
+                .updated
+                .iter()
+                .map(|(before, after)| {
+                    let fields: Vec<&str> = changed_fields(before, after)
+                        .into_iter()
+                        .map(|key| fmt.t(key))
+                        .collect();
+                    format!(
+                        "{} — {}",
+                        named(&after.ign, &after.country),
+                        fields.join(", ")
+                    )
+                })
+                .collect();
+            page = page.field(fmt.t("import.updated"), lines.join("\n"), false);
+        }
+        if !self.removed.is_empty() {
+            let lines: Vec<String> = self
+                .removed
+                .iter()
+                .map(|m| named(&m.ign, &m.country))
+                .collect();
+            page = page.field(fmt.t("import.removed"), lines.join("\n"), false);
+        }
+        page.footer(fmt.tf("import.footer", &[&IMPORT_EXPIRY_MINUTES]))
+    }
+}
+
+/// `/team-audit` lists at most this many entries; narrow it with `ign` or `days`.
+const AUDIT_ENTRY_LIMIT: usize = 50;
+
+/// One detail of a member as shown in the audit log, `—` when empty.
+fn audit_value(member: &TeamMember, key: &str, fmt: &Format) -> String {
+    let value = match key {
+        "profile.ign" => member.ign.clone(),
+        "profile.country" => display_name(&member.country, fmt.language),
+        "profile.skills" => member.skills.join(", "),
+        "profile.rank" => fmt.t(member.rank.label_key()).to_string(),
+        "profile.about" => truncate_chars(&member.about, 100, "…"),
+        "profile.discord" => member.discord_tag.clone(),
+        "profile.timezone" => member.timezone.clone(),
+        "profile.joined" => member.joined.map(|d| fmt.date(d)).unwrap_or_default(),
+        "profile.base" => member.base.clone(),
+        "profile.squad" => member.squad.clone(),
+        _ => String::new(),
+    };
+    if value.trim().is_empty() {
+        "—".to_string()
+    } else {
+        value
+    }
+}
+
+/// What an entry changed, with the values before and after.
+fn audit_change(change: &Change, fmt: &Format) -> String {
+    match change {
+        Change::Name { before, after } => fmt.tf("audit.renamed", &[before, after]),
+        Change::Member {
+            before: None,
+            after: Some(after),
+        } => fmt.tf("audit.member_added", &[&after.ign]),
+        Change::Member {
+            before: Some(before),
+            after: None,
+        } => fmt.tf("audit.member_removed", &[&before.ign]),
+        Change::Member {
+            before: Some(before),
+            after: Some(after),
+        } => {
+            let mut lines = vec![fmt.tf("audit.member_updated", &[&after.ign])];
+            for key in changed_fields(before, after) {
+                if key == "profile.custom" {
+                    let keys: std::collections::BTreeSet<&String> =
+                        before.custom.keys().chain(after.custom.keys()).collect();
+                    for field in keys {
+                        let value = |m: &TeamMember| {
+                            m.custom.get(field).cloned().unwrap_or_else(|| "—".into())
+                        };
+                        if before.custom.get(field) != after.custom.get(field) {
+                            lines.push(format!(
+                                "└ {}: {} → {}",
+                                field,
+                                value(before),
+                                value(after)
+                            ));
+                        }
+                    }
+                    continue;
+                }
+                lines.push(format!(
+                    "└ {}: {} → {}",
+                    fmt.t(key),
+                    audit_value(before, key, fmt),
+                    audit_value(after, key, fmt)
+                ));
+            }
+            lines.join("\n")
+        }
+        Change::Member { .. } => String::new(),
+        Change::Field {
+            before: None,
+            after: Some(after),
+        } => fmt.tf(
+            "audit.field_added",
+            &[&after.label, &fmt.t(after.kind.label_key())],
+        ),
+        Change::Field {
+            before: Some(before),
+            after: None,
+        } => fmt.tf("audit.field_removed", &[&before.label]),
+        Change::Field {
+            before: Some(before),
+            after: Some(after),
+        } => fmt.tf(
+            "audit.field_changed",
+            &[
+                &before.label,
+                &fmt.t(before.kind.label_key()),
+                &after.label,
+                &fmt.t(after.kind.label_key()),
+            ],
+        ),
+        Change::Field { .. } => String::new(),
+        Change::Squad {
+            before: None,
+            after: Some(after),
+        } => fmt.tf("audit.squad_added", &[&after.name]),
+        Change::Squad {
+            before: Some(before),
+            after: None,
+        } => fmt.tf("audit.squad_removed", &[&before.name]),
+        Change::Squad {
+            before: Some(before),
+            after: Some(after),
+        } => {
+            let leads = |squad: &Squad| {
+                if squad.leads.is_empty() {
+                    "—".to_string()
+                } else {
+                    squad.leads.join(", ")
+                }
+            };
+            fmt.tf(
+                "audit.squad_leads",
+                &[&after.name, &leads(before), &leads(after)],
+            )
+        }
+        Change::Squad { .. } => String::new(),
+    }
+}
+
+fn audit_entry(entry: &AuditEntry, fmt: &Format) -> String {
+    format!(
+        "**{}** · <@{}>\n{}",
+        fmt.datetime(entry.at),
+        entry.by,
+        audit_change(&entry.change, fmt)
+    )
+}
+
+/// A single change, as mirrored to the moderation channel.
+impl Render for AuditEntry {
+    fn render(&self, fmt: &Format) -> Page {
+        Page::new()
+            .title(fmt.t("audit.title"))
+            .description(audit_entry(self, fmt))
+    }
+}
+
+impl Render for AuditView {
+    fn render(&self, fmt: &Format) -> Page {
+        let title = match &self.ign {
+            Some(ign) => fmt.tf("audit.title_member", &[ign]),
+            None => fmt.t("audit.title").to_string(),
+        };
+        let page = Page::new().title(title);
+        if self.entries.is_empty() {
+            return page.description(fmt.tf("audit.empty", &[&fmt.number(self.days)]));
+        }
+        let entries: Vec<String> = self
+            .entries
+            .iter()
+            .take(AUDIT_ENTRY_LIMIT)
+            .map(|entry| audit_entry(entry, fmt))
+            .collect();
+        let footer = if self.entries.len() > AUDIT_ENTRY_LIMIT {
+            fmt.tf(
+                "audit.footer_limited",
+                &[
+                    &fmt.number(AUDIT_ENTRY_LIMIT as i64),
+                    &fmt.number(self.entries.len() as i64),
+                    &fmt.number(self.days),
+                ],
+            )
+        } else {
+            fmt.tf(
+                "audit.footer",
+                &[
+                    &fmt.number(self.entries.len() as i64),
+                    &fmt.number(self.days),
+                ],
+            )
+        };
+        page.description(entries.join("\n\n")).footer(footer)
+    }
+}
+
+fn change_lines(changes: &[Change], fmt: &Format) -> String {
+    changes
+        .iter()
+        .map(|change| audit_change(change, fmt))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl Render for RestorePreview {
+    fn render(&self, fmt: &Format) -> Page {
+        let page = Page::new().title(fmt.tf("restore.title", &[&self.snapshot.version]));
+        let intro = fmt.tf(
+            "restore.intro",
+            &[&fmt.datetime(self.snapshot.at), &self.snapshot.by],
+        );
+        page.description(format!("{}\n\n{}", intro, change_lines(&self.changes, fmt)))
+            .footer(fmt.t("restore.footer"))
+    }
+}
+
+impl Render for Undone {
+    fn render(&self, fmt: &Format) -> Page {
+        let intro = fmt.tf(
+            "undo.intro",
+            &[&fmt.datetime(self.snapshot.at), &self.snapshot.by],
+        );
+        Page::new()
+            .title(fmt.t("undo.title"))
+            .description(format!("{}\n\n{}", intro, change_lines(&self.changes, fmt)))
+            .footer(fmt.tf("undo.footer", &[&self.snapshot.version]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::Rank;
+
+    fn fixture(json: &str) -> Value {
+        serde_json::from_str(json).expect("fixture is valid JSON")
+    }
+
+    #[test]
+    fn lookup() {
+        let json = fixture(include_str!("../tests/fixtures/lookup.json"));
+        insta::assert_snapshot!(render_or_message(
+            PlayerLookup::from_json(&json, "Steve"),
+            &json,
+            &Format::default()
+        ));
+    }
+
+    #[test]
+    fn lookup_message() {
+        let json = fixture(include_str!("../tests/fixtures/lookup_not_found.json"));
+        insta::assert_snapshot!(render_or_message(
+            PlayerLookup::from_json(&json, "Nobody"),
+            &json,
+            &Format::default()
+        ));
+    }
+
+    #[test]
+    fn stats() {
+        let json = fixture(include_str!("../tests/fixtures/stats.json"));
+        insta::assert_snapshot!(PlayerStats::from_json(&json, "Steve")
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn stats_with_numeric_values() {
+        let json = fixture(include_str!("../tests/fixtures/stats_numeric.json"));
+        insta::assert_snapshot!(PlayerStats::from_json(&json, "Alex")
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn leaderboard() {
+        let json = fixture(include_str!("../tests/fixtures/leaderboard_playtime.json"));
+        insta::assert_snapshot!(Leaderboard::from_json(&json, "playtime", 1)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn leaderboard_empty() {
+        let json = fixture(include_str!("../tests/fixtures/empty_result.json"));
+        insta::assert_snapshot!(Leaderboard::from_json(&json, "money", 7)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn auction_list() {
+        let json = fixture(include_str!("../tests/fixtures/auction_list.json"));
+        insta::assert_snapshot!(AuctionPage::from_json(&json, 2)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn auction_list_empty() {
+        let json = fixture(include_str!("../tests/fixtures/empty_result.json"));
+        insta::assert_snapshot!(AuctionPage::from_json(&json, 1)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn transactions() {
+        let json = fixture(include_str!("../tests/fixtures/transactions.json"));
+        insta::assert_snapshot!(TransactionPage::from_json(&json, 1)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn transactions_empty() {
+        let json = fixture(include_str!("../tests/fixtures/empty_result.json"));
+        insta::assert_snapshot!(TransactionPage::from_json(&json, 1)
+            .unwrap()
+            .render(&Format::default()));
+    }
+
+    #[test]
+    fn limits_are_applied_per_character() {
+        let page = Page::new()
+            .title("🏆".repeat(300))
+            .description("💎 diamond\n".repeat(1000))
+            .field("", "", true);
+        let page = (0..30).fold(page, |p, i| {
+            p.field(format!("f{}", i), "é".repeat(2000), false)
+        });
+        let limited = page.limited();
+
+        assert_eq!(limited.title.as_ref().unwrap().chars().count(), TITLE_LIMIT);
+        assert!(limited.description.as_ref().unwrap().chars().count() <= DESCRIPTION_LIMIT);
+        assert!(limited.fields.len() <= FIELD_COUNT_LIMIT);
+        assert_eq!(limited.fields[0].name, ZWSP);
+        assert!(limited
+            .fields
+            .iter()
+            .all(|f| f.value.chars().count() <= FIELD_VALUE_LIMIT));
+        assert!(limited.total_chars() <= EMBED_TOTAL_LIMIT);
+    }
+
+    #[test]
+    fn split_text_keeps_entries_whole() {
+        let entries: Vec<String> = (1..=40)
+            .map(|i| {
+                format!(
+                    "**{}**. 💎 Diamond Block ×64 - **$1.000**\n└ *Seller: Ünïcødé*",
+                    i
+                )
+            })
+            .collect();
+        let text = entries.join("\n\n");
+        let chunks = split_text(&text, 500);
+
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= 500));
+        assert_eq!(chunks.join("\n\n"), text);
+    }
+
+    #[test]
+    fn split_text_never_cuts_inside_a_character() {
+        let chunks = split_text(&"🏆".repeat(10), 3);
+        assert_eq!(chunks, vec!["🏆🏆🏆", "🏆🏆🏆", "🏆🏆🏆", "🏆"]);
+    }
+
+    #[test]
+    fn split_pages_respect_field_and_total_limits() {
+        let page = (1..=60).fold(
+            Page::new().title("👥 Team").footer("Last updated"),
+            |page, i| page.field(format!("Player{}", i), "Country: 🇳🇱\n".repeat(20), false),
+        );
+        let pages = page.split();
+
+        assert!(pages.len() > 2);
+        for (i, part) in pages.iter().enumerate() {
+            assert!(part.fields.len() <= FIELD_COUNT_LIMIT);
+            assert!(part.total_chars() <= EMBED_TOTAL_LIMIT);
+            assert_eq!(part.footer.is_some(), i == pages.len() - 1);
+        }
+        let fields: usize = pages.iter().map(|p| p.fields.len()).sum();
+        assert_eq!(fields, 60);
+        assert_eq!(
+            pages[0].title.as_deref(),
+            Some(format!("👥 Team (1/{})", pages.len()).as_str())
+        );
+    }
+
+    #[test]
+    fn split_pages_leave_room_for_the_part_suffix() {
+        // Five of these plus the title and footer fill a page exactly
+        let name = "N".repeat(175);
+        let value = "v".repeat(FIELD_VALUE_LIMIT);
+        let page = (0..6).fold(Page::new().title("T").footer("abcd"), |page, _| {
+            page.field(name.clone(), value.clone(), false)
+        });
+        let pages = page.split();
+
+        assert_eq!(pages.len(), 2);
+        assert!(pages.iter().all(|p| p.total_chars() <= EMBED_TOTAL_LIMIT));
+
+        let long = Page::new()
+            .title("T".repeat(TITLE_LIMIT))
+            .description("a\n\n".repeat(2000));
+        for part in long.split() {
+            let title = part.title.unwrap();
+            assert!(title.chars().count() <= TITLE_LIMIT);
+            assert!(title.ends_with("/2)"));
+        }
+    }
+
+    #[test]
+    fn stats_in_reader_format() {
+        let json = fixture(include_str!("../tests/fixtures/stats.json"));
+        let fmt = Format {
+            numbers: crate::locale::NumberStyle::Compact,
+            language: crate::locale::Language::Nl,
+        };
+        insta::assert_snapshot!(PlayerStats::from_json(&json, "Steve").unwrap().render(&fmt));
+    }
+
+    #[test]
+    fn team_profile() {
+        use crate::team::{CustomField, Rank, TeamMember};
+        use chrono::TimeZone;
+
+        let json = fixture(include_str!("../tests/fixtures/stats.json"));
+        let member = TeamMember {
+            ign: "Steve".into(),
+            country: "NL".into(),
+            skills: vec!["PvP".into(), "Redstone".into()],
+            about: "Likes long walks through the nether".into(),
+            rank: Rank::Admin,
+            timezone: "UTC+2".into(),
+            joined: chrono::NaiveDate::from_ymd_opt(2024, 3, 1),
+            base: "Spawn shop at -1200 / 340".into(),
+            squad: "Builders".into(),
+            custom: [
+                ("farms".to_string(), "12".to_string()),
+                ("trusted".to_string(), "yes".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            ..TeamMember::default()
+        };
+        let profile = Profile {
+            member,
+            fields: vec![
+                CustomField {
+                    key: "farms".into(),
+                    label: "Farms".into(),
+                    kind: FieldKind::Number,
+                },
+                CustomField {
+                    key: "trusted".into(),
+                    label: "Trusted".into(),
+                    kind: FieldKind::Toggle,
+                },
+            ],
+            presence: Presence::Online {
+                location: Some("overworld".into()),
+            },
+            stats: PlayerStats::from_json(&json, "Steve"),
+            discord_id: Some(1234),
+            lead: true,
+            at: chrono::Utc.with_ymd_and_hms(2025, 6, 1, 12, 30, 0).unwrap(),
+        };
+        insta::assert_snapshot!(profile.render(&Format::default()));
+    }
+
+    #[test]
+    fn team_countries_in_dutch() {
+        use crate::locale::Language;
+        use crate::team::{Team, TeamMember};
+
+        let member = |ign: &str, country: &str| TeamMember {
+            ign: ign.into(),
+            country: country.into(),
+            ..TeamMember::default()
+        };
+        let team = Team {
+            name: "Donut Bakers".into(),
+            members: vec![
+                member("steve", "DE"),
+                member("Alex", "NL"),
+                member("Notch", ""),
+                member("Bob", "NL"),
+                member("Herobrine", "Narnia"),
+            ],
+            custom_fields: vec![],
+            squads: vec![],
+        };
+        let fmt = Format {
+            language: Language::Nl,
+            ..Format::default()
+        };
+        insta::assert_snapshot!(CountryBreakdown::of(&team).render(&fmt));
+    }
+
+    #[test]
+    fn import_preview() {
+        use crate::team::{Rank, TeamMember};
+
+        let member = |ign: &str, country: &str| TeamMember {
+            ign: ign.into(),
+            country: country.into(),
+            ..TeamMember::default()
+        };
+        let current = vec![
+            member("Steve", "NL"),
+            member("Alex", "DE"),
+            member("Notch", ""),
+        ];
+        let mut steve = current[0].clone();
+        steve.skills = vec!["PvP".into()];
+        steve.rank = Rank::Admin;
+        let incoming = vec![steve, current[1].clone(), member("Jeb", "SE")];
+        insta::assert_snapshot!(RosterDiff::between(&current, &incoming).render(&Format::default()));
+    }
+
+    #[test]
+    fn audit_log() {
+        use crate::team::{CustomField, Rank};
+        use chrono::TimeZone;
+
+        let at = chrono::Utc.with_ymd_and_hms(2024, 5, 1, 18, 30, 0).unwrap();
+        let steve = TeamMember {
+            ign: "Steve".into(),
+            country: "NL".into(),
+            skills: vec!["PvP".into()],
+            ..TeamMember::default()
+        };
+        let mut promoted = steve.clone();
+        promoted.rank = Rank::Admin;
+        promoted.skills.push("Building".into());
+        promoted.custom.insert("farms".into(), "3".into());
+        let changes = vec![
+            Change::Member {
+                before: Some(steve.clone()),
+                after: Some(promoted),
+            },
+            Change::Member {
+                before: None,
+                after: Some(steve.clone()),
+            },
+            Change::Name {
+                before: "Donut Bakers".into(),
+                after: "Donut Kings".into(),
+            },
+            Change::Field {
+                before: None,
+                after: Some(CustomField {
+                    key: "farms".into(),
+                    label: "Farms".into(),
+                    kind: FieldKind::Number,
+                }),
+            },
+        ];
+        let view = AuditView {
+            entries: changes
+                .into_iter()
+                .enumerate()
+                .map(|(i, change)| AuditEntry {
+                    id: 4 - i as u64,
+                    at,
+                    by: 1234,
+                    change,
+                })
+                .collect(),
+            ign: None,
+            days: 30,
+        };
+        insta::assert_snapshot!(view.render(&Format::default()));
+    }
+
+    #[test]
+    fn restore_preview_in_dutch() {
+        use crate::history::Snapshot;
+        use crate::locale::Language;
+        use crate::team::Team;
+        use chrono::TimeZone;
+
+        let steve = TeamMember {
+            ign: "Steve".into(),
+            country: "NL".into(),
+            about: "Builds the farms".into(),
+            squad: "Builders".into(),
+            ..TeamMember::default()
+        };
+        let old = Team {
+            name: "Donut Bakers".into(),
+            members: vec![steve.clone()],
+            custom_fields: vec![],
+            squads: vec![Squad {
+                name: "Builders".into(),
+                leads: vec!["Steve".into()],
+            }],
+        };
+        let mut current = old.clone();
+        current.members[0].country = "BE".into();
+        current.squads[0].leads.clear();
+        current.members.push(TeamMember {
+            ign: "Alex".into(),
+            ..TeamMember::default()
+        });
+        let preview = RestorePreview {
+            changes: Change::between(&current, &old),
+            snapshot: Snapshot {
+                version: 7,
+                at: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 18, 30, 0).unwrap(),
+                by: 1234,
+                team: old,
+            },
+        };
+        let fmt = Format {
+            language: Language::Nl,
+            ..Format::default()
+        };
+        insta::assert_snapshot!(preview.render(&fmt));
+    }
+
+    fn status_team() -> crate::team::Team {
+        let member = |ign: &str, rank: Rank, country: &str, squad: &str| TeamMember {
+            ign: ign.into(),
+            rank,
+            country: country.into(),
+            skills: vec!["PvP".into()],
+            squad: squad.into(),
+            ..TeamMember::default()
+        };
+        crate::team::Team {
+            name: "Donut Bakers".into(),
+            members: vec![
+                member("alex", Rank::Member, "BE", ""),
+                member("Steve", Rank::Owner, "NL", "Builders"),
+                member("Notch", Rank::Member, "", "Builders"),
+            ],
+            custom_fields: vec![],
+            squads: vec![Squad {
+                name: "Builders".into(),
+                leads: vec!["Notch".into()],
+            }],
+        }
+    }
+
+    fn status_presence() -> std::collections::HashMap<String, Presence> {
+        [
+            (
+                "steve".to_string(),
+                Presence::Online {
+                    location: Some("overworld".into()),
+                },
+            ),
+            ("notch".to_string(), Presence::Online { location: None }),
+            ("alex".to_string(), Presence::Offline),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    #[test]
+    fn detailed_team_list() {
+        use crate::status::{StatusConfig, TeamStatus};
+
+        let status = TeamStatus {
+            team: status_team(),
+            presence: Default::default(),
+            config: StatusConfig {
+                layout: Layout::Detailed,
+                details: Detail::DEFAULT.to_vec(),
+                group_by_squad: false,
+                image: false,
+            },
+            at: None,
+        };
+        insta::assert_snapshot!(status.render(&Format::default()));
+    }
+
+    #[test]
+    fn compact_team_status() {
+        use crate::status::{StatusConfig, TeamStatus};
+        use chrono::TimeZone;
+
+        let status = TeamStatus {
+            team: status_team(),
+            presence: status_presence(),
+            config: StatusConfig {
+                layout: Layout::Compact,
+                details: vec![Detail::Country, Detail::Skills, Detail::Squad],
+                group_by_squad: false,
+                image: false,
+            },
+            at: Some(chrono::Utc.with_ymd_and_hms(2024, 5, 1, 18, 30, 0).unwrap()),
+        };
+        insta::assert_snapshot!(status.render(&Format::default()));
+    }
+
+    #[test]
+    fn online_only_by_squad() {
+        use crate::status::{StatusConfig, TeamStatus};
+
+        let status = TeamStatus {
+            team: status_team(),
+            presence: status_presence(),
+            config: StatusConfig {
+                layout: Layout::OnlineOnly,
+                details: vec![Detail::Country, Detail::Squad],
+                group_by_squad: true,
+                image: false,
+            },
+            at: None,
+        };
+        insta::assert_snapshot!(status.render(&Format::default()));
+    }
+
+    #[test]
+    fn pending_application() {
+        use crate::applications::Application;
+        use chrono::TimeZone;
+
+        let application = Application {
+            id: 3,
+            user_id: 1234,
+            user_tag: "steve#0001".into(),
+            ign: "Steve".into(),
+            country: "NL".into(),
+            skills: vec!["PvP".into(), "Farming".into()],
+            about: "Looking for a team to grind with.".into(),
+            submitted: chrono::Utc.with_ymd_and_hms(2024, 5, 1, 18, 30, 0).unwrap(),
+            state: ApplicationState::Pending,
+            reviewed_by: None,
+            decided: None,
+            locale: String::new(),
+            channel_id: None,
+            message_id: None,
+        };
+        let view = ApplicationView {
+            application,
+            stats: Some(PlayerStats {
+                player: "Steve".into(),
+                money: Some(1_250_000),
+                kills: Some(42),
+                deaths: Some(7),
+                ..PlayerStats::default()
+            }),
+            expiry_days: 7,
+        };
+        insta::assert_snapshot!(view.render(&Format::default()));
+    }
+
+    #[test]
+    fn stats_with_derived_values() {
+        let view = StatsView {
+            stats: PlayerStats {
+                player: "Steve".into(),
+                shards: Some(25),
+                money_made_from_sell: Some(400_000),
+                money_spent_on_shop: Some(150_000),
+                playtime_ms: Some(36_000_000),
+                kills: Some(42),
+                deaths: Some(7),
+                mobs_killed: Some(1_200),
+                placed_blocks: Some(5_000),
+                broken_blocks: Some(2_500),
+                ..PlayerStats::default()
+            },
+            percentiles: vec![(Derived::KdRatio, 91.5), (Derived::ShardsPerHour, 20.0)],
+        };
+        insta::assert_snapshot!(view.render(&Format::default()));
+    }
+
+    #[test]
+    fn comparison_marks_row_leaders() {
+        let comparison = Comparison {
+            players: vec![
+                PlayerStats {
+                    player: "Steve".into(),
+                    money: Some(1_250_000),
+                    shards: Some(300),
+                    playtime_ms: Some(36_000_000),
+                    kills: Some(42),
+                    deaths: Some(7),
+                    placed_blocks: Some(5_000),
+                    broken_blocks: Some(2_500),
+                    ..PlayerStats::default()
+                },
+                PlayerStats {
+                    player: "Alex".into(),
+                    money: Some(90_000),
+                    shards: Some(300),
+                    playtime_ms: Some(3_600_000),
+                    kills: Some(3),
+                    deaths: Some(0),
+                    mobs_killed: Some(12),
+                    ..PlayerStats::default()
+                },
+            ],
+            missing: vec!["Herobrine".into()],
+        };
+        insta::assert_snapshot!(comparison.render(&Format::default()));
+    }
+
+    #[test]
+    fn goal_progress_and_announcements() {
+        use crate::goals::{Goal, GoalStat};
+        use chrono::TimeZone;
+
+        let set_at = chrono::Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap();
+        let goal = |stat, target| Goal {
+            user_id: 42,
+            ign: "Steve".into(),
+            stat,
+            target,
+            set_at,
+            reached: None,
+            locale: "en-US".into(),
+        };
+        let progress = GoalProgress {
+            ign: "Steve".into(),
+            goals: vec![
+                goal(GoalStat::Money, 10_000_000),
+                goal(GoalStat::Playtime, 500 * 3_600_000),
+                goal(GoalStat::MobsKilled, 1_000),
+            ],
+            stats: Some(PlayerStats {
+                player: "Steve".into(),
+                money: Some(2_500_000),
+                playtime_ms: Some(360_000_000),
+                ..PlayerStats::default()
+            }),
+        };
+        let reached = Announcement::Goal {
+            goal: goal(GoalStat::Money, 10_000_000),
+            value: 10_400_000,
+        };
+        let milestone = Announcement::Milestone {
+            ign: "Alex".into(),
+            stat: GoalStat::Kills,
+            threshold: 1_000,
+        };
+        let fmt = Format::default();
+        insta::assert_snapshot!(format!(
+            "{}\n\n{}\n\n{}",
+            progress.render(&fmt),
+            reached.render(&fmt),
+            milestone.render(&fmt)
+        ));
+    }
+
+    #[test]
+    fn transactions_in_dutch() {
+        let json = fixture(include_str!("../tests/fixtures/transactions.json"));
+        let fmt = Format {
+            language: crate::locale::Language::Nl,
+            ..Format::default()
+        };
+        insta::assert_snapshot!(TransactionPage::from_json(&json, 1).unwrap().render(&fmt));
+    }
+
+    #[test]
+    fn trader_profile_stays_within_field_limits() {
+        use crate::auction::Listing;
+
+        let long_name = "Netherite Sword of Very Long Names ".repeat(10);
+        let listing = Listing {
+            item_id: "minecraft:netherite_sword".into(),
+            display_name: long_name.clone(),
+            count: 1,
+            enchants: Default::default(),
+            price: 1_250_000,
+            seller: "Steve".into(),
+        };
+        let sale = SaleRecord {
+            item_key: "minecraft:netherite_sword".into(),
+            item_id: "minecraft:netherite_sword".into(),
+            name: long_name.clone(),
+            label: long_name,
+            count: 1,
+            price: 2_000_000,
+            seller: "Steve".into(),
+            buyer: Some("Alex".into()),
+            sold_at: 1_700_000_000,
+            ingested_at: false,
+        };
+        let profile = TraderProfile {
+            ign: "Steve".into(),
+            listings: vec![listing; 6],
+            sales: vec![sale; 6],
+            purchases: Vec::new(),
+        };
+        let page = profile.render(&Format::default()).limited();
+
+        assert!(page.fields[0].value.contains("**$1.250.000**"));
+        assert!(page
+            .fields
+            .iter()
+            .all(|f| f.value.chars().count() <= FIELD_VALUE_LIMIT));
+        assert!(page.total_chars() <= EMBED_TOTAL_LIMIT);
+    }
+
+    #[test]
+    fn digest_pages_stay_within_limits() {
+        use crate::digest::DigestPeriod;
+        use crate::market::MarketData;
+        use chrono::TimeZone;
+
+        let now = chrono::Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let label = "Enchanted Book (Sharpness V, Looting III, Unbreaking III) ".repeat(5);
+        let data = MarketData {
+            sales: (0..5)
+                .map(|i| SaleRecord {
+                    item_key: format!("minecraft:book|{}", i),
+                    item_id: "minecraft:enchanted_book".into(),
+                    name: label.clone(),
+                    label: label.clone(),
+                    count: 1,
+                    price: 1_000 + i,
+                    seller: "Steve".into(),
+                    buyer: Some("Alex".into()),
+                    sold_at: now.timestamp() - 60,
+                    ingested_at: false,
+                })
+                .collect(),
+        };
+        let digest = Digest::build(&data, DigestPeriod::Daily, Vec::new(), now);
+        let pages = digest.render(&Format::default()).split();
+
+        for page in &pages {
+            assert!(page
+                .fields
+                .iter()
+                .all(|f| f.value.chars().count() <= FIELD_VALUE_LIMIT));
+            assert!(page.total_chars() <= EMBED_TOTAL_LIMIT);
+        }
+        // Long labels spill over into a second field rather than being cut
+        assert!(pages.iter().map(|p| p.fields.len()).sum::<usize>() > 5);
+    }
+}
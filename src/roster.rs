@@ -0,0 +1,577 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fs,
+    io::{self},
+    path::PathBuf,
+};
+
+use crate::countries;
+use crate::locale::Format;
+use crate::team::{parse_date, parse_skills, Rank, Team, TeamMember};
+
+/// How long a `/team-import` preview can be confirmed.
+pub const IMPORT_EXPIRY_MINUTES: i64 = 15;
+
+/// Uploads larger than this are refused before downloading.
+pub const MAX_IMPORT_BYTES: u64 = 1024 * 1024;
+
+/// File format for `/team-export` and `/team-import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RosterFormat {
+    Json,
+    Csv,
+}
+
+impl RosterFormat {
+    pub fn parse(s: &str) -> Option<RosterFormat> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "json" => Some(RosterFormat::Json),
+            "csv" => Some(RosterFormat::Csv),
+            _ => None,
+        }
+    }
+
+    /// The format of an uploaded file, going by its extension.
+    pub fn from_filename(name: &str) -> Option<RosterFormat> {
+        name.rsplit_once('.')
+            .and_then(|(_, extension)| RosterFormat::parse(extension))
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RosterFormat::Json => "json",
+            RosterFormat::Csv => "csv",
+        }
+    }
+}
+
+/// CSV columns for the built-in details. Custom fields follow as `field:<key>`.
+const COLUMNS: [&str; 10] = [
+    "ign", "country", "skills", "rank", "about", "discord", "timezone", "joined", "base", "squad",
+];
+
+const FIELD_PREFIX: &str = "field:";
+
+pub fn export(team: &Team, format: RosterFormat) -> String {
+    match format {
+        RosterFormat::Json => serde_json::to_string_pretty(team).unwrap_or_else(|_| "{}".into()),
+        RosterFormat::Csv => export_csv(team),
+    }
+}
+
+fn export_csv(team: &Team) -> String {
+    let mut header: Vec<String> = COLUMNS.iter().map(|c| c.to_string()).collect();
+    header.extend(
+        team.custom_fields
+            .iter()
+            .map(|f| format!("{}{}", FIELD_PREFIX, f.key)),
+    );
+    let mut lines = vec![csv_record(&header)];
+    for m in &team.members {
+        let mut record = vec![
+            m.ign.clone(),
+            m.country.clone(),
+            m.skills.join(", "),
+            m.rank.as_str().to_string(),
+            m.about.clone(),
+            m.discord_tag.clone(),
+            m.timezone.clone(),
+            m.joined.map(|d| d.to_string()).unwrap_or_default(),
+            m.base.clone(),
+            m.squad.clone(),
+        ];
+        record.extend(
+            team.custom_fields
+                .iter()
+                .map(|f| m.custom.get(&f.key).cloned().unwrap_or_default()),
+        );
+        lines.push(csv_record(&record));
+    }
+    // CRLF as RFC 4180 asks, so spreadsheet programs open it cleanly
+    lines.join("\r\n") + "\r\n"
+}
+
+fn csv_record(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|value| {
+            if value.contains([',', ';', '"', '\n', '\r']) || value.trim() != value {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Splits CSV text into records. Quoted values may hold separators, quotes
+/// (doubled) and line breaks. Spreadsheets set to Dutch save with `;`, so
+/// that's used when the header has no commas.
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.trim_start_matches('\u{feff}');
+    let header = text.lines().next().unwrap_or_default();
+    let separator = if !header.contains(',') && header.contains(';') {
+        ';'
+    } else {
+        ','
+    };
+
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut value = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    value.push('"');
+                }
+                '"' => quoted = false,
+                c => value.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => quoted = true,
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut value));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == separator => record.push(std::mem::take(&mut value)),
+            c => value.push(c),
+        }
+    }
+    if !value.is_empty() || !record.is_empty() {
+        record.push(value);
+        records.push(record);
+    }
+    // Blank lines, often trailing ones
+    records.retain(|r| r.iter().any(|v| !v.trim().is_empty()));
+    records
+}
+
+/// One member as read from a file: values by column, and the row to name in
+/// errors.
+struct Row {
+    number: usize,
+    values: BTreeMap<String, String>,
+}
+
+fn csv_rows(text: &str) -> Vec<Row> {
+    let mut records = parse_csv(text).into_iter();
+    let header: Vec<String> = match records.next() {
+        Some(header) => header.iter().map(|h| h.trim().to_lowercase()).collect(),
+        None => return Vec::new(),
+    };
+    records
+        .enumerate()
+        .map(|(i, record)| Row {
+            // The header is row 1
+            number: i + 2,
+            values: header.iter().cloned().zip(record).collect(),
+        })
+        .collect()
+}
+
+/// Members from a `/team-export` JSON file, or a plain list of members.
+fn json_rows(text: &str) -> Result<Vec<Row>, serde_json::Error> {
+    let json: Value = serde_json::from_str(text.trim_start_matches('\u{feff}'))?;
+    let members = json
+        .get("members")
+        .unwrap_or(&json)
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    let text = |value: &Value| match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| {
+                item.as_str()
+                    .map(str::to_string)
+                    .unwrap_or(item.to_string())
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        other => other.to_string(),
+    };
+    Ok(members
+        .iter()
+        .enumerate()
+        .map(|(i, member)| {
+            let mut values = BTreeMap::new();
+            for (key, value) in member.as_object().into_iter().flatten() {
+                match key.as_str() {
+                    "custom" => {
+                        for (field, value) in value.as_object().into_iter().flatten() {
+                            values.insert(format!("{}{}", FIELD_PREFIX, field), text(value));
+                        }
+                    }
+                    "discord_tag" => {
+                        values.insert("discord".to_string(), text(value));
+                    }
+                    // Files saved before skills became a list
+                    "skill" => {
+                        values.insert("skills".to_string(), text(value));
+                    }
+                    key => {
+                        values.insert(key.to_string(), text(value));
+                    }
+                }
+            }
+            Row {
+                number: i + 1,
+                values,
+            }
+        })
+        .collect())
+}
+
+/// A catalogue message without its leading ❌, for use inside a list.
+fn plain(message: String) -> String {
+    message.trim_start_matches('❌').trim_start().to_string()
+}
+
+/// Builds a member from a row, starting from their current details so columns
+/// missing from the file keep their value.
+fn member_from_row(row: &Row, team: &Team, fmt: &Format) -> Result<TeamMember, String> {
+    let get = |column: &str| row.values.get(column).map(|v| v.trim());
+    let ign = get("ign").unwrap_or_default();
+    if ign.is_empty() {
+        return Err(fmt.t("import.missing_ign").to_string());
+    }
+    let mut member = team.member(ign).cloned().unwrap_or_default();
+    member.ign = ign.to_string();
+
+    if let Some(country) = get("country") {
+        member.country = if country.is_empty() {
+            String::new()
+        } else {
+            countries::resolve(country, fmt).map_err(plain)?
+        };
+    }
+    if let Some(skills) = get("skills") {
+        member.skills = parse_skills(skills);
+    }
+    if let Some(rank) = get("rank").filter(|r| !r.is_empty()) {
+        member.rank = Rank::parse(rank).ok_or_else(|| fmt.tf("import.invalid_rank", &[&rank]))?;
+    }
+    if let Some(about) = get("about") {
+        member.about = about.to_string();
+    }
+    if let Some(discord) = get("discord") {
+        member.discord_tag = discord.to_string();
+    }
+    if let Some(timezone) = get("timezone") {
+        member.timezone = timezone.to_string();
+    }
+    if let Some(joined) = get("joined").filter(|j| !j.is_empty()) {
+        member.joined = Some(
+            parse_date(joined).ok_or_else(|| plain(fmt.tf("team.invalid_joined", &[&joined])))?,
+        );
+    }
+    if let Some(base) = get("base") {
+        member.base = base.to_string();
+    }
+    if let Some(squad) = get("squad") {
+        member.squad = if squad.is_empty() {
+            String::new()
+        } else {
+            team.squad(squad)
+                .map(|s| s.name.clone())
+                .ok_or_else(|| fmt.tf("import.unknown_squad", &[&squad]))?
+        };
+    }
+    for field in &team.custom_fields {
+        let value = match get(&format!("{}{}", FIELD_PREFIX, field.key)) {
+            Some(value) => value,
+            None => continue,
+        };
+        if value.is_empty() {
+            member.custom.remove(&field.key);
+            continue;
+        }
+        let value = field
+            .kind
+            .normalize(value)
+            .map_err(|key| format!("{}: {}", field.label, plain(fmt.tf(key, &[&value]))))?;
+        member.custom.insert(field.key.clone(), value);
+    }
+    Ok(member)
+}
+
+/// Reads and validates a roster file. Every problem is reported, one line
+/// each, rather than only the first.
+pub fn read(
+    text: &str,
+    format: RosterFormat,
+    team: &Team,
+    fmt: &Format,
+) -> Result<Vec<TeamMember>, Vec<String>> {
+    let rows = match format {
+        RosterFormat::Json => {
+            json_rows(text).map_err(|e| vec![fmt.tf("import.invalid_json", &[&e])])?
+        }
+        RosterFormat::Csv => csv_rows(text),
+    };
+    if rows.is_empty() {
+        return Err(vec![fmt.t("import.empty").to_string()]);
+    }
+
+    let mut errors = Vec::new();
+    let unknown: BTreeSet<&String> = rows
+        .iter()
+        .flat_map(|row| row.values.keys())
+        .filter(|column| {
+            let known = COLUMNS.contains(&column.as_str())
+                || column
+                    .strip_prefix(FIELD_PREFIX)
+                    .is_some_and(|key| team.field(key).is_some());
+            !known
+        })
+        .collect();
+    for column in unknown {
+        errors.push(fmt.tf("import.unknown_column", &[column]));
+    }
+
+    let mut members = Vec::new();
+    let mut seen = HashSet::new();
+    for row in &rows {
+        match member_from_row(row, team, fmt) {
+            Ok(member) if !seen.insert(member.ign.to_lowercase()) => errors.push(fmt.tf(
+                "import.row_error",
+                &[&row.number, &fmt.tf("import.duplicate_ign", &[&member.ign])],
+            )),
+            Ok(member) => members.push(member),
+            Err(message) => errors.push(fmt.tf("import.row_error", &[&row.number, &message])),
+        }
+    }
+    if errors.is_empty() {
+        Ok(members)
+    } else {
+        Err(errors)
+    }
+}
+
+/// What an import would change.
+#[derive(Debug, Clone, Default)]
+pub struct RosterDiff {
+    pub added: Vec<TeamMember>,
+    /// Before and after.
+    pub updated: Vec<(TeamMember, TeamMember)>,
+    pub removed: Vec<TeamMember>,
+}
+
+impl RosterDiff {
+    pub fn between(current: &[TeamMember], incoming: &[TeamMember]) -> RosterDiff {
+        let find = |members: &[TeamMember], ign: &str| {
+            members
+                .iter()
+                .find(|m| m.ign.eq_ignore_ascii_case(ign))
+                .cloned()
+        };
+        let mut diff = RosterDiff::default();
+        for member in incoming {
+            match find(current, &member.ign) {
+                None => diff.added.push(member.clone()),
+                Some(before) if before != *member => diff.updated.push((before, member.clone())),
+                Some(_) => {}
+            }
+        }
+        for member in current {
+            if find(incoming, &member.ign).is_none() {
+                diff.removed.push(member.clone());
+            }
+        }
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Catalogue keys naming the details that differ between two versions of a
+/// member.
+pub fn changed_fields(before: &TeamMember, after: &TeamMember) -> Vec<&'static str> {
+    [
+        ("profile.ign", before.ign != after.ign),
+        ("profile.country", before.country != after.country),
+        ("profile.skills", before.skills != after.skills),
+        ("profile.rank", before.rank != after.rank),
+        ("profile.about", before.about != after.about),
+        ("profile.discord", before.discord_tag != after.discord_tag),
+        ("profile.timezone", before.timezone != after.timezone),
+        ("profile.joined", before.joined != after.joined),
+        ("profile.base", before.base != after.base),
+        ("profile.squad", before.squad != after.squad),
+        ("profile.custom", before.custom != after.custom),
+    ]
+    .into_iter()
+    .filter_map(|(key, changed)| changed.then_some(key))
+    .collect()
+}
+
+/// A validated import waiting for its confirm button.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingImport {
+    pub user_id: u64,
+    pub created: DateTime<Utc>,
+    /// The roster the preview was made against; confirming a stale preview
+    /// would throw away changes made since.
+    pub base: Vec<TeamMember>,
+    pub members: Vec<TeamMember>,
+}
+
+impl PendingImport {
+    fn expired(&self, now: DateTime<Utc>) -> bool {
+        now - self.created > Duration::minutes(IMPORT_EXPIRY_MINUTES)
+    }
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("IMPORT_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("team_imports.json")
+}
+
+fn load() -> BTreeMap<String, PendingImport> {
+    let path = store_path();
+    if !path.exists() {
+        return BTreeMap::new();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+fn save(pending: &BTreeMap<String, PendingImport>) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(pending).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// Keeps an import under `id` until it's confirmed, cancelled or expires.
+pub fn stage(id: &str, import: PendingImport) -> io::Result<()> {
+    let mut pending = load();
+    let now = Utc::now();
+    pending.retain(|_, p| !p.expired(now));
+    pending.insert(id.to_string(), import);
+    save(&pending)
+}
+
+/// Who staged the import under `id`, if it's still waiting.
+pub fn owner(id: &str) -> Option<u64> {
+    load().get(id).map(|p| p.user_id)
+}
+
+/// Removes the import staged under `id`, returning it unless it expired.
+pub fn take(id: &str) -> io::Result<Option<PendingImport>> {
+    let mut pending = load();
+    let import = pending.remove(id);
+    save(&pending)?;
+    Ok(import.filter(|p| !p.expired(Utc::now())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::team::{CustomField, FieldKind};
+
+    fn team() -> Team {
+        Team {
+            name: "Donut Bakers".into(),
+            members: vec![TeamMember {
+                ign: "Steve".into(),
+                country: "NL".into(),
+                skills: vec!["PvP".into()],
+                rank: Rank::Admin,
+                timezone: "UTC+2".into(),
+                ..TeamMember::default()
+            }],
+            custom_fields: vec![CustomField {
+                key: "farms".into(),
+                label: "Farms".into(),
+                kind: FieldKind::Number,
+            }],
+            squads: vec![],
+        }
+    }
+
+    #[test]
+    fn csv_round_trip() {
+        let mut team = team();
+        team.members[0].about = "Says \"hi\", a lot\nand more".into();
+        team.members[0].custom.insert("farms".into(), "3".into());
+        let csv = export(&team, RosterFormat::Csv);
+        let members = read(&csv, RosterFormat::Csv, &team, &Format::default()).unwrap();
+        assert_eq!(members, team.members);
+    }
+
+    #[test]
+    fn missing_columns_keep_current_values() {
+        let csv = "IGN;Country\r\nsteve;Belgium\r\nAlex;de\r\n\r\n";
+        let members = read(csv, RosterFormat::Csv, &team(), &Format::default()).unwrap();
+        assert_eq!(members[0].country, "BE");
+        assert_eq!(members[0].timezone, "UTC+2");
+        assert_eq!(members[0].rank, Rank::Admin);
+        assert_eq!(members[1].country, "DE");
+        assert_eq!(members[1].rank, Rank::Member);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let json = r#"{"members": [
+            {"ign": "Steve", "rank": "boss", "custom": {"farms": "lots"}},
+            {"ign": "", "country": "NL"},
+            {"ign": "steve", "country": "Atlantis"},
+            {"ign": "Alex", "colour": "blue"}
+        ]}"#;
+        let errors = read(json, RosterFormat::Json, &team(), &Format::default()).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "Unknown column `colour`.",
+                "Row 1: rank must be owner, admin or member, not **boss**.",
+                "Row 2: every row needs an IGN.",
+                "Row 3: **Atlantis** isn't a country I know. Use its name or a two-letter code like NL.",
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_sorts_members() {
+        let current = team().members;
+        let mut changed = current[0].clone();
+        changed.skills = vec!["Building".into()];
+        let new = TeamMember {
+            ign: "Alex".into(),
+            ..TeamMember::default()
+        };
+        let diff = RosterDiff::between(&current, &[changed.clone(), new]);
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(
+            changed_fields(&diff.updated[0].0, &diff.updated[0].1),
+            vec!["profile.skills"]
+        );
+        assert!(diff.removed.is_empty());
+        let diff = RosterDiff::between(&current, &[]);
+        assert_eq!(diff.removed.len(), 1);
+        assert!(RosterDiff::between(&current, &current).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self},
+    path::PathBuf,
+};
+
+use crate::locale::{Format, Language, NumberStyle};
+
+/// Formatting choices; unset values fall back to the server default.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct Preferences {
+    #[serde(default)]
+    pub numbers: Option<NumberStyle>,
+    #[serde(default)]
+    pub language: Option<Language>,
+}
+
+impl Preferences {
+    /// These preferences with `fallback` filling the gaps.
+    pub fn over(&self, fallback: Format) -> Format {
+        Format {
+            numbers: self.numbers.unwrap_or(fallback.numbers),
+            language: self.language.unwrap_or(fallback.language),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Settings {
+    /// Server default, also used for channel posts like the team status embed.
+    #[serde(default)]
+    pub guild: Preferences,
+    /// Per-user overrides keyed by Discord user ID.
+    #[serde(default)]
+    pub users: HashMap<String, Preferences>,
+}
+
+fn store_path() -> PathBuf {
+    if let Ok(p) = std::env::var("SETTINGS_STORE_PATH") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("settings_data.json")
+}
+
+pub fn load() -> Settings {
+    let path = store_path();
+    if !path.exists() {
+        return Settings::default();
+    }
+    match fs::read_to_string(&path) {
+        Ok(s) => serde_json::from_str(&s).unwrap_or_default(),
+        Err(_) => Settings::default(),
+    }
+}
+
+pub fn save(settings: &Settings) -> io::Result<()> {
+    let path = store_path();
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            let _ = fs::create_dir_all(parent);
+        }
+    }
+    let json = serde_json::to_string_pretty(settings).unwrap_or_else(|_| "{}".into());
+    fs::write(path, json)
+}
+
+/// Format for posts that have no single reader, like the team status embed.
+pub fn guild_format() -> Format {
+    load().guild.over(Format::default())
+}
+
+/// Format for a reply to `user_id`: their own choices over the server default.
+/// The language falls back to the interaction's `locale` when neither the user
+/// nor the server picked one.
+pub fn format_for(user_id: u64, locale: &str) -> Format {
+    let settings = load();
+    let fallback = Format {
+        language: Language::from_discord(locale).unwrap_or_default(),
+        ..Format::default()
+    };
+    let guild = settings.guild.over(fallback);
+    match settings.users.get(&user_id.to_string()) {
+        Some(prefs) => prefs.over(guild),
+        None => guild,
+    }
+}
+
+/// Applies the given changes to a user's preferences (or the server default
+/// when `user_id` is `None`) and returns the result.
+pub fn update(
+    user_id: Option<u64>,
+    numbers: Option<NumberStyle>,
+    language: Option<Language>,
+) -> io::Result<Preferences> {
+    let mut settings = load();
+    let prefs = match user_id {
+        Some(id) => settings.users.entry(id.to_string()).or_default(),
+        None => &mut settings.guild,
+    };
+    if numbers.is_some() {
+        prefs.numbers = numbers;
+    }
+    if language.is_some() {
+        prefs.language = language;
+    }
+    let updated = *prefs;
+    save(&settings)?;
+    Ok(updated)
+}
+
+/// Forgets a user's choices so the server default applies again.
+pub fn reset_user(user_id: u64) -> io::Result<()> {
+    let mut settings = load();
+    settings.users.remove(&user_id.to_string());
+    save(&settings)
+}
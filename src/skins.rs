@@ -0,0 +1,89 @@
+use reqwest::Client;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tracing::warn;
+
+/// Cached heads are fetched again after this long, so skin changes show up.
+const MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+fn cache_dir() -> PathBuf {
+    if let Ok(p) = std::env::var("SKIN_CACHE_DIR") {
+        return PathBuf::from(p);
+    }
+    PathBuf::from("skins")
+}
+
+/// Where heads come from; `{}` is replaced by the IGN.
+fn head_url(ign: &str) -> String {
+    let template = std::env::var("SKIN_HEAD_URL")
+        .unwrap_or_else(|_| "https://mc-heads.net/avatar/{}/64".to_string());
+    template.replace("{}", &ign.replace(' ', "%20"))
+}
+
+/// `None` when nothing of the IGN is left to name the file after.
+fn cache_path(ign: &str) -> Option<PathBuf> {
+    let name: String = ign
+        .to_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    (!name.is_empty()).then(|| cache_dir().join(format!("{}.png", name)))
+}
+
+fn is_fresh(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+        .map(|age| age < MAX_AGE)
+        .unwrap_or(false)
+}
+
+/// The player's head as a PNG. Served from the cache while it's fresh,
+/// otherwise fetched and cached; a stale copy is used when the skin service
+/// can't be reached. `None` when there's nothing to show, including IGNs
+/// without a single valid character, so they get the placeholder.
+pub async fn head(client: &Client, ign: &str) -> Option<Vec<u8>> {
+    cached_png(client, &head_url(ign), &cache_path(ign)?).await
+}
+
+/// A PNG from `url`, kept at `path` for [`MAX_AGE`] and used stale when the
+/// service can't be reached.
+pub async fn cached_png(client: &Client, url: &str, path: &Path) -> Option<Vec<u8>> {
+    if is_fresh(path) {
+        if let Ok(bytes) = fs::read(path) {
+            return Some(bytes);
+        }
+    }
+
+    match fetch(client, url).await {
+        Some(bytes) => {
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            if let Err(e) = fs::write(path, &bytes) {
+                warn!("Failed to cache {}: {:?}", url, e);
+            }
+            Some(bytes)
+        }
+        None => fs::read(path).ok(),
+    }
+}
+
+async fn fetch(client: &Client, url: &str) -> Option<Vec<u8>> {
+    let res = client
+        .get(url)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+        .ok()?;
+    if !res.status().is_success() {
+        return None;
+    }
+    let bytes = res.bytes().await.ok()?;
+    // Only keep what looks like a PNG, not an error page
+    bytes.starts_with(b"\x89PNG").then(|| bytes.to_vec())
+}
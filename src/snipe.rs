@@ -0,0 +1,293 @@
+use chrono::Utc;
+use reqwest::Client;
+use serenity::{model::prelude::ChannelId, prelude::*};
+use std::collections::HashSet;
+use std::time::Duration;
+use tracing::{error, warn};
+
+use crate::auction::Listing;
+use crate::market::{median_unit_price, MarketData};
+
+/// Listing keys already posted, so browsing the same page twice doesn't re-flag.
+pub struct FlaggedSnipes;
+
+impl TypeMapKey for FlaggedSnipes {
+    type Value = HashSet<String>;
+}
+
+/// Flagged keys are forgotten in bulk once the set grows past this.
+const MAX_REMEMBERED: usize = 5000;
+
+pub struct SnipeConfig {
+    pub channel_id: u64,
+    /// Minimum discount below the median per-unit price, in percent.
+    pub threshold_percent: f64,
+    pub lookback_days: i64,
+    /// Minimum number of recent sales before a median is trusted.
+    pub min_samples: usize,
+}
+
+/// Reads the opt-in snipe settings; `None` when `SNIPE_CHANNEL_ID` is unset.
+pub fn config() -> Option<SnipeConfig> {
+    let channel_id = std::env::var("SNIPE_CHANNEL_ID")
+        .ok()
+        .and_then(|s| s.parse::<u64>().ok())?;
+    let threshold_percent = std::env::var("SNIPE_THRESHOLD_PERCENT")
+        .ok()
+        .and_then(|s| s.parse::<f64>().ok())
+        .unwrap_or(30.0);
+    let lookback_days = std::env::var("SNIPE_LOOKBACK_DAYS")
+        .ok()
+        .and_then(|s| s.parse::<i64>().ok())
+        .unwrap_or(7);
+    let min_samples = std::env::var("SNIPE_MIN_SAMPLES")
+        .ok()
+        .and_then(|s| s.parse::<usize>().ok())
+        .unwrap_or(3);
+    Some(SnipeConfig {
+        channel_id,
+        threshold_percent,
+        lookback_days,
+        min_samples,
+    })
+}
+
+pub struct Snipe {
+    pub listing: Listing,
+    pub median_unit: f64,
+    pub samples: usize,
+    pub discount_percent: f64,
+    /// Expected profit from buying the listing and reselling at the median.
+    pub margin: i64,
+}
+
+impl Snipe {
+    fn key(&self) -> String {
+        format!(
+            "{}|{}|{}",
+            self.listing.seller.to_ascii_lowercase(),
+            self.listing.item_key(),
+            self.listing.price
+        )
+    }
+}
+
+/// Scores listings against recent local price history and returns the
+/// ones priced at least `threshold_percent` below the median.
+pub fn score(listings: &[Listing], data: &MarketData, cfg: &SnipeConfig) -> Vec<Snipe> {
+    let since = Utc::now().timestamp() - cfg.lookback_days * 86_400;
+    listings
+        .iter()
+        .filter_map(|listing| {
+            let (median_unit, samples) = median_unit_price(data, &listing.item_key(), since)?;
+            if samples < cfg.min_samples || median_unit <= 0.0 {
+                return None;
+            }
+            let discount_percent = (median_unit - listing.unit_price()) / median_unit * 100.0;
+            if discount_percent < cfg.threshold_percent {
+                return None;
+            }
+            let margin = (median_unit * listing.count as f64).round() as i64 - listing.price;
+            Some(Snipe {
+                listing: listing.clone(),
+                median_unit,
+                samples,
+                discount_percent,
+                margin,
+            })
+        })
+        .collect()
+}
+
+/// Scoring step of the auction fetch path: posts newly found snipes to the
+/// opt-in channel. Does nothing when snipe alerts aren't configured.
+pub async fn flag_listings(ctx: &Context, listings: &[Listing]) {
+    let cfg = match config() {
+        Some(cfg) => cfg,
+        None => return,
+    };
+    let snipes = score(listings, &crate::market::load(), &cfg);
+    if snipes.is_empty() {
+        return;
+    }
+
+    let fresh: Vec<Snipe> = {
+        let mut data = ctx.data.write().await;
+        let flagged = data.entry::<FlaggedSnipes>().or_insert_with(HashSet::new);
+        if flagged.len() > MAX_REMEMBERED {
+            flagged.clear();
+        }
+        snipes
+            .into_iter()
+            .filter(|snipe| flagged.insert(snipe.key()))
+            .collect()
+    };
+
+    for snipe in fresh {
+        if let Err(e) = post_snipe(ctx, cfg.channel_id, &snipe).await {
+            error!("Failed to post snipe alert: {:?}", e);
+        }
+    }
+}
+
+async fn post_snipe(
+    ctx: &Context,
+    channel_id: u64,
+    snipe: &Snipe,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listing = &snipe.listing;
+    let fmt = crate::settings::guild_format();
+    ChannelId(channel_id)
+        .send_message(&ctx.http, |m| {
+            m.embed(|e| {
+                e.title(fmt.tf("snipe.title", &[&listing.label()]))
+                    .color(crate::constants::EMBED_COLOR_ACCENT)
+                    .field(
+                        fmt.t("snipe.price"),
+                        fmt.tf(
+                            "listing.price",
+                            &[
+                                &fmt.money(listing.price),
+                                &fmt.money(listing.unit_price().round() as i64),
+                            ],
+                        ),
+                        true,
+                    )
+                    .field(
+                        fmt.t("snipe.median"),
+                        fmt.tf(
+                            "snipe.median_value",
+                            &[&fmt.money(snipe.median_unit.round() as i64), &snipe.samples],
+                        ),
+                        true,
+                    )
+                    .field(
+                        fmt.t("snipe.margin"),
+                        fmt.tf(
+                            "snipe.margin_value",
+                            &[
+                                &fmt.money(snipe.margin),
+                                &format!("{:.1}", snipe.discount_percent),
+                            ],
+                        ),
+                        false,
+                    )
+                    .footer(|f| f.text(fmt.tf("auction.seller", &[&listing.seller])))
+            })
+        })
+        .await?;
+    Ok(())
+}
+
+/// Periodically checks the most recently listed auctions for snipes.
+pub async fn run(
+    ctx: Context,
+    http_client: Client,
+    api_key: String,
+    interval_minutes: u64,
+    pages: u32,
+) {
+    loop {
+        for page in 1..=pages {
+            let path = format!("/v1/auction/list/{}", page);
+            match crate::api::fetch_auction_json(
+                &http_client,
+                &api_key,
+                &path,
+                None,
+                Some("recently_listed"),
+            )
+            .await
+            {
+                Ok(json) => {
+                    flag_listings(&ctx, &crate::auction::parse_listings(&json)).await;
+                }
+                Err(e) => {
+                    warn!("Snipe scan failed on page {}: {:?}", page, e);
+                    break;
+                }
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval_minutes.saturating_mul(60))).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::market::SaleRecord;
+
+    fn listing(price: i64, count: i64) -> Listing {
+        Listing {
+            item_id: "minecraft:elytra".into(),
+            display_name: "Elytra".into(),
+            count,
+            enchants: Default::default(),
+            price,
+            seller: "Steve".into(),
+        }
+    }
+
+    fn history(unit_prices: &[i64], days_ago: i64) -> MarketData {
+        let sold_at = Utc::now().timestamp() - days_ago * 86_400;
+        MarketData {
+            sales: unit_prices
+                .iter()
+                .map(|&price| SaleRecord {
+                    item_key: listing(0, 1).item_key(),
+                    item_id: "minecraft:elytra".into(),
+                    name: "Elytra".into(),
+                    label: "Elytra".into(),
+                    count: 1,
+                    price,
+                    seller: "Alex".into(),
+                    buyer: None,
+                    sold_at,
+                    ingested_at: false,
+                })
+                .collect(),
+        }
+    }
+
+    fn cfg() -> SnipeConfig {
+        SnipeConfig {
+            channel_id: 1,
+            threshold_percent: 30.0,
+            lookback_days: 7,
+            min_samples: 3,
+        }
+    }
+
+    #[test]
+    fn listings_well_below_the_median_are_snipes() {
+        let data = history(&[900, 1_000, 1_100], 1);
+        let snipes = score(&[listing(1_400, 2), listing(800, 1)], &data, &cfg());
+
+        // 700 each is 30% off; 800 is only 20% off
+        assert_eq!(snipes.len(), 1);
+        let snipe = &snipes[0];
+        assert_eq!(snipe.median_unit, 1_000.0);
+        assert_eq!(snipe.samples, 3);
+        assert_eq!(snipe.discount_percent, 30.0);
+        // Resold at the median: 2 × 1.000 - 1.400
+        assert_eq!(snipe.margin, 600);
+    }
+
+    #[test]
+    fn thin_or_old_history_is_not_trusted() {
+        let listings = [listing(100, 1)];
+        assert!(score(&listings, &history(&[1_000, 1_000], 1), &cfg()).is_empty());
+        assert!(score(&listings, &history(&[1_000, 1_000, 1_000], 8), &cfg()).is_empty());
+    }
+
+    #[test]
+    fn the_same_listing_has_the_same_key() {
+        let data = history(&[1_000, 1_000, 1_000], 1);
+        let first = score(&[listing(100, 1)], &data, &cfg());
+        let again = score(&[listing(100, 1)], &data, &cfg());
+        let cheaper = score(&[listing(90, 1)], &data, &cfg());
+        assert_eq!(first[0].key(), again[0].key());
+        assert_ne!(first[0].key(), cheaper[0].key());
+    }
+}
@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use std::collections::HashMap;
+
+use crate::api::fetch_presence;
+use crate::models::Presence;
+use crate::team::{Detail, Rank, Squad, Team, TeamMember};
+
+/// How the team post and `/team-list` lay out the roster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    /// One line per member.
+    Compact,
+    /// A field per member with their details, under rank or squad headings.
+    Detailed,
+    /// One line per online member, with where they are.
+    OnlineOnly,
+}
+
+impl Layout {
+    pub fn parse(s: &str) -> Option<Layout> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "compact" => Some(Layout::Compact),
+            "detailed" => Some(Layout::Detailed),
+            "online" | "online-only" | "online_only" => Some(Layout::OnlineOnly),
+            _ => None,
+        }
+    }
+}
+
+pub struct StatusConfig {
+    pub layout: Layout,
+    /// Member details shown, in this order.
+    pub details: Vec<Detail>,
+    /// Group by squad instead of by rank.
+    pub group_by_squad: bool,
+    /// Post the team status as a rendered image instead of embed fields.
+    pub image: bool,
+}
+
+/// Reads `TEAM_LAYOUT`, `TEAM_FIELDS`, `ONLINE_GROUP_BY` and
+/// `TEAM_STATUS_IMAGE`. Unset or unknown values keep the detailed layout with
+/// the default details, grouped by rank, as embeds.
+pub fn config() -> StatusConfig {
+    let layout = std::env::var("TEAM_LAYOUT")
+        .ok()
+        .and_then(|s| Layout::parse(&s))
+        .unwrap_or(Layout::Detailed);
+    let details: Vec<Detail> = std::env::var("TEAM_FIELDS")
+        .map(|s| s.split(',').filter_map(Detail::parse).collect())
+        .unwrap_or_default();
+    let group_by_squad = std::env::var("ONLINE_GROUP_BY")
+        .map(|s| s.trim().eq_ignore_ascii_case("squad"))
+        .unwrap_or(false);
+    let image = std::env::var("TEAM_STATUS_IMAGE")
+        .map(|s| matches!(s.trim().to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    StatusConfig {
+        layout,
+        details: if details.is_empty() {
+            Detail::DEFAULT.to_vec()
+        } else {
+            details
+        },
+        group_by_squad,
+        image,
+    }
+}
+
+/// What the team status groups members under.
+pub enum Group<'a> {
+    Rank(Rank),
+    /// `None` for members outside any squad.
+    Squad(Option<&'a Squad>),
+}
+
+/// The roster as the team post and `/team-list` show it.
+pub struct TeamStatus {
+    pub team: Team,
+    /// Presence by lowercase IGN. Empty when nobody was looked up, as for
+    /// `/team-list`.
+    pub presence: HashMap<String, Presence>,
+    pub config: StatusConfig,
+    /// When presence was checked, for the footer.
+    pub at: Option<DateTime<Utc>>,
+}
+
+impl TeamStatus {
+    pub fn presence_of(&self, ign: &str) -> Option<&Presence> {
+        self.presence.get(&ign.to_lowercase())
+    }
+
+    /// The team with members in display order: Owner > Admin > Member, then
+    /// by IGN.
+    pub fn sorted_team(&self) -> Team {
+        let mut team = self.team.clone();
+        team.members.sort_by(|a, b| {
+            a.rank
+                .sort_key()
+                .cmp(&b.rank.sort_key())
+                .then_with(|| a.ign.to_ascii_lowercase().cmp(&b.ign.to_ascii_lowercase()))
+        });
+        team
+    }
+
+    /// Whether the layout shows `member`: everyone, or only who's online.
+    pub fn shows(&self, member: &TeamMember) -> bool {
+        self.config.layout != Layout::OnlineOnly
+            || matches!(self.presence_of(&member.ign), Some(Presence::Online { .. }))
+    }
+
+    /// The members of `team` the layout shows, under their rank or squad in
+    /// roster order. Groups with nobody to show are left out.
+    pub fn groups<'a>(&self, team: &'a Team) -> Vec<(Group<'a>, Vec<&'a TeamMember>)> {
+        let groups: Vec<(Group<'a>, Vec<&'a TeamMember>)> = if self.config.group_by_squad {
+            team.by_squad()
+                .into_iter()
+                .map(|(squad, members)| (Group::Squad(squad), members))
+                .collect()
+        } else {
+            [Rank::Owner, Rank::Admin, Rank::Member]
+                .into_iter()
+                .map(|rank| {
+                    let members = team.members.iter().filter(|m| m.rank == rank).collect();
+                    (Group::Rank(rank), members)
+                })
+                .collect()
+        };
+        groups
+            .into_iter()
+            .map(|(group, members)| {
+                let members: Vec<&TeamMember> =
+                    members.into_iter().filter(|m| self.shows(m)).collect();
+                (group, members)
+            })
+            .filter(|(_, members)| !members.is_empty())
+            .collect()
+    }
+}
+
+/// Looks up every member, one at a time to go easy on the API.
+pub async fn fetch_presences(
+    client: &Client,
+    donut_key: &str,
+    team: &Team,
+) -> HashMap<String, Presence> {
+    let mut presence = HashMap::new();
+    for member in &team.members {
+        let found = fetch_presence(client, donut_key, &member.ign).await;
+        presence.insert(member.ign.to_lowercase(), found);
+    }
+    presence
+}
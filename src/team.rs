@@ -1,10 +1,18 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use std::{
+    collections::BTreeMap,
     fs,
     io::{self},
     path::PathBuf,
 };
 
+use crate::audit::{self, Change};
+use crate::countries;
+use crate::history;
+use crate::locale::Format;
+use crate::models::{PlayerStats, Presence};
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Rank {
     #[serde(rename = "Owner")]
@@ -22,6 +30,14 @@ impl Default for Rank {
 }
 
 impl Rank {
+    pub const ALL: [Rank; 3] = [Rank::Owner, Rank::Admin, Rank::Member];
+
+    /// Like [`Rank::from_str`], but `None` for anything that isn't a rank.
+    pub fn parse(s: &str) -> Option<Rank> {
+        Rank::ALL
+            .into_iter()
+            .find(|rank| rank.as_str().eq_ignore_ascii_case(s.trim()))
+    }
     pub fn from_str(s: &str) -> Rank {
         match s.to_ascii_lowercase().as_str() {
             "owner" => Rank::Owner,
@@ -29,13 +45,6 @@ impl Rank {
             _ => Rank::Member,
         }
     }
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Rank::Owner => "Owner",
-            Rank::Admin => "Admin",
-            Rank::Member => "Member",
-        }
-    }
     pub fn sort_key(&self) -> u8 {
         match self {
             Rank::Owner => 0,
@@ -43,7 +52,21 @@ impl Rank {
             Rank::Member => 2,
         }
     }
-    #[allow(dead_code)]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rank::Owner => "owner",
+            Rank::Admin => "admin",
+            Rank::Member => "member",
+        }
+    }
+    /// Catalogue key for the rank's name.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Rank::Owner => "rank.owner",
+            Rank::Admin => "rank.admin",
+            Rank::Member => "rank.member",
+        }
+    }
     pub fn emoji(&self) -> &'static str {
         match self {
             Rank::Owner => "👑",
@@ -53,21 +76,336 @@ impl Rank {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// The kind of value an admin-defined profile field holds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldKind {
+    Text,
+    Number,
+    Date,
+    Link,
+    Toggle,
+}
+
+impl FieldKind {
+    pub const ALL: [FieldKind; 5] = [
+        FieldKind::Text,
+        FieldKind::Number,
+        FieldKind::Date,
+        FieldKind::Link,
+        FieldKind::Toggle,
+    ];
+
+    pub fn parse(s: &str) -> Option<FieldKind> {
+        FieldKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "text",
+            FieldKind::Number => "number",
+            FieldKind::Date => "date",
+            FieldKind::Link => "link",
+            FieldKind::Toggle => "toggle",
+        }
+    }
+
+    /// Catalogue key for the kind's name.
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            FieldKind::Text => "field.kind.text",
+            FieldKind::Number => "field.kind.number",
+            FieldKind::Date => "field.kind.date",
+            FieldKind::Link => "field.kind.link",
+            FieldKind::Toggle => "field.kind.toggle",
+        }
+    }
+
+    /// The value in its stored form, or the catalogue key explaining why it
+    /// doesn't fit this kind.
+    pub fn normalize(&self, value: &str) -> Result<String, &'static str> {
+        let value = value.trim();
+        match self {
+            FieldKind::Text => Ok(value.to_string()),
+            FieldKind::Number => value
+                .replace(',', ".")
+                .parse::<f64>()
+                .ok()
+                .filter(|n| n.is_finite())
+                .map(|n| n.to_string())
+                .ok_or("field.invalid_number"),
+            FieldKind::Date => parse_date(value)
+                .map(|d| d.to_string())
+                .ok_or("field.invalid_date"),
+            FieldKind::Link => {
+                if (value.starts_with("https://") || value.starts_with("http://"))
+                    && !value.contains(char::is_whitespace)
+                {
+                    Ok(value.to_string())
+                } else {
+                    Err("field.invalid_link")
+                }
+            }
+            FieldKind::Toggle => match value.to_ascii_lowercase().as_str() {
+                "yes" | "y" | "true" | "on" | "ja" | "j" => Ok("yes".to_string()),
+                "no" | "n" | "false" | "off" | "nee" => Ok("no".to_string()),
+                _ => Err("field.invalid_toggle"),
+            },
+        }
+    }
+}
+
+/// A profile field defined by the team's admins.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CustomField {
+    /// Lowercase name used in commands and as the key in [`TeamMember::custom`].
+    pub key: String,
+    pub label: String,
+    pub kind: FieldKind,
+}
+
+/// A named group within the team, such as builders or PvP.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Squad {
+    pub name: String,
+    /// IGNs of the members leading it.
+    #[serde(default)]
+    pub leads: Vec<String>,
+}
+
+impl Squad {
+    pub fn is_lead(&self, ign: &str) -> bool {
+        self.leads.iter().any(|l| l.eq_ignore_ascii_case(ign))
+    }
+}
+
+/// Longest squad name accepted, so it fits embed field titles.
+pub const MAX_SQUAD_NAME: usize = 32;
+
+/// `pvp, Building ,pvp` -> `["pvp", "Building"]`
+pub fn parse_skills(input: &str) -> Vec<String> {
+    let mut skills: Vec<String> = Vec::new();
+    for skill in input.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        if !skills.iter().any(|s| s.eq_ignore_ascii_case(skill)) {
+            skills.push(skill.to_string());
+        }
+    }
+    skills
+}
+
+/// Accepts the old single `skill` string as well as a list.
+fn skill_list<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Skills {
+        One(String),
+        Many(Vec<String>),
+    }
+    Ok(match Skills::deserialize(deserializer)? {
+        Skills::One(skill) => parse_skills(&skill),
+        Skills::Many(skills) => skills,
+    })
+}
+
+/// Stores countries saved by name under their ISO code.
+fn country_code<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let country = String::deserialize(deserializer)?;
+    Ok(match countries::exact(&country) {
+        Some(known) => known.code.to_string(),
+        None => country,
+    })
+}
+
+/// `2024-03-01`
+pub fn parse_date(input: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").ok()
+}
+
+/// The fixed offset of timezones written as `UTC+2`, `GMT-05:30` or `+0100`.
+/// Named zones like `Europe/Amsterdam` are shown as typed.
+pub fn utc_offset(timezone: &str) -> Option<FixedOffset> {
+    let tz = timezone.trim();
+    let upper = tz.to_ascii_uppercase();
+    let rest = upper
+        .strip_prefix("UTC")
+        .or_else(|| upper.strip_prefix("GMT"))
+        .unwrap_or(&upper)
+        .trim();
+    if rest.is_empty() {
+        // Plain `UTC` or `GMT`
+        return if tz.is_empty() {
+            None
+        } else {
+            FixedOffset::east_opt(0)
+        };
+    }
+    let (sign, digits) = if let Some(digits) = rest.strip_prefix('+') {
+        (1, digits)
+    } else if let Some(digits) = rest.strip_prefix('-') {
+        (-1, digits)
+    } else {
+        return None;
+    };
+    if !digits.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return None;
+    }
+    let (hours, minutes) = match digits.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None if digits.len() > 2 => {
+            let (h, m) = digits.split_at(digits.len() - 2);
+            (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?)
+        }
+        None => (digits.parse::<i32>().ok()?, 0),
+    };
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct TeamMember {
     pub ign: String,
+    /// ISO 3166 code, or the text as typed for teams saved before codes.
+    #[serde(deserialize_with = "country_code")]
     pub country: String,
-    pub skill: String,
+    #[serde(default, alias = "skill", deserialize_with = "skill_list")]
+    pub skills: Vec<String>,
     pub about: String,
     pub discord_tag: String,
     #[serde(default)]
     pub rank: Rank,
+    /// `UTC+2` style offset or a zone name, as the member gave it.
+    #[serde(default)]
+    pub timezone: String,
+    #[serde(default)]
+    pub joined: Option<NaiveDate>,
+    /// Where their in-game base is or what they do there.
+    #[serde(default)]
+    pub base: String,
+    /// Values of the team's [`CustomField`]s, keyed by field key.
+    #[serde(default)]
+    pub custom: BTreeMap<String, String>,
+    /// Name of the [`Squad`] they're in, empty when they aren't in one.
+    #[serde(default)]
+    pub squad: String,
+}
+
+/// A member detail the team post and `/team-list` can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detail {
+    Country,
+    Skills,
+    Discord,
+    Squad,
+    About,
+    Timezone,
+    Base,
+    Joined,
+}
+
+impl Detail {
+    /// What's shown unless configured otherwise.
+    pub const DEFAULT: [Detail; 5] = [
+        Detail::Country,
+        Detail::Skills,
+        Detail::Discord,
+        Detail::Squad,
+        Detail::About,
+    ];
+
+    pub fn parse(s: &str) -> Option<Detail> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "country" => Some(Detail::Country),
+            "skills" | "skill" => Some(Detail::Skills),
+            "discord" => Some(Detail::Discord),
+            "squad" => Some(Detail::Squad),
+            "about" => Some(Detail::About),
+            "timezone" => Some(Detail::Timezone),
+            "base" => Some(Detail::Base),
+            "joined" => Some(Detail::Joined),
+            _ => None,
+        }
+    }
+
+    /// `Country: {}` style catalogue key for the detail's line.
+    pub fn line_key(&self) -> &'static str {
+        match self {
+            Detail::Country => "team.member_country",
+            Detail::Skills => "team.member_skills",
+            Detail::Discord => "team.member_discord",
+            Detail::Squad => "team.member_squad",
+            Detail::About => "team.member_about",
+            Detail::Timezone => "team.member_timezone",
+            Detail::Base => "team.member_base",
+            Detail::Joined => "team.member_joined",
+        }
+    }
+
+    /// The member's value for this detail, or `None` when they have none.
+    pub fn value(&self, member: &TeamMember, fmt: &Format) -> Option<String> {
+        let value = match self {
+            Detail::Country => {
+                let name = countries::display_name(&member.country, fmt.language);
+                let flag = countries::country_flag(&member.country);
+                if flag.is_empty() {
+                    name
+                } else {
+                    format!("{} ({})", name, flag)
+                }
+            }
+            Detail::Skills => member.skills.join(", "),
+            Detail::Discord => member.discord_tag.clone(),
+            Detail::Squad => member.squad.clone(),
+            Detail::About => member.about.clone(),
+            Detail::Timezone => member.timezone.clone(),
+            Detail::Base => member.base.clone(),
+            Detail::Joined => member.joined.map(|d| fmt.date(d)).unwrap_or_default(),
+        };
+        (!value.trim().is_empty()).then_some(value)
+    }
+}
+
+impl TeamMember {
+    /// Country, skills, Discord tag, squad and about text, one per line.
+    pub fn details(&self, fmt: &Format) -> String {
+        self.detail_lines(&Detail::DEFAULT, fmt)
+    }
+
+    /// One line per detail. Country, skills and Discord show `-` when
+    /// missing; the others are left out.
+    pub fn detail_lines(&self, details: &[Detail], fmt: &Format) -> String {
+        details
+            .iter()
+            .filter_map(|detail| {
+                let value = match detail.value(self, fmt) {
+                    Some(value) => value,
+                    None if matches!(
+                        detail,
+                        Detail::Country | Detail::Skills | Detail::Discord
+                    ) =>
+                    {
+                        "-".to_string()
+                    }
+                    None => return None,
+                };
+                Some(fmt.tf(detail.line_key(), &[&value]))
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Team {
     pub name: String,
     pub members: Vec<TeamMember>,
+    #[serde(default)]
+    pub custom_fields: Vec<CustomField>,
+    #[serde(default)]
+    pub squads: Vec<Squad>,
 }
 
 impl Default for Team {
@@ -75,10 +413,126 @@ impl Default for Team {
         Team {
             name: "My Team".into(),
             members: vec![],
+            custom_fields: vec![],
+            squads: vec![],
         }
     }
 }
 
+impl Team {
+    pub fn member(&self, ign: &str) -> Option<&TeamMember> {
+        self.members.iter().find(|m| m.ign.eq_ignore_ascii_case(ign.trim()))
+    }
+
+    pub fn field(&self, key: &str) -> Option<&CustomField> {
+        let key = field_key(key);
+        self.custom_fields.iter().find(|f| f.key == key)
+    }
+
+    pub fn squad(&self, name: &str) -> Option<&Squad> {
+        self.squads
+            .iter()
+            .find(|s| s.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Whether `member` leads the squad they're in.
+    pub fn is_lead(&self, member: &TeamMember) -> bool {
+        self.squad(&member.squad)
+            .is_some_and(|s| s.is_lead(&member.ign))
+    }
+
+    /// Members of `squad`, leads first.
+    pub fn squad_members(&self, squad: &str) -> Vec<&TeamMember> {
+        let mut members: Vec<&TeamMember> = self
+            .members
+            .iter()
+            .filter(|m| m.squad.eq_ignore_ascii_case(squad.trim()))
+            .collect();
+        members.sort_by_key(|m| !self.is_lead(m));
+        members
+    }
+
+    /// Keeps only the members of `squad`.
+    pub fn only_squad(mut self, squad: &str) -> Team {
+        self.members
+            .retain(|m| m.squad.eq_ignore_ascii_case(squad.trim()));
+        self
+    }
+
+    /// Members grouped by squad in the order the squads were created, with
+    /// those in none last under `None`. Empty squads are left out.
+    pub fn by_squad(&self) -> Vec<(Option<&Squad>, Vec<&TeamMember>)> {
+        let mut groups: Vec<(Option<&Squad>, Vec<&TeamMember>)> = self
+            .squads
+            .iter()
+            .map(|squad| (Some(squad), self.squad_members(&squad.name)))
+            .collect();
+        let unassigned: Vec<&TeamMember> = self
+            .members
+            .iter()
+            .filter(|m| self.squad(&m.squad).is_none())
+            .collect();
+        groups.push((None, unassigned));
+        groups.retain(|(_, members)| !members.is_empty());
+        groups
+    }
+}
+
+/// Everything `/team-profile` shows about a member.
+#[derive(Debug, Clone)]
+pub struct Profile {
+    pub member: TeamMember,
+    pub fields: Vec<CustomField>,
+    pub presence: Presence,
+    pub stats: Option<PlayerStats>,
+    /// Discord account linked to the IGN with `/link`.
+    pub discord_id: Option<u64>,
+    /// Whether they lead their squad.
+    pub lead: bool,
+    /// When the profile was fetched, for the member's local time.
+    pub at: DateTime<Utc>,
+}
+
+/// Members per country for `/team-countries`.
+#[derive(Debug, Clone)]
+pub struct CountryBreakdown {
+    pub team_name: String,
+    /// Stored country, empty when unknown, with the IGNs from there. Most
+    /// members first; members without a country last.
+    pub countries: Vec<(String, Vec<String>)>,
+}
+
+impl CountryBreakdown {
+    pub fn of(team: &Team) -> CountryBreakdown {
+        let mut by_country: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for member in &team.members {
+            by_country
+                .entry(member.country.trim().to_string())
+                .or_default()
+                .push(member.ign.clone());
+        }
+        let mut countries: Vec<(String, Vec<String>)> = by_country.into_iter().collect();
+        for (_, igns) in &mut countries {
+            igns.sort_by_key(|ign| ign.to_ascii_lowercase());
+        }
+        countries
+            .sort_by_key(|(country, igns)| (country.is_empty(), std::cmp::Reverse(igns.len())));
+        CountryBreakdown {
+            team_name: team.name.clone(),
+            countries,
+        }
+    }
+}
+
+/// `Favourite Farm` -> `favourite-farm`
+pub fn field_key(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
 fn store_path() -> PathBuf {
     if let Ok(p) = std::env::var("TEAM_STORE_PATH") {
         return PathBuf::from(p);
@@ -108,91 +562,466 @@ pub fn save(team: &Team) -> io::Result<()> {
     fs::write(path, json)
 }
 
-pub fn set_name(new_name: &str) -> io::Result<Team> {
+/// Saves a change made by the Discord user `by`, logging it and keeping the
+/// roster as it was so the change can be undone. Once the roster is saved,
+/// failing to log it is only reported.
+fn save_change(before: &Team, team: &Team, by: u64) -> io::Result<()> {
+    save(team)?;
+    let changes = Change::between(before, team);
+    if changes.is_empty() {
+        return Ok(());
+    }
+    if let Err(e) = audit::record(by, changes) {
+        tracing::warn!("Failed to record roster change: {:?}", e);
+    }
+    if let Err(e) = history::push(before, by) {
+        tracing::warn!("Failed to save roster snapshot: {:?}", e);
+    }
+    Ok(())
+}
+
+pub fn set_name(new_name: &str, by: u64) -> io::Result<Team> {
     let mut team = load();
+    let before = team.clone();
     team.name = new_name.trim().to_string();
-    save(&team)?;
+    save_change(&before, &team, by)?;
     Ok(team)
 }
 
-pub fn upsert_member(member: TeamMember) -> io::Result<(Team, bool)> {
+/// Adds or replaces a member. Custom field values, the squad and the join date
+/// carry over from an existing entry unless given; new members join today.
+pub fn upsert_member(mut member: TeamMember, by: u64) -> io::Result<(Team, bool)> {
     let mut team = load();
+    let before = team.clone();
     let mut updated = false;
     if let Some(existing) = team
         .members
         .iter_mut()
         .find(|m| m.ign.eq_ignore_ascii_case(&member.ign))
     {
+        member.joined = member.joined.or(existing.joined);
+        if member.squad.is_empty() {
+            member.squad = std::mem::take(&mut existing.squad);
+        }
+        let mut custom = std::mem::take(&mut existing.custom);
+        custom.append(&mut member.custom);
+        member.custom = custom;
         *existing = member;
         updated = true;
     } else {
+        member.joined = member.joined.or_else(|| Some(Utc::now().date_naive()));
         team.members.push(member);
     }
-    save(&team)?;
+    save_change(&before, &team, by)?;
+    Ok((team, updated))
+}
+
+/// Changes to an existing member. Fields left `None` are kept as they are.
+#[derive(Debug, Clone, Default)]
+pub struct MemberUpdate {
+    pub country: Option<String>,
+    pub skills: Option<Vec<String>>,
+    pub rank: Option<Rank>,
+    pub about: Option<String>,
+    pub discord_tag: Option<String>,
+    pub timezone: Option<String>,
+    pub joined: Option<NaiveDate>,
+    pub base: Option<String>,
+}
+
+impl MemberUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.country.is_none()
+            && self.skills.is_none()
+            && self.rank.is_none()
+            && self.about.is_none()
+            && self.discord_tag.is_none()
+            && self.timezone.is_none()
+            && self.joined.is_none()
+            && self.base.is_none()
+    }
+
+    pub fn apply(self, member: &mut TeamMember) {
+        if let Some(country) = self.country {
+            member.country = country;
+        }
+        if let Some(skills) = self.skills {
+            member.skills = skills;
+        }
+        if let Some(rank) = self.rank {
+            member.rank = rank;
+        }
+        if let Some(about) = self.about {
+            member.about = about;
+        }
+        if let Some(discord_tag) = self.discord_tag {
+            member.discord_tag = discord_tag;
+        }
+        if let Some(timezone) = self.timezone {
+            member.timezone = timezone;
+        }
+        if self.joined.is_some() {
+            member.joined = self.joined;
+        }
+        if let Some(base) = self.base {
+            member.base = base;
+        }
+    }
+}
+
+/// Applies `update` to an existing member. Returns the member as saved, or
+/// `None` when nobody on the team has that IGN.
+pub fn update_member(
+    ign: &str,
+    update: MemberUpdate,
+    by: u64,
+) -> io::Result<(Team, Option<TeamMember>)> {
+    let mut team = load();
+    let before = team.clone();
+    let updated = match team
+        .members
+        .iter_mut()
+        .find(|m| m.ign.eq_ignore_ascii_case(ign.trim()))
+    {
+        Some(member) => {
+            update.apply(member);
+            Some(member.clone())
+        }
+        None => None,
+    };
+    if updated.is_some() {
+        save_change(&before, &team, by)?;
+    }
     Ok((team, updated))
 }
 
-pub fn remove_member(ign: &str) -> io::Result<(Team, bool)> {
+/// Replaces the whole roster, as `/team-import` does. New members without a
+/// join date joined today.
+pub fn replace_members(mut members: Vec<TeamMember>, by: u64) -> io::Result<Team> {
+    let mut team = load();
+    let before = team.clone();
+    let today = Utc::now().date_naive();
+    for member in &mut members {
+        if team.member(&member.ign).is_none() {
+            member.joined = member.joined.or(Some(today));
+        }
+    }
+    team.members = members;
+    save_change(&before, &team, by)?;
+    Ok(team)
+}
+
+pub fn remove_member(ign: &str, by: u64) -> io::Result<(Team, bool)> {
     let mut team = load();
-    let orig_len = team.members.len();
+    let before = team.clone();
     team.members.retain(|m| !m.ign.eq_ignore_ascii_case(ign));
-    let removed = team.members.len() != orig_len;
-    save(&team)?;
+    let removed = team.members.len() != before.members.len();
+    save_change(&before, &team, by)?;
     Ok((team, removed))
 }
 
-fn flag_from_code(code: &str) -> Option<String> {
-    if code.len() != 2 {
-        return None;
+/// Adds a custom field, or changes the label and kind of an existing one.
+/// Returns whether it already existed.
+pub fn define_field(field: CustomField, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    let existed = match team.custom_fields.iter_mut().find(|f| f.key == field.key) {
+        Some(existing) => {
+            *existing = field;
+            true
+        }
+        None => {
+            team.custom_fields.push(field);
+            false
+        }
+    };
+    save_change(&before, &team, by)?;
+    Ok((team, existed))
+}
+
+/// Removes a custom field together with every member's value for it.
+pub fn remove_field(key: &str, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    let key = field_key(key);
+    team.custom_fields.retain(|f| f.key != key);
+    let removed = team.custom_fields.len() != before.custom_fields.len();
+    for member in &mut team.members {
+        member.custom.remove(&key);
+    }
+    save_change(&before, &team, by)?;
+    Ok((team, removed))
+}
+
+/// Sets (or with `None`, clears) one member's value for a custom field.
+/// Returns whether the member was found.
+pub fn set_custom_value(
+    ign: &str,
+    key: &str,
+    value: Option<String>,
+    by: u64,
+) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    let key = field_key(key);
+    let found = match team
+        .members
+        .iter_mut()
+        .find(|m| m.ign.eq_ignore_ascii_case(ign.trim()))
+    {
+        Some(member) => {
+            match value {
+                Some(value) => member.custom.insert(key, value),
+                None => member.custom.remove(&key),
+            };
+            true
+        }
+        None => false,
+    };
+    if found {
+        save_change(&before, &team, by)?;
+    }
+    Ok((team, found))
+}
+
+/// Adds a squad. Returns whether one with that name already existed, in which
+/// case nothing changes.
+pub fn create_squad(name: &str, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    if team.squad(name).is_some() {
+        return Ok((team, true));
+    }
+    team.squads.push(Squad {
+        name: name.trim().to_string(),
+        leads: Vec::new(),
+    });
+    save_change(&before, &team, by)?;
+    Ok((team, false))
+}
+
+/// Removes a squad. Its members stay on the team, without a squad.
+pub fn remove_squad(name: &str, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    team.squads
+        .retain(|s| !s.name.eq_ignore_ascii_case(name.trim()));
+    let removed = team.squads.len() != before.squads.len();
+    for member in &mut team.members {
+        if member.squad.eq_ignore_ascii_case(name.trim()) {
+            member.squad.clear();
+        }
+    }
+    save_change(&before, &team, by)?;
+    Ok((team, removed))
+}
+
+/// Moves a member into the squad named `squad`, or out of theirs when it's
+/// empty, and stops them leading the squad they leave. Returns whether the
+/// member was found.
+fn move_to_squad(team: &mut Team, ign: &str, squad: &str) -> bool {
+    let squad = team
+        .squad(squad)
+        .map(|s| s.name.clone())
+        .unwrap_or_default();
+    let member = match team
+        .members
+        .iter_mut()
+        .find(|m| m.ign.eq_ignore_ascii_case(ign.trim()))
+    {
+        Some(member) => member,
+        None => return false,
+    };
+    if member.squad.eq_ignore_ascii_case(&squad) {
+        return true;
+    }
+    let ign = member.ign.clone();
+    let previous = std::mem::replace(&mut member.squad, squad);
+    if let Some(old) = team
+        .squads
+        .iter_mut()
+        .find(|s| s.name.eq_ignore_ascii_case(&previous))
+    {
+        old.leads.retain(|l| !l.eq_ignore_ascii_case(&ign));
+    }
+    true
+}
+
+/// Moves a member into `squad`, or with `None` out of theirs. The squad must
+/// exist. Returns whether the member was found.
+pub fn assign_squad(ign: &str, squad: Option<&str>, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    let found = move_to_squad(&mut team, ign, squad.unwrap_or_default());
+    if found {
+        save_change(&before, &team, by)?;
     }
-    let mut res = String::new();
-    for ch in code.chars() {
-        let u = ch.to_ascii_uppercase();
-        if !u.is_ascii_alphabetic() {
-            return None;
-        }
-        let base: u32 = 'A' as u32;
-        let ri_offset: u32 = 0x1F1E6;
-        let cp = ri_offset + (u as u32 - base);
-        if let Some(c) = char::from_u32(cp) {
-            res.push(c);
-        }
-    }
-    Some(res)
-}
-
-pub fn country_flag(input: &str) -> String {
-    if input.is_empty() {
-        return String::new();
-    }
-    let trimmed = input.trim();
-    if let Some(flag) = flag_from_code(trimmed) {
-        return flag;
-    }
-    let name = trimmed.to_ascii_lowercase();
-    let code = match name.as_str() {
-        "belgium" | "belgie" | "belgië" => "BE",
-        "netherlands" | "the netherlands" | "holland" | "nederland" => "NL",
-        "united kingdom" | "uk" | "great britain" | "england" | "scotland" | "wales"
-        | "northern ireland" => "GB",
-        "united states" | "usa" | "us" | "america" => "US",
-        "germany" | "deutschland" => "DE",
-        "france" => "FR",
-        "spain" | "españa" => "ES",
-        "italy" | "italia" => "IT",
-        "canada" => "CA",
-        "australia" => "AU",
-        "ireland" => "IE",
-        "poland" => "PL",
-        "portugal" => "PT",
-        "sweden" => "SE",
-        "norway" => "NO",
-        "denmark" => "DK",
-        "finland" => "FI",
-        "luxembourg" => "LU",
-        "switzerland" => "CH",
-        "austria" => "AT",
-        _ => "",
+    Ok((team, found))
+}
+
+/// Makes a member one of the leads of `squad`, moving them into it first if
+/// needed, or with `lead` false no longer one. Returns whether the member was
+/// found.
+pub fn set_squad_lead(squad: &str, ign: &str, lead: bool, by: u64) -> io::Result<(Team, bool)> {
+    let mut team = load();
+    let before = team.clone();
+    let ign = match team.member(ign) {
+        Some(member) => member.ign.clone(),
+        None => return Ok((team, false)),
+    };
+    if lead {
+        move_to_squad(&mut team, &ign, squad);
+    }
+    if let Some(squad) = team
+        .squads
+        .iter_mut()
+        .find(|s| s.name.eq_ignore_ascii_case(squad.trim()))
+    {
+        squad.leads.retain(|l| !l.eq_ignore_ascii_case(&ign));
+        if lead {
+            squad.leads.push(ign);
+        }
+    }
+    save_change(&before, &team, by)?;
+    Ok((team, true))
+}
+
+/// Puts the roster back as it was before the last change, forgetting that
+/// change. Returns the snapshot restored and what it changed, or `None` when
+/// there's nothing to undo.
+pub fn undo(by: u64) -> io::Result<Option<(history::Snapshot, Vec<Change>)>> {
+    let snapshot = match history::latest() {
+        Some(snapshot) => snapshot,
+        None => return Ok(None),
+    };
+    let current = load();
+    // Only forget the snapshot once the roster is back, so a failed write loses nothing
+    save(&snapshot.team)?;
+    history::pop()?;
+    let changes = Change::between(&current, &snapshot.team);
+    if let Err(e) = audit::record(by, changes.clone()) {
+        tracing::warn!("Failed to record roster change: {:?}", e);
+    }
+    Ok(Some((snapshot, changes)))
+}
+
+/// Restores the roster saved as `version`. Unlike an undo this is a change of
+/// its own, so it can be undone in turn.
+pub fn restore(version: u64, by: u64) -> io::Result<Option<Team>> {
+    let snapshot = match history::get(version) {
+        Some(snapshot) => snapshot,
+        None => return Ok(None),
     };
-    flag_from_code(code).unwrap_or_default()
+    let before = load();
+    save_change(&before, &snapshot.team, by)?;
+    Ok(Some(snapshot.team))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn old_single_skill_still_loads() {
+        let member: TeamMember = serde_json::from_str(
+            r#"{"ign":"Steve","country":"Holland","skill":"PvP, building","about":"","discord_tag":""}"#,
+        )
+        .unwrap();
+        assert_eq!(member.country, "NL");
+        assert_eq!(member.skills, vec!["PvP", "building"]);
+        assert_eq!(member.joined, None);
+    }
+
+    #[test]
+    fn timezone_offsets() {
+        let minutes = |tz: &str| utc_offset(tz).map(|o| o.local_minus_utc() / 60);
+        assert_eq!(minutes("UTC+2"), Some(120));
+        assert_eq!(minutes("gmt-05:30"), Some(-330));
+        assert_eq!(minutes("+0100"), Some(60));
+        assert_eq!(minutes("UTC"), Some(0));
+        assert_eq!(minutes("Europe/Amsterdam"), None);
+    }
+
+    #[test]
+    fn field_values_are_validated() {
+        assert_eq!(FieldKind::Number.normalize("1,5"), Ok("1.5".to_string()));
+        assert_eq!(FieldKind::Date.normalize("2024-02-30"), Err("field.invalid_date"));
+        assert_eq!(FieldKind::Toggle.normalize("Ja"), Ok("yes".to_string()));
+        assert_eq!(FieldKind::Link.normalize("example.com"), Err("field.invalid_link"));
+    }
+
+    #[test]
+    fn edits_only_touch_given_fields() {
+        let mut member = TeamMember {
+            ign: "Steve".into(),
+            country: "NL".into(),
+            skills: vec!["PvP".into()],
+            about: "Builds farms".into(),
+            rank: Rank::Admin,
+            ..TeamMember::default()
+        };
+        let update = MemberUpdate {
+            skills: Some(parse_skills("Redstone, trading")),
+            about: Some(String::new()),
+            ..MemberUpdate::default()
+        };
+        assert!(!update.is_empty());
+        update.apply(&mut member);
+        assert_eq!(member.country, "NL");
+        assert_eq!(member.skills, vec!["Redstone", "trading"]);
+        assert_eq!(member.about, "");
+        assert_eq!(member.rank, Rank::Admin);
+        assert!(MemberUpdate::default().is_empty());
+    }
+
+    #[test]
+    fn squads_group_members_with_leads_first() {
+        let member = |ign: &str, squad: &str| TeamMember {
+            ign: ign.into(),
+            squad: squad.into(),
+            ..TeamMember::default()
+        };
+        let mut team = Team {
+            members: vec![
+                member("Steve", "builders"),
+                member("Alex", "Builders"),
+                member("Notch", ""),
+                member("Herobrine", "PvP"),
+            ],
+            squads: vec![
+                Squad {
+                    name: "Builders".into(),
+                    leads: vec!["alex".into()],
+                },
+                Squad {
+                    name: "Farmers".into(),
+                    leads: vec![],
+                },
+            ],
+            ..Team::default()
+        };
+        let groups: Vec<(Option<&str>, Vec<&str>)> = team
+            .by_squad()
+            .into_iter()
+            .map(|(squad, members)| {
+                (
+                    squad.map(|s| s.name.as_str()),
+                    members.iter().map(|m| m.ign.as_str()).collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            groups,
+            vec![
+                (Some("Builders"), vec!["Alex", "Steve"]),
+                (None, vec!["Notch", "Herobrine"]),
+            ]
+        );
+
+        // Leaving a squad also ends leading it
+        assert!(move_to_squad(&mut team, "ALEX", "farmers"));
+        assert_eq!(team.members[1].squad, "Farmers");
+        assert!(team.squads[0].leads.is_empty());
+        assert!(!move_to_squad(&mut team, "Dinnerbone", "Farmers"));
+    }
 }
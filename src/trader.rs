@@ -0,0 +1,258 @@
+use reqwest::Client;
+use serenity::{
+    model::prelude::interaction::application_command::ApplicationCommandInteraction, prelude::*,
+};
+use std::collections::HashMap;
+use tracing::warn;
+
+use crate::api::fetch_auction_json;
+use crate::auction::{parse_listings, parse_sales, Listing};
+use crate::locale::Format;
+use crate::market::SaleRecord;
+use crate::render::Render;
+
+/// How many auction list / transaction pages `/trader` scans live.
+const SCAN_PAGES: u32 = 5;
+
+/// Names with how often they appear, most frequent first.
+pub type Ranked = Vec<(String, usize)>;
+
+/// A player's trading activity: live listings plus their ingested sales and purchases.
+pub struct TraderProfile {
+    pub ign: String,
+    pub listings: Vec<Listing>,
+    pub sales: Vec<SaleRecord>,
+    pub purchases: Vec<SaleRecord>,
+}
+
+impl TraderProfile {
+    pub fn total_volume(&self) -> i64 {
+        self.sales
+            .iter()
+            .chain(&self.purchases)
+            .map(|s| s.price)
+            .sum()
+    }
+
+    pub fn average_sale_price(&self) -> Option<i64> {
+        if self.sales.is_empty() {
+            return None;
+        }
+        let total: i64 = self.sales.iter().map(|s| s.price).sum();
+        Some(total / self.sales.len() as i64)
+    }
+
+    /// Items by number of trades (sales and purchases), most traded first.
+    pub fn most_traded(&self) -> Ranked {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for record in self.sales.iter().chain(&self.purchases) {
+            *counts.entry(record.item_id.as_str()).or_default() += 1;
+        }
+        let mut items: Ranked = counts
+            .into_iter()
+            .map(|(id, n)| (id.replace("minecraft:", "").replace("_", " "), n))
+            .collect();
+        items.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        items
+    }
+
+    /// Their edges in the buyer/seller graph: who they sold to and bought from.
+    pub fn partners(&self) -> (Ranked, Ranked) {
+        fn rank(names: Vec<&str>) -> Ranked {
+            let mut counts: HashMap<String, (String, usize)> = HashMap::new();
+            for name in names {
+                counts
+                    .entry(name.to_ascii_lowercase())
+                    .or_insert_with(|| (name.to_string(), 0))
+                    .1 += 1;
+            }
+            let mut ranked: Ranked = counts.into_values().collect();
+            ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            ranked
+        }
+        let buyers = rank(
+            self.sales
+                .iter()
+                .filter_map(|s| s.buyer.as_deref())
+                .collect(),
+        );
+        let sellers = rank(self.purchases.iter().map(|s| s.seller.as_str()).collect());
+        (buyers, sellers)
+    }
+}
+
+/// Builds a profile from the live auction list and the local transaction history.
+/// Fresh transaction pages are ingested first so the history is up to date.
+pub async fn load_profile(
+    client: &Client,
+    donut_key: &str,
+    ign: &str,
+) -> Result<TraderProfile, Box<dyn std::error::Error + Send + Sync>> {
+    let mut listings = Vec::new();
+    for page in 1..=SCAN_PAGES {
+        let json = fetch_auction_json(
+            client,
+            donut_key,
+            &format!("/v1/auction/list/{}", page),
+            None,
+            None,
+        )
+        .await?;
+        let page_listings = parse_listings(&json);
+        if page_listings.is_empty() {
+            break;
+        }
+        listings.extend(
+            page_listings
+                .into_iter()
+                .filter(|l| l.seller.eq_ignore_ascii_case(ign)),
+        );
+    }
+
+    for page in 1..=SCAN_PAGES {
+        let path = format!("/v1/auction/transactions/{}", page);
+        let json = fetch_auction_json(client, donut_key, &path, None, None).await?;
+        let sales = parse_sales(&json);
+        if sales.is_empty() {
+            break;
+        }
+        if let Err(e) = crate::market::ingest(&sales) {
+            warn!("Failed to store market history: {:?}", e);
+        }
+    }
+
+    let data = crate::market::load();
+    let mut sales: Vec<SaleRecord> = data
+        .sales
+        .iter()
+        .filter(|s| s.seller.eq_ignore_ascii_case(ign))
+        .cloned()
+        .collect();
+    let mut purchases: Vec<SaleRecord> = data
+        .sales
+        .iter()
+        .filter(|s| {
+            s.buyer
+                .as_deref()
+                .is_some_and(|b| b.eq_ignore_ascii_case(ign))
+        })
+        .cloned()
+        .collect();
+    sales.sort_by_key(|s| std::cmp::Reverse(s.sold_at));
+    purchases.sort_by_key(|s| std::cmp::Reverse(s.sold_at));
+
+    Ok(TraderProfile {
+        ign: ign.to_string(),
+        listings,
+        sales,
+        purchases,
+    })
+}
+
+pub async fn send_trader(
+    cmd: &ApplicationCommandInteraction,
+    ctx: &Context,
+    client: &Client,
+    donut_key: &str,
+    ign: &str,
+    fmt: &Format,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Immediately ack with a deferred response (allows more than 3s processing)
+    cmd.create_interaction_response(&ctx.http, |resp| resp.kind(serenity::model::prelude::interaction::InteractionResponseType::DeferredChannelMessageWithSource)).await?;
+
+    match load_profile(client, donut_key, ign).await {
+        Ok(profile) => {
+            let embed = profile.render(fmt).to_embed();
+            cmd.edit_original_interaction_response(&ctx.http, |r| r.add_embed(embed))
+                .await?;
+        }
+        Err(e) => {
+            let error_msg = fmt.tf("trader.load_failed", &[&e]);
+            cmd.edit_original_interaction_response(&ctx.http, |response| {
+                response.embed(|e| {
+                    e.title(fmt.t("common.error"))
+                        .description(error_msg)
+                        .color(crate::constants::EMBED_COLOR_ERROR)
+                })
+            })
+            .await?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(item: &str, price: i64, seller: &str, buyer: Option<&str>) -> SaleRecord {
+        SaleRecord {
+            item_key: format!("minecraft:{}", item),
+            item_id: format!("minecraft:{}", item),
+            name: item.replace('_', " "),
+            label: item.replace('_', " "),
+            count: 1,
+            price,
+            seller: seller.into(),
+            buyer: buyer.map(str::to_string),
+            sold_at: 1_700_000_000,
+            ingested_at: false,
+        }
+    }
+
+    fn profile() -> TraderProfile {
+        TraderProfile {
+            ign: "Steve".into(),
+            listings: Vec::new(),
+            sales: vec![
+                record("diamond_block", 1_000, "Steve", Some("Alex")),
+                record("diamond_block", 2_000, "Steve", Some("alex")),
+                record("elytra", 3_000, "Steve", Some("Notch")),
+                record("dirt", 1, "Steve", None),
+            ],
+            purchases: vec![
+                record("elytra", 2_500, "Notch", Some("Steve")),
+                record("golden_apple", 500, "Herobrine", Some("Steve")),
+            ],
+        }
+    }
+
+    #[test]
+    fn volume_counts_sales_and_purchases() {
+        let profile = profile();
+        assert_eq!(profile.total_volume(), 9_001);
+        assert_eq!(profile.average_sale_price(), Some(1_500));
+        let quiet = TraderProfile {
+            sales: Vec::new(),
+            ..profile
+        };
+        assert_eq!(quiet.average_sale_price(), None);
+    }
+
+    #[test]
+    fn most_traded_counts_both_sides() {
+        assert_eq!(
+            profile().most_traded(),
+            vec![
+                ("diamond block".to_string(), 2),
+                ("elytra".to_string(), 2),
+                ("dirt".to_string(), 1),
+                ("golden apple".to_string(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn partners_merge_names_case_insensitively() {
+        let (buyers, sellers) = profile().partners();
+        assert_eq!(
+            buyers,
+            vec![("Alex".to_string(), 2), ("Notch".to_string(), 1)]
+        );
+        assert_eq!(
+            sellers,
+            vec![("Herobrine".to_string(), 1), ("Notch".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use reqwest::Client;
+use serenity::{model::prelude::ChannelId, prelude::*};
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tracing::{error, warn};
+
+use crate::api::fetch_auction_json;
+use crate::auction::{parse_listings, Listing};
+
+/// A team member's listing together with the cheapest competing listing.
+struct Undercut {
+    ours: Listing,
+    theirs: Listing,
+}
+
+impl Undercut {
+    fn unit_diff(&self) -> f64 {
+        self.ours.unit_price() - self.theirs.unit_price()
+    }
+
+    fn percent(&self) -> f64 {
+        self.unit_diff() / self.ours.unit_price() * 100.0
+    }
+}
+
+/// Periodically scans the auction house for team members' listings and
+/// posts an alert to `channel_id` whenever someone lists the same item
+/// (same ID and enchant set) for a lower per-unit price.
+pub async fn run(
+    ctx: Context,
+    http_client: Client,
+    api_key: String,
+    channel_id: u64,
+    interval_minutes: u64,
+    scan_pages: u32,
+) {
+    // (seller, item key) -> competing unit price we last alerted about
+    let mut notified: HashMap<(String, String), f64> = HashMap::new();
+    loop {
+        let (undercuts, complete) = find_undercuts(&http_client, &api_key, scan_pages).await;
+        let mut active: HashSet<(String, String)> = HashSet::new();
+        for undercut in undercuts {
+            let key = (
+                undercut.ours.seller.to_ascii_lowercase(),
+                undercut.ours.item_key(),
+            );
+            active.insert(key.clone());
+            let theirs = undercut.theirs.unit_price();
+            if notified.get(&key).is_some_and(|last| theirs >= *last) {
+                continue;
+            }
+            match post_alert(&ctx, channel_id, &undercut).await {
+                Ok(()) => {
+                    notified.insert(key, theirs);
+                }
+                Err(e) => error!("Failed to post undercut alert: {:?}", e),
+            }
+        }
+        // Forget listings that sold, expired or are no longer undercut. A
+        // partial scan can't tell those apart from listings it didn't reach.
+        if complete {
+            notified.retain(|key, _| active.contains(key));
+        }
+
+        tokio::time::sleep(Duration::from_secs(interval_minutes.saturating_mul(60))).await;
+    }
+}
+
+/// `ours` against the cheapest other seller's listing of the same item and
+/// enchants, when that one is cheaper per unit.
+fn undercut(ours: &Listing, competitors: &[Listing]) -> Option<Undercut> {
+    let key = ours.item_key();
+    let theirs = competitors
+        .iter()
+        .filter(|c| c.item_key() == key && !c.seller.eq_ignore_ascii_case(&ours.seller))
+        .min_by(|a, b| a.unit_price().total_cmp(&b.unit_price()))?;
+    (theirs.unit_price() < ours.unit_price()).then(|| Undercut {
+        theirs: theirs.clone(),
+        ours: ours.clone(),
+    })
+}
+
+/// Finds undercut team listings. A failed page or search is logged and
+/// skipped so the rest of the scan still alerts; the returned flag is false
+/// when that happened.
+async fn find_undercuts(
+    http_client: &Client,
+    api_key: &str,
+    scan_pages: u32,
+) -> (Vec<Undercut>, bool) {
+    let roster: HashSet<String> = crate::team::load()
+        .members
+        .iter()
+        .map(|m| m.ign.to_ascii_lowercase())
+        .collect();
+    if roster.is_empty() {
+        return (Vec::new(), true);
+    }
+
+    let mut complete = true;
+    let mut ours: Vec<Listing> = Vec::new();
+    for page in 1..=scan_pages {
+        let path = format!("/v1/auction/list/{}", page);
+        let json = match fetch_auction_json(http_client, api_key, &path, None, None).await {
+            Ok(json) => json,
+            Err(e) => {
+                warn!("Undercut scan failed on page {}: {:?}", page, e);
+                complete = false;
+                break;
+            }
+        };
+        let listings = parse_listings(&json);
+        if listings.is_empty() {
+            break;
+        }
+        ours.extend(
+            listings
+                .into_iter()
+                .filter(|l| roster.contains(&l.seller.to_ascii_lowercase())),
+        );
+    }
+
+    // One lowest-price search per distinct item name covers every listing of it
+    let mut cheapest_by_name: HashMap<String, Vec<Listing>> = HashMap::new();
+    let mut undercuts = Vec::new();
+    for listing in ours {
+        if !cheapest_by_name.contains_key(&listing.display_name) {
+            let competitors = match fetch_auction_json(
+                http_client,
+                api_key,
+                "/v1/auction/list/1",
+                Some(&listing.display_name),
+                Some("lowest_price"),
+            )
+            .await
+            {
+                Ok(json) => parse_listings(&json),
+                Err(e) => {
+                    // Cache the miss so other listings of this item don't retry it
+                    warn!(
+                        "Undercut search for {} failed: {:?}",
+                        listing.display_name, e
+                    );
+                    complete = false;
+                    Vec::new()
+                }
+            };
+            cheapest_by_name.insert(listing.display_name.clone(), competitors);
+        }
+        undercuts.extend(undercut(&listing, &cheapest_by_name[&listing.display_name]));
+    }
+    (undercuts, complete)
+}
+
+async fn post_alert(
+    ctx: &Context,
+    channel_id: u64,
+    undercut: &Undercut,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let seller = &undercut.ours.seller;
+    let mention = crate::players::discord_id_for(seller)
+        .map(|id| format!("<@{}>", id))
+        .unwrap_or_else(|| format!("**{}**", seller));
+    let unit_diff = undercut.unit_diff().round() as i64;
+    let percent = undercut.percent();
+    let fmt = crate::settings::guild_format();
+
+    ChannelId(channel_id)
+        .send_message(&ctx.http, |m| {
+            m.content(fmt.tf("undercut.mention", &[&mention]))
+                .embed(|e| {
+                    e.title(fmt.tf("undercut.title", &[&undercut.ours.label()]))
+                        .color(crate::constants::EMBED_COLOR_ERROR_ALT)
+                        .field(
+                            fmt.t("undercut.yours"),
+                            fmt.tf(
+                                "listing.price",
+                                &[
+                                    &fmt.money(undercut.ours.price),
+                                    &fmt.money(undercut.ours.unit_price().round() as i64),
+                                ],
+                            ),
+                            true,
+                        )
+                        .field(
+                            fmt.t("undercut.theirs"),
+                            format!(
+                                "{}\n└ *{}*",
+                                fmt.tf(
+                                    "listing.price",
+                                    &[
+                                        &fmt.money(undercut.theirs.price),
+                                        &fmt.money(undercut.theirs.unit_price().round() as i64),
+                                    ],
+                                ),
+                                fmt.tf("auction.seller", &[&undercut.theirs.seller])
+                            ),
+                            true,
+                        )
+                        .field(
+                            fmt.t("undercut.by"),
+                            fmt.tf(
+                                "undercut.by_value",
+                                &[&fmt.money(unit_diff), &format!("{:.1}", percent)],
+                            ),
+                            false,
+                        )
+                })
+        })
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn listing(seller: &str, price: i64, count: i64, enchants: &[(&str, i64)]) -> Listing {
+        Listing {
+            item_id: "minecraft:diamond_sword".into(),
+            display_name: "Diamond Sword".into(),
+            count,
+            enchants: enchants
+                .iter()
+                .map(|&(name, level)| (name.to_string(), level))
+                .collect::<BTreeMap<_, _>>(),
+            price,
+            seller: seller.into(),
+        }
+    }
+
+    #[test]
+    fn cheapest_other_seller_of_the_same_item_undercuts() {
+        let ours = listing("Steve", 1_000, 1, &[("sharpness", 5)]);
+        let competitors = vec![
+            listing("Alex", 900, 1, &[("sharpness", 5)]),
+            listing("Notch", 800, 1, &[("sharpness", 5)]),
+            // Different enchants and our own listings don't count
+            listing("Herobrine", 100, 1, &[("sharpness", 4)]),
+            listing("steve", 50, 1, &[("sharpness", 5)]),
+        ];
+        let found = undercut(&ours, &competitors).unwrap();
+        assert_eq!(found.theirs.seller, "Notch");
+        assert_eq!(found.unit_diff(), 200.0);
+        assert_eq!(found.percent(), 20.0);
+    }
+
+    #[test]
+    fn prices_are_compared_per_unit() {
+        // 64 for 6.400 is 100 each; 10 for 1.200 is 120 each
+        let ours = listing("Steve", 6_400, 64, &[]);
+        let competitors = vec![listing("Alex", 1_200, 10, &[])];
+        assert!(undercut(&ours, &competitors).is_none());
+
+        let competitors = vec![listing("Alex", 900, 10, &[])];
+        assert_eq!(undercut(&ours, &competitors).unwrap().unit_diff(), 10.0);
+    }
+
+    #[test]
+    fn equal_prices_are_not_undercuts() {
+        let ours = listing("Steve", 500, 1, &[]);
+        assert!(undercut(&ours, &[listing("Alex", 500, 1, &[])]).is_none());
+    }
+}